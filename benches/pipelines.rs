@@ -0,0 +1,109 @@
+// Compares the regex rewrite pipeline that `nsc build` uses today with the
+// tokenizer front end the transpiler is migrating to, on the same corpora.
+//
+//     cargo bench --bench pipelines
+//     cargo bench --bench pipelines -- --save-baseline main
+//     cargo bench --bench pipelines -- --baseline main
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use nullscript::compiler::lexer::{Lexer, TokenKind};
+use nullscript::compiler::NullScriptTranspiler;
+use nullscript::core::keywords::KEYWORDS;
+use std::collections::HashMap;
+use std::path::Path;
+
+fn examples() -> Vec<String> {
+    let mut paths: Vec<_> = std::fs::read_dir(Path::new(env!("CARGO_MANIFEST_DIR")).join("tests"))
+        .expect("tests/ directory")
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "ns"))
+        .collect();
+    paths.sort();
+    paths.iter().map(|path| std::fs::read_to_string(path).expect("readable example")).collect()
+}
+
+fn repeat_to_lines(unit: &str, lines: usize) -> String {
+    let unit_lines = unit.lines().count().max(1);
+    unit.repeat(lines.div_ceil(unit_lines))
+}
+
+fn class_heavy(classes: usize) -> String {
+    (0..classes)
+        .map(|i| {
+            format!(
+                "model Shape{i} inherits Base {{\n    fixed id;\n    run __init__(id) {{\n        self.id = id;\n    }}\n    run area() {{\n        whatever (self.id more 0) {{\n            return maths.max(self.id, {i});\n        }}\n        return 0;\n    }}\n}}\n\n"
+            )
+        })
+        .collect()
+}
+
+fn string_heavy(lines: usize) -> String {
+    (0..lines)
+        .map(|i| {
+            format!(
+                "fixed message{i} = \"run whatever fixed speak {i}\" + 'otherwise since when' + `model ${{self.name}} is {i}`;\n"
+            )
+        })
+        .collect()
+}
+
+fn corpora() -> Vec<(&'static str, String)> {
+    let examples = examples();
+    let all = examples.concat();
+
+    vec![
+        ("small", examples.into_iter().min_by_key(|source| source.len()).unwrap_or_default()),
+        ("10k-lines", repeat_to_lines(&all, 10_000)),
+        ("class-heavy", class_heavy(500)),
+        ("string-heavy", string_heavy(5_000)),
+    ]
+}
+
+fn tokenizer_pipeline(source: &str, keywords: &HashMap<&str, &str>) -> String {
+    let tokens = Lexer::new(source).with_max_depth(usize::MAX).tokenize().expect("corpus tokenizes");
+    let mut output = String::with_capacity(source.len());
+    let mut member = false;
+
+    for token in tokens {
+        match (token.kind, keywords.get(token.text)) {
+            (TokenKind::Identifier, Some(js)) if !member => output.push_str(js),
+            _ => output.push_str(token.text),
+        }
+        if !token.is_trivia() {
+            member = token.kind == TokenKind::Punct && token.text == ".";
+        }
+    }
+
+    output
+}
+
+fn pipelines(c: &mut Criterion) {
+    let transpiler = NullScriptTranspiler::new();
+    let keywords: HashMap<&str, &str> = KEYWORDS
+        .iter()
+        .filter(|(nullscript_keyword, js_keyword)| nullscript_keyword != js_keyword)
+        .copied()
+        .collect();
+
+    for (name, source) in corpora() {
+        let mut group = c.benchmark_group(name);
+        group.throughput(Throughput::Bytes(source.len() as u64));
+        if source.len() > 100_000 {
+            group.sample_size(20);
+        }
+
+        group.bench_with_input(BenchmarkId::new("regex", source.len()), &source, |b, source| {
+            b.iter(|| transpiler.transpile(black_box(source)).expect("corpus transpiles"))
+        });
+        group.bench_with_input(BenchmarkId::new("tokenizer", source.len()), &source, |b, source| {
+            b.iter(|| tokenizer_pipeline(black_box(source), &keywords))
+        });
+        group.bench_with_input(BenchmarkId::new("lex", source.len()), &source, |b, source| {
+            b.iter(|| Lexer::new(black_box(source)).with_max_depth(usize::MAX).tokenize().expect("corpus tokenizes"))
+        });
+        group.finish();
+    }
+}
+
+criterion_group!(benches, pipelines);
+criterion_main!(benches);
@@ -0,0 +1,24 @@
+#!/usr/bin/env bash
+# Runs the regex vs tokenizer pipeline benchmarks against a saved criterion
+# baseline and fails when any of them regressed beyond the noise threshold.
+#
+#   git checkout main && ./scripts/check-bench.sh --save
+#   git checkout my-branch && ./scripts/check-bench.sh
+set -euo pipefail
+
+BASELINE="${NSC_BENCH_BASELINE:-main}"
+NOISE="${NSC_BENCH_NOISE:-0.05}"
+
+if [[ "${1:-}" == "--save" ]]; then
+  exec cargo bench --bench pipelines -- --save-baseline "$BASELINE"
+fi
+
+log=$(mktemp)
+trap 'rm -f "$log"' EXIT
+cargo bench --bench pipelines -- --baseline "$BASELINE" --noise-threshold "$NOISE" | tee "$log"
+
+if grep -q "Performance has regressed" "$log"; then
+  echo "error: pipeline benchmarks regressed against baseline '${BASELINE}'" >&2
+  grep -B2 "Performance has regressed" "$log" | grep -E "^[a-z0-9-]+/" >&2 || true
+  exit 1
+fi
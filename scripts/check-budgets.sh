@@ -0,0 +1,53 @@
+#!/usr/bin/env bash
+# Fails when the release nsc binary outgrows its size budget or `nsc --version`
+# gets slower to start than editor integrations can afford.
+set -euo pipefail
+
+MAX_BINARY_BYTES="${NSC_MAX_BINARY_BYTES:-5242880}"
+MAX_STARTUP_MS="${NSC_MAX_STARTUP_MS:-20}"
+RUNS="${NSC_STARTUP_RUNS:-21}"
+NSC="${NSC_BINARY:-target/release/nsc}"
+
+if [[ ! -x "$NSC" ]]; then
+  cargo build --release --quiet
+fi
+
+size=$(wc -c < "$NSC" | tr -d ' ')
+echo "binary size: ${size} bytes (budget ${MAX_BINARY_BYTES})"
+
+# Prints how long a command took in microseconds. `date +%s%N` is GNU-only, so
+# this uses bash 5's $EPOCHREALTIME and falls back to perl (bash 3.2 on macOS).
+elapsed_us() {
+  if [[ -n "${EPOCHREALTIME:-}" ]]; then
+    local start=${EPOCHREALTIME/[.,]/}
+    "$@" > /dev/null
+    local end=${EPOCHREALTIME/[.,]/}
+    echo $(( 10#$end - 10#$start ))
+  else
+    perl -MTime::HiRes=time -e '
+      open(my $out, ">&", \*STDOUT) or die;
+      open(STDOUT, ">", "/dev/null") or die;
+      my $start = time;
+      system(@ARGV) == 0 or exit 1;
+      printf $out "%d\n", (time - $start) * 1e6;
+    ' "$@"
+  fi
+}
+
+timings=()
+for _ in $(seq "$RUNS"); do
+  timings+=("$(elapsed_us "$NSC" --version)")
+done
+median_us=$(printf '%s\n' "${timings[@]}" | sort -n | sed -n "$(( (RUNS + 1) / 2 ))p")
+echo "nsc --version: ${median_us} µs median of ${RUNS} runs (budget ${MAX_STARTUP_MS} ms)"
+
+status=0
+if (( size > MAX_BINARY_BYTES )); then
+  echo "error: binary is $(( size - MAX_BINARY_BYTES )) bytes over budget" >&2
+  status=1
+fi
+if (( median_us > MAX_STARTUP_MS * 1000 )); then
+  echo "error: startup is $(( median_us - MAX_STARTUP_MS * 1000 )) µs over budget" >&2
+  status=1
+fi
+exit "$status"
@@ -0,0 +1,131 @@
+use crate::compiler::NullScriptTranspiler;
+use crate::core::NullScriptError;
+use crate::utils::files::FileUtils;
+use crate::utils::git::GitUtils;
+use crate::utils::paths::PathUtils;
+use std::collections::BTreeMap;
+use std::path::Path;
+
+pub struct CommitGrowth {
+    pub commit: String,
+    pub subject: String,
+    pub growth: i64,
+    pub files: Vec<(String, i64)>,
+}
+
+pub struct SizeBlame {
+    pub from: String,
+    pub to: String,
+    pub before: u64,
+    pub after: u64,
+    pub commits: Vec<CommitGrowth>,
+    pub files: Vec<(String, i64)>,
+    pub failed: usize,
+}
+
+pub fn blame_size(path: &Path, range: &str, transpiler: &NullScriptTranspiler) -> Result<SizeBlame, NullScriptError> {
+    let (from, to) = match range.split_once("..") {
+        Some((from, "")) => (from, "HEAD"),
+        Some((from, to)) => (from, to),
+        None => (range, "HEAD"),
+    };
+
+    let root = GitUtils::toplevel(path)?;
+    let pathspec = PathUtils::relative_to(&dunce::canonicalize(path)?, &dunce::canonicalize(&root)?)
+        .map(|relative| PathUtils::to_slash(&relative))
+        .filter(|relative| !relative.is_empty())
+        .unwrap_or_else(|| ".".to_string());
+
+    let mut failed = 0;
+    let mut measure = |commit: &str, file: &str| -> Result<Option<u64>, NullScriptError> {
+        if !FileUtils::is_nullscript_file(Path::new(file)) {
+            return Ok(None);
+        }
+        let Some(source) = GitUtils::show(&root, commit, file)? else {
+            return Ok(None);
+        };
+        match transpiler.transpile(&source) {
+            Ok(js) => Ok(Some(js.len() as u64)),
+            Err(_) => {
+                failed += 1;
+                Ok(None)
+            }
+        }
+    };
+
+    let mut sizes = BTreeMap::new();
+    for file in GitUtils::tree_files(&root, from, &pathspec)? {
+        if let Some(size) = measure(from, &file)? {
+            sizes.insert(file, size);
+        }
+    }
+    let initial = sizes.clone();
+
+    let mut previous = from.to_string();
+    let mut commits = Vec::new();
+    for (commit, subject) in GitUtils::first_parent_commits(&root, from, to)? {
+        let before = sizes.clone();
+        for file in GitUtils::changed_files(&root, &previous, &commit, &pathspec)? {
+            match measure(&commit, &file)? {
+                Some(size) => sizes.insert(file, size),
+                None => sizes.remove(&file),
+            };
+        }
+
+        let files = growth(&before, &sizes);
+        if !files.is_empty() {
+            commits.push(CommitGrowth { commit: commit.clone(), subject, growth: files.iter().map(|(_, delta)| delta).sum(), files });
+        }
+        previous = commit;
+    }
+    commits.sort_by_key(|commit| std::cmp::Reverse(commit.growth));
+
+    Ok(SizeBlame {
+        from: from.to_string(),
+        to: to.to_string(),
+        before: initial.values().sum(),
+        after: sizes.values().sum(),
+        files: growth(&initial, &sizes),
+        commits,
+        failed,
+    })
+}
+
+pub fn signed_size(delta: i64) -> String {
+    let sign = if delta < 0 { "-" } else { "+" };
+    format!("{}{}", sign, FileUtils::format_file_size(delta.unsigned_abs()))
+}
+
+fn growth(before: &BTreeMap<String, u64>, after: &BTreeMap<String, u64>) -> Vec<(String, i64)> {
+    let mut files: Vec<(String, i64)> = before
+        .keys()
+        .chain(after.keys())
+        .collect::<std::collections::BTreeSet<_>>()
+        .into_iter()
+        .map(|file| {
+            let size = |sizes: &BTreeMap<String, u64>| sizes.get(file).copied().unwrap_or(0) as i64;
+            (file.clone(), size(after) - size(before))
+        })
+        .filter(|(_, delta)| *delta != 0)
+        .collect();
+
+    files.sort_by_key(|(_, delta)| std::cmp::Reverse(*delta));
+    files
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_growth_lists_changed_files_largest_first() {
+        let before = BTreeMap::from([("a.ns".to_string(), 100), ("gone.ns".to_string(), 50), ("same.ns".to_string(), 10)]);
+        let after = BTreeMap::from([("a.ns".to_string(), 120), ("new.ns".to_string(), 900), ("same.ns".to_string(), 10)]);
+
+        assert_eq!(
+            growth(&before, &after),
+            vec![("new.ns".to_string(), 900), ("a.ns".to_string(), 20), ("gone.ns".to_string(), -50)]
+        );
+        assert_eq!(signed_size(-2048), "-2.0 KB");
+    }
+}
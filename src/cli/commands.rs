@@ -1,9 +1,20 @@
 use crate::core::{NullScriptError, format_error};
+use crate::core::i18n::{self, tr, trf, trn, Lang};
 use crate::cli::handler::CliHandler;
+use crate::cli::editor::{self, ErrorLocation};
+use crate::cli::output;
+use crate::cli::prompt;
 use clap::{Args, CommandFactory, Parser, Subcommand};
 use colored::Colorize;
-use std::path::PathBuf;
-use std::process::Command;
+use crate::compiler::NullScriptTranspiler;
+use crate::core::config::{NsConfig, OperatorAliases};
+use crate::core::version::Version;
+use crate::compiler::options::{ModuleFormat, Target, TranspileOptions};
+use crate::compiler::platform::PLATFORMS;
+use crate::utils::commands::{CommandUtils, ToolPolicy};
+use crate::utils::files::FileUtils;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
 
 #[derive(Parser)]
 #[command(
@@ -14,9 +25,24 @@ use std::process::Command;
     after_help = "Examples:
   nsc build src/                    # Transpile all .ns files in src/ to JavaScript
   nsc run hello.ns                  # Run a NullScript file
+  nsc run app.ns --inspect-memory   # Run and write a heap memory report
+  nsc repl                          # Try NullScript interactively
   nsc keywords                      # Show all available keywords
   nsc system --info                 # Show system information
   nsc info src/ --detailed          # Show detailed file information
+  nsc graph src/ --format mermaid   # Print the module import graph
+  nsc analyze src/                  # Report unused modules
+  nsc analyze src/ --profile-build --sample 10%   # Profile a tenth of the modules
+  nsc check src/ --changed=main     # Check only what changed since main
+  nsc check src/ --types            # Also type-check the output with tsc
+  nsc compdb src/ --output compile_commands.json   # Describe how each file is built
+  nsc convert legacy.js --emit-fixture   # Convert JavaScript to NullScript and record a fixture
+  nsc fmt src/ --check              # List .ns files that are not formatted
+  nsc lsp                           # Run the language server over stdio for editors
+  nsc codemod js-to-nullscript src/ # Preview a codemod as a diff; add --write to apply it
+  nsc config --resolve src/app.ns   # Show where each setting for a file comes from
+  nsc examples classes --run        # Show and run a bundled example
+  nsc self update --check           # Check for a newer nsc release
 
 Learn more at: https://github.com/nullscript-lang/nullscript"
 )]
@@ -24,6 +50,36 @@ pub struct Cli {
     #[arg(short = 'v', long = "version", help = "Print Version")]
     pub version: bool,
 
+    #[arg(long = "plain", global = true, help = "Plain output without emojis, box drawing, or color")]
+    pub plain: bool,
+
+    #[arg(long = "theme", global = true, value_name = "THEME", value_parser = output::Theme::parse, help = "Output style: unicode, ascii (no emojis or box drawing) or plain (ascii without color)")]
+    pub theme: Option<output::Theme>,
+
+    #[arg(long = "lang", global = true, value_name = "LANG", help = "Language for CLI messages (en, es); defaults to NSC_LANG or LANG")]
+    pub lang: Option<String>,
+
+    #[arg(long = "tool-timeout", global = true, value_name = "SECONDS", help = "Kill external tools (node) that run longer than this")]
+    pub tool_timeout: Option<u64>,
+
+    #[arg(long = "tool-retries", global = true, value_name = "COUNT", help = "Retry timed-out tool invocations this many times")]
+    pub tool_retries: Option<u32>,
+
+    #[arg(long = "yes", global = true, help = "Answer yes to confirmations and take defaults instead of prompting")]
+    pub yes: bool,
+
+    #[arg(long = "hermetic", global = true, help = "Never run external tools (node, curl, generator commands); fail instead")]
+    pub hermetic: bool,
+
+    #[arg(long = "max-file-size", global = true, value_name = "SIZE", value_parser = FileUtils::parse_size, help = "Largest source file to transpile, e.g. 512KB or 10MB")]
+    pub max_file_size: Option<u64>,
+
+    #[arg(long = "max-nesting-depth", global = true, value_name = "DEPTH", help = "Deepest bracket/template nesting accepted before reporting an error")]
+    pub max_nesting_depth: Option<usize>,
+
+    #[arg(long = "max-errors", global = true, value_name = "COUNT", help = "Most syntax errors to report for one file (default 20)")]
+    pub max_errors: Option<usize>,
+
     #[command(subcommand)]
     pub command: Option<Commands>,
 }
@@ -32,9 +88,23 @@ pub struct Cli {
 pub enum Commands {
     Build(BuildArgs),
     Run(RunArgs),
+    Repl(ReplArgs),
     Keywords(KeywordsArgs),
     System(SystemArgs),
     Info(InfoArgs),
+    Graph(GraphArgs),
+    Analyze(AnalyzeArgs),
+    Check(CheckArgs),
+    Lint(LintArgs),
+    Compdb(CompdbArgs),
+    Examples(ExamplesArgs),
+    Config(ConfigArgs),
+    Convert(ConvertArgs),
+    Fmt(FmtArgs),
+    Codemod(CodemodArgs),
+    Lsp(LspArgs),
+    #[command(name = "self")]
+    SelfCommand(SelfArgs),
 }
 
 #[derive(Args)]
@@ -43,19 +113,356 @@ pub struct BuildArgs {
 
     #[arg(short = 'o', long = "outDir", default_value = "dist")]
     pub out_dir: PathBuf,
+
+    #[arg(long = "warn-unused", help = "Warn about modules never imported from an entry point")]
+    pub warn_unused: bool,
+
+    #[arg(long = "platform", value_parser = PLATFORMS.to_vec(), help = "Pick platform-specific modules such as utils.node.ns")]
+    pub platform: Option<String>,
+
+    #[arg(long = "dry-run", help = "Transpile in memory and list the files that would be written")]
+    pub dry_run: bool,
+
+    #[arg(long = "bundle", conflicts_with_all = ["dry_run", "file_names"], help = "Resolve use/share across the project and write a single JavaScript file")]
+    pub bundle: bool,
+
+    #[arg(long = "out", value_name = "FILE", requires = "bundle", help = "Bundle file to write (default: <outDir>/bundle.js)")]
+    pub out: Option<PathBuf>,
+
+    #[arg(long = "no-cache", help = "Retranspile every file instead of reusing unchanged outputs recorded in .ns-cache/")]
+    pub no_cache: bool,
+
+    #[command(flatten)]
+    pub emit: EmitArgs,
+
+    #[arg(
+        long = "explain-transforms",
+        value_name = "FILE",
+        num_args = 0..=1,
+        help = "List the transform passes that changed each file; with FILE, write them as JSON"
+    )]
+    pub explain_transforms: Option<Option<PathBuf>>,
+
+    #[arg(long = "depfile", value_name = "FILE", help = "Write Makefile-style dependency rules for every generated file")]
+    pub depfile: Option<PathBuf>,
+
+    #[arg(
+        long = "fileNames",
+        value_name = "TEMPLATE",
+        help = "Name emitted files from a template such as [name].[contenthash:8].js"
+    )]
+    pub file_names: Option<String>,
+
+    #[arg(long = "open-editor", help = "If the build fails, open the error location in $VISUAL or $EDITOR")]
+    pub open_editor: bool,
+
+    #[command(flatten)]
+    pub progress: ProgressArgs,
+}
+
+impl BuildArgs {
+    pub fn transpile_options(&self, config: &NsConfig) -> TranspileOptions {
+        self.emit.transpile_options(config)
+    }
+}
+
+#[derive(Args)]
+pub struct EmitArgs {
+    #[arg(
+        long = "target",
+        value_parser = Target::parse,
+        help = "JavaScript language level to emit (es2015, es2017, es2020, es2022, esnext); overrides \"target\" in nsconfig.json"
+    )]
+    pub target: Option<Target>,
+
+    #[arg(long = "module", value_parser = ModuleFormat::parse, help = "Module format to emit (esm, cjs)")]
+    pub module: Option<ModuleFormat>,
+
+    #[arg(long = "sourceMap", help = "Write a .map file next to every generated .js file")]
+    pub source_map: bool,
+
+    #[arg(long = "minify", help = "Strip comments and indentation, drop constant branches, and shorten local names in the generated JavaScript")]
+    pub minify: bool,
+
+    #[arg(long = "strict-ns", help = "Fail when a .ns file contains no NullScript keywords")]
+    pub strict_ns: bool,
+
+    #[arg(long = "dialect", help = "Keyword dialect of the sources")]
+    pub dialect: Option<String>,
+
+    #[arg(long = "define", value_name = "NAME=VALUE", value_parser = TranspileOptions::parse_define, help = "Replace an identifier with a constant value")]
+    pub define: Vec<(String, String)>,
+
+    #[arg(
+        long = "operator-aliases",
+        value_name = "MODE",
+        value_parser = OperatorAliases::parse,
+        help = "Whether is/and/not and the other operator words are rewritten (on, off, accept-both); overrides \"operatorAliases\" in nsconfig.json"
+    )]
+    pub operator_aliases: Option<OperatorAliases>,
+
+    #[arg(long = "runtime-shim", help = "Import speak and maths from nullscript/runtime instead of rewriting them to console and Math")]
+    pub runtime_shim: bool,
+}
+
+impl EmitArgs {
+    pub fn transpile_options(&self, config: &NsConfig) -> TranspileOptions {
+        let options = TranspileOptions::new()
+            .with_target(self.target.or(config.target).unwrap_or_default())
+            .with_module(self.module.unwrap_or_default())
+            .with_source_map(self.source_map)
+            .with_minify(self.minify)
+            .with_strict_ns(self.strict_ns)
+            .with_dialect(self.dialect.clone())
+            .with_top_level_await(config.top_level_await)
+            .with_runtime_shim(self.runtime_shim || config.runtime_shim)
+            .with_operator_aliases(self.operator_aliases.unwrap_or(config.operator_aliases));
+
+        self.define
+            .iter()
+            .fold(options, |options, (name, value)| options.with_define(name, value))
+    }
 }
 
 #[derive(Args)]
 pub struct RunArgs {
     pub file: PathBuf,
+
+    #[arg(long = "inspect-memory", help = "Capture a heap snapshot at exit (or on SIGUSR2) and write a memory report")]
+    pub inspect_memory: bool,
+
+    #[arg(long = "reportsDir", default_value = "reports", help = "Directory for memory reports and heap snapshots")]
+    pub reports_dir: PathBuf,
+
+    #[arg(long = "allow-outside", help = "Run files that nsconfig.json excludes from the project without warning")]
+    pub allow_outside: bool,
+
+    #[arg(short = 'q', long = "quiet", help = "Only print the program's own output")]
+    pub quiet: bool,
+
+    #[arg(
+        long = "engine",
+        default_value = "node",
+        value_parser = ["node", "builtin"],
+        help = "Run with node, or with the JavaScript engine built into nsc (needs the 'engine' feature)"
+    )]
+    pub engine: String,
+}
+
+#[derive(Args)]
+pub struct ReplArgs {
+    #[arg(
+        long = "engine",
+        default_value = "node",
+        value_parser = ["node", "builtin"],
+        help = "Evaluate with node, or with the JavaScript engine built into nsc (needs the 'engine' feature)"
+    )]
+    pub engine: String,
+}
+
+
+
+#[derive(Args)]
+pub struct GraphArgs {
+    pub path: PathBuf,
+
+    #[arg(short = 'f', long = "format", default_value = "dot", value_parser = ["dot", "mermaid", "json"])]
+    pub format: String,
+
+    #[arg(long = "cluster", help = "Group modules by directory")]
+    pub cluster: bool,
+
+    #[arg(long = "cycles", help = "Highlight import cycles")]
+    pub cycles: bool,
+
+    #[arg(long = "external", help = "Include external packages as nodes")]
+    pub external: bool,
+
+    #[arg(short = 'e', long = "entry", help = "Only show modules reachable from this entry point")]
+    pub entry: Vec<PathBuf>,
+
+    #[arg(long = "depth", requires = "entry", help = "Maximum import depth from the entry point")]
+    pub depth: Option<usize>,
+
+    #[arg(short = 'o', long = "output", help = "Write the graph to a file instead of stdout")]
+    pub output: Option<PathBuf>,
+}
+
+#[derive(Args)]
+pub struct CheckArgs {
+    #[arg(default_value = ".")]
+    pub path: PathBuf,
+
+    #[arg(
+        long = "changed",
+        value_name = "REF",
+        num_args = 0..=1,
+        default_missing_value = "HEAD",
+        help = "Only check files changed since REF (default HEAD) and report problems on changed lines"
+    )]
+    pub changed: Option<String>,
+
+    #[arg(long = "open-editor", help = "Open the first error location in $VISUAL or $EDITOR")]
+    pub open_editor: bool,
+
+    #[arg(
+        long = "diagnostics",
+        value_name = "FILE",
+        help = "Merge findings from another tool's JSON report (adds to nsconfig.json externalDiagnostics)"
+    )]
+    pub diagnostics: Vec<PathBuf>,
+
+    #[arg(
+        long = "docs",
+        value_name = "GLOB",
+        help = "Check the ```nullscript code blocks in Markdown files instead of the project"
+    )]
+    pub docs: Vec<String>,
+
+    #[arg(long = "run-docs", requires = "docs", help = "Also run every Markdown code block with node")]
+    pub run_docs: bool,
+
+    #[arg(
+        long = "types",
+        value_name = "COMMAND",
+        num_args = 0..=1,
+        default_missing_value = "tsc",
+        help = "Type-check the transpiled JavaScript with tsc (or COMMAND, given the .js files, printing tsc-style errors)"
+    )]
+    pub types: Option<String>,
+}
+
+#[derive(Args)]
+pub struct LintArgs {
+    #[arg(default_value = ".", help = "Files, directories or glob patterns to lint")]
+    pub paths: Vec<String>,
+
+    #[arg(long = "rules", help = "List the lint rules and the level nsconfig.json gives each")]
+    pub rules: bool,
+}
+
+#[derive(Args)]
+pub struct AnalyzeArgs {
+    pub path: PathBuf,
+
+    #[arg(short = 'e', long = "entry", help = "Entry point to analyze from (adds to nsconfig.json entries)")]
+    pub entry: Vec<PathBuf>,
+
+    #[arg(long = "profile-build", help = "Transpile every module and report the time spent in each pass")]
+    pub profile_build: bool,
+
+    #[arg(long = "minify-sizes", help = "Transpile every module with and without --minify and report the size of each")]
+    pub minify_sizes: bool,
+
+    #[arg(
+        long = "blame-size",
+        value_name = "FROM..TO",
+        help = "Attribute output size growth between two commits (TO defaults to HEAD) to commits and files"
+    )]
+    pub blame_size: Option<String>,
+
+    #[arg(long = "heatmap", value_name = "FILE", help = "Write an HTML heatmap of keyword usage by directory")]
+    pub heatmap: Option<PathBuf>,
+
+    #[arg(
+        long = "heatmap-since",
+        value_name = "REV",
+        requires = "heatmap",
+        help = "Chart keyword usage per commit since REV instead of by directory"
+    )]
+    pub heatmap_since: Option<String>,
+
+    #[arg(
+        long = "sample",
+        value_name = "PERCENT",
+        value_parser = Sample::parse,
+        help = "Profile, size and chart only an evenly spread share of the modules, e.g. 10%"
+    )]
+    pub sample: Option<Sample>,
+
+    #[arg(
+        long = "changed-only",
+        value_name = "REF",
+        num_args = 0..=1,
+        default_missing_value = "HEAD",
+        help = "Profile, size and chart only the modules changed since REF (default HEAD)"
+    )]
+    pub changed_only: Option<String>,
+
+    #[command(flatten)]
+    pub progress: ProgressArgs,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Sample {
+    percent: usize,
+}
 
+impl Sample {
+    pub fn parse(value: &str) -> Result<Self, String> {
+        let percent = value.trim().trim_end_matches('%').trim();
+        match percent.parse::<usize>() {
+            Ok(percent @ 1..=100) => Ok(Sample { percent }),
+            _ => Err(format!("invalid sample '{}', expected a percentage from 1% to 100%", value)),
+        }
+    }
+
+    pub fn pick<T>(self, items: Vec<T>) -> Vec<T> {
+        let total = items.len();
+        let keep = (total * self.percent).div_ceil(100);
+        items
+            .into_iter()
+            .enumerate()
+            .filter(|(index, _)| (index + 1) * keep / total > index * keep / total)
+            .map(|(_, item)| item)
+            .collect()
+    }
+}
+
+#[derive(Args)]
+pub struct ProgressArgs {
+    #[arg(long = "progress", value_parser = ["json"], help = "Emit progress events as NDJSON for IDEs and build servers")]
+    pub progress: Option<String>,
+
+    #[arg(
+        long = "progress-file",
+        value_name = "PATH",
+        requires = "progress",
+        help = "Write progress events to a file or named pipe instead of stdout"
+    )]
+    pub progress_file: Option<PathBuf>,
+}
+
+#[derive(Args)]
+pub struct CompdbArgs {
+    pub path: PathBuf,
+
+    #[arg(long = "outDir", default_value = "dist", help = "Output directory the build would write to")]
+    pub out_dir: PathBuf,
+
+    #[arg(long = "platform", value_parser = PLATFORMS.to_vec(), help = "Pick platform-specific modules such as utils.node.ns")]
+    pub platform: Option<String>,
+
+    #[command(flatten)]
+    pub emit: EmitArgs,
+
+    #[arg(long = "output", value_name = "FILE", help = "Write the database to a file instead of stdout")]
+    pub output: Option<PathBuf>,
+}
 
 #[derive(Args)]
 pub struct KeywordsArgs {
-    #[arg(short = 'c', long = "category")]
+    #[arg(short = 'c', long = "category", help = "Only show one category (basics, operators, advanced, console, globals, functions)")]
     pub category: Option<String>,
+
+    #[arg(short = 'f', long = "filter", value_name = "TEXT", help = "Only show keywords whose NullScript or JavaScript name contains TEXT")]
+    pub filter: Option<String>,
+
+    #[arg(long = "columns", value_name = "N", help = "Layout width in characters (defaults to $COLUMNS on a terminal)")]
+    pub columns: Option<usize>,
+
+    #[arg(long = "no-pager", help = "Print directly instead of piping through $PAGER")]
+    pub no_pager: bool,
 }
 
 #[derive(Args)]
@@ -64,6 +471,92 @@ pub struct SystemArgs {
     pub info: bool,
 }
 
+#[derive(Args)]
+pub struct ConfigArgs {
+    #[arg(long = "resolve", value_name = "PATH", default_value = ".", help = "File or directory to resolve the configuration for")]
+    pub resolve: PathBuf,
+
+    #[arg(long = "json", help = "Print the resolved configuration as JSON")]
+    pub json: bool,
+}
+
+#[derive(Args)]
+pub struct ConvertArgs {
+    #[arg(help = "JavaScript file to convert")]
+    pub input: PathBuf,
+
+    #[arg(short = 'o', long = "output", help = "Where to write the .ns file (defaults to next to the input)")]
+    pub output: Option<PathBuf>,
+
+    #[arg(long = "force", help = "Overwrite existing files")]
+    pub force: bool,
+
+    #[arg(
+        long = "emit-fixture",
+        value_name = "DIR",
+        num_args = 0..=1,
+        default_missing_value = "tests/fixtures/roundtrip",
+        help = "Record the original, converted and re-transpiled sources as a round-trip fixture"
+    )]
+    pub emit_fixture: Option<PathBuf>,
+
+    #[arg(long = "keep-operators", help = "Keep ===, && and ! even when \"operatorAliases\" is \"on\" in nsconfig.json")]
+    pub keep_operators: bool,
+}
+
+#[derive(Args)]
+pub struct FmtArgs {
+    #[arg(default_value = ".", help = "Files, directories or glob patterns to format")]
+    pub paths: Vec<String>,
+
+    #[arg(long = "check", help = "Only list files that would change; exit with 1 if there are any")]
+    pub check: bool,
+}
+
+#[derive(Args)]
+pub struct CodemodArgs {
+    #[arg(help = "Built-in codemod name or a .ns-codemod file; lists the built-ins when omitted")]
+    pub codemod: Option<String>,
+
+    #[arg(default_value = ".", help = "Files, directories or glob patterns to rewrite")]
+    pub paths: Vec<String>,
+
+    #[arg(long = "write", help = "Rewrite the files instead of printing a diff")]
+    pub write: bool,
+}
+
+#[derive(Args)]
+pub struct LspArgs {
+    #[arg(long = "stdio", help = "Talk to the editor over stdin/stdout (the default and only transport)")]
+    pub stdio: bool,
+}
+
+#[derive(Args)]
+pub struct ExamplesArgs {
+    #[arg(help = "Example to show; lists all examples when omitted")]
+    pub name: Option<String>,
+
+    #[arg(long = "run", requires = "name", help = "Run the example after showing its source")]
+    pub run: bool,
+}
+
+#[derive(Args)]
+pub struct SelfArgs {
+    #[command(subcommand)]
+    pub command: SelfCommands,
+}
+
+#[derive(Subcommand)]
+pub enum SelfCommands {
+    Update(SelfUpdateArgs),
+}
+
+#[derive(Args)]
+pub struct SelfUpdateArgs {
+    #[arg(long = "check", help = "Only compare the installed version with the latest release")]
+    pub check: bool,
+}
+
 #[derive(Args)]
 pub struct InfoArgs {
     pub path: PathBuf,
@@ -72,18 +565,121 @@ pub struct InfoArgs {
     pub detailed: bool,
 }
 
+pub static EXTERNAL_TOOLS: &[(&str, &str)] = &[
+    ("nsc run", "node"),
+    ("nsc repl", "node"),
+    ("nsc examples --run", "node"),
+    ("nsc system", "node"),
+    ("nsc self update", "curl"),
+    ("nsc build", "generate.command (nsconfig.json)"),
+    ("nsc check --changed", "git"),
+    ("nsc analyze --blame-size", "git"),
+    ("nsc analyze --heatmap-since", "git"),
+    ("nsc analyze --changed-only", "git"),
+    ("nsc check --run-docs", "node"),
+    ("nsc check --types", "tsc (or the --types command)"),
+    ("--open-editor", "$VISUAL / $EDITOR"),
+];
+
+impl Commands {
+    fn external_tool_feature(&self) -> Option<&'static str> {
+        match self {
+            Commands::Run(args) if args.engine == "node" => Some("nsc run"),
+            Commands::Repl(args) if args.engine == "node" => Some("nsc repl"),
+            Commands::Examples(args) if args.run => Some("nsc examples --run"),
+            Commands::System(_) => Some("nsc system"),
+            Commands::SelfCommand(_) => Some("nsc self update"),
+            Commands::Build(args) if args.open_editor => Some("--open-editor"),
+            Commands::Check(args) if args.open_editor => Some("--open-editor"),
+            Commands::Check(args) if args.changed.is_some() => Some("nsc check --changed"),
+            Commands::Check(args) if args.run_docs => Some("nsc check --run-docs"),
+            Commands::Check(args) if args.types.is_some() => Some("nsc check --types"),
+            Commands::Analyze(args) if args.blame_size.is_some() => Some("nsc analyze --blame-size"),
+            Commands::Analyze(args) if args.heatmap_since.is_some() => Some("nsc analyze --heatmap-since"),
+            Commands::Analyze(args) if args.changed_only.is_some() => Some("nsc analyze --changed-only"),
+            _ => None,
+        }
+    }
+
+    fn opens_editor_on_error(&self) -> Option<bool> {
+        match self {
+            Commands::Build(args) => Some(args.open_editor),
+            _ => None,
+        }
+    }
+
+    fn project_path(&self) -> Option<&Path> {
+        match self {
+            Commands::Build(args) => Some(&args.path),
+            Commands::Run(args) => Some(&args.file),
+            Commands::Info(args) => Some(&args.path),
+            Commands::Graph(args) => Some(&args.path),
+            Commands::Analyze(args) => Some(&args.path),
+            Commands::Check(args) => Some(&args.path),
+            Commands::Compdb(args) => Some(&args.path),
+            Commands::Config(args) => Some(&args.resolve),
+            Commands::Convert(args) => Some(&args.input),
+            Commands::Fmt(_) | Commands::Codemod(_) | Commands::Lint(_) => Some(Path::new(".")),
+            Commands::Keywords(_) | Commands::System(_) | Commands::Examples(_) | Commands::Repl(_) => Some(Path::new(".")),
+            Commands::Lsp(_) | Commands::SelfCommand(_) => None,
+        }
+    }
+}
+
 impl CliHandler {
     pub async fn handle_command(&self, command: Commands) -> Result<(), NullScriptError> {
+        if let Some(path) = command.project_path() {
+            self.check_nsc_version(path)?;
+        }
+        if let Some(feature) = command.external_tool_feature() {
+            self.forbid_in_hermetic_mode(feature)?;
+        }
+
         match command {
-            Commands::Build(args) => self.handle_build(args.path, args.out_dir).await,
-            Commands::Run(args) => self.handle_run(args.file).await,
-            Commands::Keywords(args) => self.handle_keywords(args.category),
+            Commands::Build(args) => self.handle_build(args).await,
+            Commands::Run(args) => self.handle_run(args).await,
+            Commands::Repl(args) => self.handle_repl(args).await,
+            Commands::Keywords(args) => self.handle_keywords(args),
             Commands::System(args) => self.handle_system(args),
             Commands::Info(args) => self.handle_info(args),
+            Commands::Graph(args) => self.handle_graph(args).await,
+            Commands::Analyze(args) => self.handle_analyze(args),
+            Commands::Check(args) => self.handle_check(args).await,
+            Commands::Lint(args) => self.handle_lint(args).await,
+            Commands::Compdb(args) => self.handle_compdb(args).await,
+            Commands::Examples(args) => self.handle_examples(args).await,
+            Commands::Config(args) => self.handle_config(args),
+            Commands::Convert(args) => self.handle_convert(args).await,
+            Commands::Fmt(args) => self.handle_fmt(args).await,
+            Commands::Codemod(args) => self.handle_codemod(args).await,
+            Commands::Lsp(_) => self.handle_lsp(),
+            Commands::SelfCommand(args) => match args.command {
+                SelfCommands::Update(args) => self.handle_self_update(args),
+            },
         }
     }
 }
 
+pub fn node_too_old(node: Version, target: Target) -> Option<String> {
+    let required = target.min_node_version();
+    (node < required).then(|| {
+        trf("target.node_too_old", &[("node", &node), ("target", &target.as_str()), ("required", &required)])
+    })
+}
+
+fn tool_policy(cli: &Cli, mut policy: ToolPolicy) -> ToolPolicy {
+    if let Some(secs) = cli.tool_timeout {
+        policy.timeout = (secs > 0).then(|| Duration::from_secs(secs));
+    }
+    if let Some(retries) = cli.tool_retries {
+        policy.retries = retries;
+    }
+    if cli.hermetic {
+        policy.hermetic = true;
+    }
+    policy
+}
+
 pub async fn run() -> Result<(), NullScriptError> {
     let cli = Cli::parse();
 
@@ -92,12 +688,35 @@ pub async fn run() -> Result<(), NullScriptError> {
         return Ok(());
     }
 
-    let handler = CliHandler::new();
+    i18n::set_lang(Lang::detect(cli.lang.as_deref()));
+    output::set_theme(output::theme_requested(cli.theme, cli.plain));
+
+    CommandUtils::set_policy(tool_policy(&cli, ToolPolicy::from_env()));
+    prompt::set_assume_yes(cli.yes);
+
+    let mut transpiler = NullScriptTranspiler::new();
+    if let Some(max_file_size) = cli.max_file_size {
+        transpiler = transpiler.with_max_file_size(max_file_size);
+    }
+    if let Some(max_nesting_depth) = cli.max_nesting_depth {
+        transpiler = transpiler.with_max_nesting_depth(max_nesting_depth);
+    }
+    if let Some(max_errors) = cli.max_errors {
+        transpiler = transpiler.with_max_errors(max_errors);
+    }
+
+    let handler = CliHandler::with_transpiler(transpiler);
 
     if let Some(command) = cli.command {
+        let open_editor = command.opens_editor_on_error();
         if let Err(e) = handler.handle_command(command).await {
-            eprintln!("{}", format_error(&e).red());
-            std::process::exit(1);
+            output::eline(format_error(&e).red());
+            if let (Some(open_first), Some(location)) = (open_editor, ErrorLocation::of(&e)) {
+                if let Err(e) = editor::offer(&[location], open_first).await {
+                    output::eline(format_error(&e).red());
+                }
+            }
+            std::process::exit(e.exit_code());
         }
     } else {
         let _ = Cli::command().print_help();
@@ -109,64 +728,72 @@ pub async fn run() -> Result<(), NullScriptError> {
 
 impl CliHandler {
     pub fn check_node_availability() -> bool {
-        Command::new("node").arg("--version").output().is_ok()
+        CommandUtils::probe_command("node", &["--version"]).is_ok_and(|output| output.status.success())
     }
 
-    pub fn show_system_info() {
-        println!("{}", "🔧 System Information".cyan());
-        println!("{}", "=".repeat(30).bright_black());
-        println!("Node.js: {}", if Self::check_node_availability() { "✅ Available".green() } else { "❌ Not found".red() });
-        println!("NullScript: {} v{}", "✅ Available".green(), env!("CARGO_PKG_VERSION"));
+    pub fn show_system_info(target: Option<Target>) {
+        output::heading(tr("system.title"));
+        let node = CommandUtils::node_version();
+        match node {
+            Some(version) => output::line(format!("Node.js: {} v{}", tr("system.available").green(), version)),
+            None => output::line(format!("Node.js: {}", tr("system.not_found").red())),
+        }
+        output::line(format!("NullScript: {} v{}", tr("system.available").green(), env!("CARGO_PKG_VERSION")));
+
+        if let Some(target) = target {
+            output::line(trf("system.target", &[("target", &target.as_str()), ("version", &target.min_node_version())]));
+            if let Some(warning) = node.and_then(|node| node_too_old(node, target)) {
+                output::line(warning.yellow());
+            }
+        }
     }
 
     pub fn handle_system(&self, _args: SystemArgs) -> Result<(), NullScriptError> {
-        Self::show_system_info();
+        Self::show_system_info(NsConfig::discover(Path::new("."))?.config.target);
         Ok(())
     }
 
     pub fn handle_info(&self, args: InfoArgs) -> Result<(), NullScriptError> {
-        use crate::utils::files::FileUtils;
+        use crate::utils::paths::PathUtils;
 
         if !args.path.exists() {
-            eprintln!("{}", format!("❌ Path does not exist: {}", args.path.display()).red());
+            output::eline(trf("info.missing_path", &[("path", &args.path.display())]).red());
             std::process::exit(1);
         }
 
-        println!("{}", "📁 File Information".cyan());
-        println!("{}", "=".repeat(30).bright_black());
-        println!("Path: {}", args.path.display());
+        output::heading(tr("info.title"));
+        output::line(trf("info.path", &[("path", &args.path.display())]));
 
         if let Some(ext) = FileUtils::get_extension(&args.path) {
-            println!("Extension: {}", ext);
+            output::line(trf("info.extension", &[("ext", &ext)]));
         }
 
         if let Some(stem) = FileUtils::get_stem(&args.path) {
-            println!("Name: {}", stem);
+            output::line(trf("info.name", &[("name", &stem)]));
         }
 
         if args.path.is_file() {
             let size = FileUtils::get_file_size(&args.path)?;
-            println!("Size: {}", FileUtils::format_file_size(size));
+            output::line(trf("info.size", &[("size", &FileUtils::format_file_size(size))]));
 
             if args.detailed {
                 let lines = FileUtils::count_lines(&args.path)?;
-                println!("Lines: {}", lines);
+                output::line(trf("info.lines", &[("count", &lines)]));
 
                 if let Ok(modified) = FileUtils::get_modified_time(&args.path) {
                     if let Ok(duration) = modified.elapsed() {
-                        println!("Modified: {} ago", format_duration(duration));
+                        output::line(trf("info.modified", &[("duration", &format_duration(duration))]));
                     }
                 }
             }
         } else if args.path.is_dir() {
             let (total_files, nullscript_files) = self.get_file_stats(&args.path)?;
-            println!("Total files: {}", total_files);
-            println!("NullScript files: {}", nullscript_files);
+            output::line(trf("info.total_files", &[("count", &total_files)]));
+            output::line(trf("info.nullscript_files", &[("count", &nullscript_files)]));
 
             if args.detailed {
-                println!();
-                println!("{}", "📋 File Details:".cyan());
-                println!("{}", "─".repeat(40).bright_black());
+                output::blank();
+                output::section(tr("info.details"));
 
                 let mut file_details = Vec::new();
                 let mut total_size = 0u64;
@@ -180,10 +807,9 @@ impl CliHandler {
                     let size = FileUtils::get_file_size(&file_path).unwrap_or(0);
                     total_size += size;
 
-                    let relative_path = file_path.strip_prefix(&args.path)
-                        .unwrap_or(&file_path)
-                        .to_string_lossy()
-                        .to_string();
+                    let relative_path = PathUtils::relative_to(&file_path, &args.path)
+                        .map(|relative| PathUtils::to_slash(&relative))
+                        .unwrap_or_else(|| PathUtils::to_slash(&file_path));
 
                     let ext = FileUtils::get_extension(&file_path)
                         .unwrap_or_else(|| "no-ext".to_string());
@@ -194,7 +820,7 @@ impl CliHandler {
                         .ok()
                         .and_then(|m| m.elapsed().ok())
                         .map(format_duration)
-                        .unwrap_or_else(|| "unknown".to_string());
+                        .unwrap_or_else(|| tr("duration.unknown").to_string());
 
                     file_details.push((relative_path, ext, size, lines, modified));
                 }
@@ -206,18 +832,18 @@ impl CliHandler {
                     let is_ns = ext == "ns";
                     let icon = if is_ns { "🎭" } else { "📄" };
 
-                    println!("{} {:<30} {:<8} {:<8} {:<6} {}",
+                    output::line(format!("{} {:<30} {:<8} {:<8} {:<6} {}",
                         icon,
-                        if path.len() > 28 { format!("{}...", &path[..25]) } else { path },
+                        if path.chars().count() > 28 { format!("{}...", path.chars().take(25).collect::<String>()) } else { path },
                         size_str,
                         format!("{}L", lines),
                         ext,
                         modified
-                    );
+                    ));
                 }
 
-                println!("{}", "─".repeat(40).bright_black());
-                println!("Total size: {}", FileUtils::format_file_size(total_size));
+                output::rule(40);
+                output::line(trf("info.total_size", &[("size", &FileUtils::format_file_size(total_size))]));
             }
         }
 
@@ -228,12 +854,74 @@ impl CliHandler {
 fn format_duration(duration: std::time::Duration) -> String {
     let secs = duration.as_secs();
     if secs < 60 {
-        format!("{} seconds", secs)
+        trn("duration.seconds", secs, &[])
     } else if secs < 3600 {
-        format!("{} minutes", secs / 60)
+        trn("duration.minutes", secs / 60, &[])
     } else if secs < 86400 {
-        format!("{} hours", secs / 3600)
+        trn("duration.hours", secs / 3600, &[])
     } else {
-        format!("{} days", secs / 86400)
+        trn("duration.days", secs / 86400, &[])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tool_flags_override_the_environment_policy() {
+        let env = ToolPolicy { timeout: Some(Duration::from_secs(30)), retries: 4, hermetic: false };
+
+        let cli = Cli::try_parse_from(["nsc", "keywords"]).unwrap();
+        let policy = tool_policy(&cli, env);
+        assert_eq!((policy.timeout, policy.retries), (Some(Duration::from_secs(30)), 4));
+
+        let cli = Cli::try_parse_from(["nsc", "keywords", "--tool-timeout", "0", "--tool-retries", "1", "--hermetic"]).unwrap();
+        let policy = tool_policy(&cli, env);
+        assert_eq!((policy.timeout, policy.retries, policy.hermetic), (None, 1, true));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_tool_retries_rerun_a_timed_out_tool() {
+        let dir = tempfile::tempdir().unwrap();
+        let marker = dir.path().join("first-run");
+        let script = format!("if [ -e '{0}' ]; then echo ok; else touch '{0}'; sleep 5; fi", marker.display());
+
+        let cli = Cli::try_parse_from(["nsc", "keywords", "--tool-timeout", "1"]).unwrap();
+        let error = CommandUtils::execute_with_policy("sh", &["-c", &script], tool_policy(&cli, ToolPolicy::default())).unwrap_err();
+        assert_eq!(error.kind(), std::io::ErrorKind::TimedOut);
+
+        std::fs::remove_file(&marker).unwrap();
+        let cli = Cli::try_parse_from(["nsc", "keywords", "--tool-timeout", "1", "--tool-retries", "1"]).unwrap();
+        let output = CommandUtils::execute_with_policy("sh", &["-c", &script], tool_policy(&cli, ToolPolicy::default())).unwrap();
+        assert_eq!(String::from_utf8_lossy(&output.stdout), "ok\n");
+    }
+
+    #[test]
+    fn test_detailed_info_truncates_multibyte_paths() {
+        let dir = tempfile::tempdir().unwrap();
+        let nested = dir.path().join("ééééééééééééé");
+        std::fs::create_dir_all(&nested).unwrap();
+        std::fs::write(nested.join("café.ns"), "fixed a = 1;\n").unwrap();
+
+        let args = InfoArgs { path: dir.path().to_path_buf(), detailed: true };
+        assert!(CliHandler::new().handle_info(args).is_ok());
+    }
+
+    #[test]
+    fn test_analyze_sample_spreads_its_picks() {
+        let Some(Commands::Analyze(args)) = Cli::try_parse_from(["nsc", "analyze", "src", "--sample", "30%", "--changed-only"]).unwrap().command else {
+            panic!("expected nsc analyze");
+        };
+        assert_eq!(args.changed_only.as_deref(), Some("HEAD"));
+
+        let sample = args.sample.unwrap();
+        assert_eq!(sample.pick((0..10).collect()), vec![3, 6, 9]);
+        assert_eq!(sample.pick(vec!["only"]), vec!["only"]);
+        assert!(sample.pick(Vec::<u8>::new()).is_empty());
+
+        assert_eq!(Sample::parse("5"), Sample::parse("5 %"));
+        assert!(Sample::parse("0%").is_err() && Sample::parse("120%").is_err() && Sample::parse("half").is_err());
     }
 }
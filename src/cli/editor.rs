@@ -0,0 +1,142 @@
+use crate::core::NullScriptError;
+use crate::core::i18n::{tr, trf};
+use crate::cli::output;
+use crate::cli::prompt;
+use crate::utils::commands::CommandUtils;
+use colored::Colorize;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ErrorLocation {
+    pub file: PathBuf,
+    pub line: Option<u32>,
+    pub column: Option<u32>,
+}
+
+impl ErrorLocation {
+    pub fn of(error: &NullScriptError) -> Option<Self> {
+        let location = match error {
+            NullScriptError::Transpile(e) => &e.location,
+            NullScriptError::Syntax(e) => &e.location,
+            NullScriptError::SyntaxErrors { errors, .. } => &errors.first()?.location,
+            NullScriptError::Type(e) => &e.location,
+            _ => return None,
+        };
+
+        Some(Self {
+            file: location.file_path.clone()?,
+            line: location.line,
+            column: location.column,
+        })
+    }
+}
+
+impl fmt::Display for ErrorLocation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.file.display())?;
+        if let Some(line) = self.line {
+            write!(f, ":{}", line)?;
+            if let Some(column) = self.column {
+                write!(f, ":{}", column)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+pub fn configured_editor() -> Option<String> {
+    ["VISUAL", "EDITOR"]
+        .iter()
+        .filter_map(|name| std::env::var(name).ok())
+        .find(|editor| !editor.trim().is_empty())
+}
+
+pub fn editor_args(editor: &str, location: &ErrorLocation) -> Vec<String> {
+    let mut words = editor.split_whitespace();
+    let program = words.next().unwrap_or_default();
+    let mut args: Vec<String> = words.map(str::to_string).collect();
+
+    let name = Path::new(program)
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+    let file = location.file.to_string_lossy().to_string();
+    let line = location.line.unwrap_or(1);
+    let column = location.column.unwrap_or(1);
+
+    match name.as_str() {
+        "code" | "code-insiders" | "codium" | "cursor" => {
+            args.extend(["--goto".to_string(), format!("{}:{}:{}", file, line, column)]);
+        }
+        "subl" | "sublime_text" | "zed" | "hx" | "helix" => args.push(format!("{}:{}:{}", file, line, column)),
+        "idea" | "webstorm" | "phpstorm" | "clion" => {
+            args.extend(["--line".to_string(), line.to_string(), "--column".to_string(), column.to_string(), file]);
+        }
+        _ => args.extend([format!("+{}", line), file]),
+    }
+
+    args
+}
+
+pub fn open(location: &ErrorLocation) -> Result<(), NullScriptError> {
+    let editor = configured_editor().ok_or_else(|| NullScriptError::Io(std::io::Error::new(
+        std::io::ErrorKind::NotFound,
+        tr("editor.not_configured").to_string(),
+    )))?;
+    let program = editor.split_whitespace().next().unwrap_or_default();
+
+    let status = Command::new(program).args(editor_args(&editor, location)).status()?;
+    if !status.success() {
+        output::eline(trf("editor.failed", &[("editor", &program), ("status", &status)]).yellow());
+    }
+
+    Ok(())
+}
+
+pub async fn offer(locations: &[ErrorLocation], open_first: bool) -> Result<(), NullScriptError> {
+    let Some(first) = locations.first() else {
+        return Ok(());
+    };
+    if open_first {
+        return open(first);
+    }
+    if !prompt::is_interactive() || CommandUtils::policy().hermetic || configured_editor().is_none() {
+        return Ok(());
+    }
+
+    let choices: Vec<String> = locations.iter().map(ErrorLocation::to_string).collect();
+    while let Some(index) = prompt::select(tr("editor.pick_title"), &choices, None).await? {
+        open(&locations[index])?;
+        if locations.len() == 1 {
+            break;
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compiler::NullScriptTranspiler;
+
+    #[test]
+    fn test_editor_args_follow_each_editors_convention() {
+        let location = ErrorLocation { file: PathBuf::from("src/app.ns"), line: Some(12), column: Some(4) };
+
+        assert_eq!(editor_args("nvim", &location), vec!["+12", "src/app.ns"]);
+        assert_eq!(editor_args("/usr/bin/code --wait", &location), vec!["--wait", "--goto", "src/app.ns:12:4"]);
+        assert_eq!(editor_args("subl", &location), vec!["src/app.ns:12:4"]);
+        assert_eq!(location.to_string(), "src/app.ns:12:4");
+    }
+
+    #[test]
+    fn test_syntax_errors_point_at_the_offending_column() {
+        let source = "fixed a = 1;\nrun f() {\n    let x: string = \"\";\n}\n";
+        let error = NullScriptTranspiler::new().validate_syntax(source, Some(Path::new("src/app.ns"))).unwrap_err();
+
+        assert_eq!(ErrorLocation::of(&error).unwrap().to_string(), "src/app.ns:3:10");
+    }
+}
@@ -0,0 +1,75 @@
+use crate::compiler::lexer::{Lexer, TokenKind};
+use crate::core::keywords::KEYWORDS;
+use colored::Colorize;
+
+pub struct Example {
+    pub name: &'static str,
+    pub source: &'static str,
+}
+
+pub static EXAMPLES: &[Example] = &[
+    Example { name: "hello", source: include_str!("examples/hello.ns") },
+    Example { name: "classes", source: include_str!("examples/classes.ns") },
+    Example { name: "async", source: include_str!("examples/async.ns") },
+    Example { name: "files", source: include_str!("examples/files.ns") },
+];
+
+impl Example {
+    pub fn find(name: &str) -> Option<&'static Example> {
+        EXAMPLES.iter().find(|example| example.name.eq_ignore_ascii_case(name))
+    }
+
+    pub fn summary_key(&self) -> String {
+        format!("examples.{}", self.name)
+    }
+
+    pub fn highlighted(&self) -> String {
+        let Ok(tokens) = Lexer::new(self.source).tokenize() else {
+            return self.source.to_string();
+        };
+
+        tokens
+            .iter()
+            .map(|token| match token.kind {
+                TokenKind::Identifier if KEYWORDS.iter().any(|(keyword, _)| *keyword == token.text) => {
+                    token.text.yellow().to_string()
+                }
+                TokenKind::String | TokenKind::Template => token.text.green().to_string(),
+                TokenKind::Number => token.text.magenta().to_string(),
+                TokenKind::LineComment | TokenKind::BlockComment => token.text.bright_black().to_string(),
+                _ => token.text.to_string(),
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compiler::NullScriptTranspiler;
+
+    #[test]
+    fn test_examples_transpile_and_run() {
+        let transpiler = NullScriptTranspiler::new();
+        let node_available = std::process::Command::new("node").arg("--version").output().is_ok();
+        let dir = tempfile::tempdir().unwrap();
+
+        for example in EXAMPLES {
+            transpiler.validate_syntax(example.source, None).unwrap();
+            let js = transpiler.transpile(example.source).unwrap();
+
+            if node_available {
+                let js_path = dir.path().join(format!("{}.mjs", example.name));
+                std::fs::write(&js_path, js).unwrap();
+
+                let run = std::process::Command::new("node").arg(&js_path).output().unwrap();
+                assert!(
+                    run.status.success(),
+                    "example '{}' failed:\n{}",
+                    example.name,
+                    String::from_utf8_lossy(&run.stderr)
+                );
+            }
+        }
+    }
+}
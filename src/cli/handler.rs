@@ -1,13 +1,59 @@
-use crate::core::NullScriptError;
-use crate::compiler::NullScriptTranspiler;
+use crate::core::{exit_code, ExternalToolError, NullScriptError, format_error};
+use crate::core::i18n::{tr, trf, trn};
+use crate::compiler::{BuildWarning, Diagnostic, NullScriptTranspiler, PassTimings, TransformRecord};
+use crate::compiler::options::{ModuleFormat, Target, TranspileOptions};
+use crate::compiler::aliases::PathAliases;
+use crate::compiler::banner::Banner;
+use crate::compiler::cache::CACHE_DIR;
+use crate::compiler::naming::FileNames;
+use crate::compiler::codegen::{self, Generated};
+use crate::compiler::codemod::{Codemod, BUILTIN_CODEMODS};
+use crate::compiler::compdb;
+use crate::compiler::depfile;
+use crate::compiler::docs::{self, DocBlock};
+use crate::compiler::converter;
+use crate::compiler::findings;
+use crate::compiler::formatter;
+use crate::compiler::lint::{self, LINT_RULES};
+use crate::compiler::progress::{self, Progress, ProgressEvent};
+use crate::compiler::typecheck;
+use crate::core::config::{DiagnosticLevel, Exclusion, LoadedConfig, NsConfig, OperatorAliases, CONFIG_FILE};
+use crate::core::version::Version;
+use crate::cli::commands::{node_too_old, EXTERNAL_TOOLS, AnalyzeArgs, BuildArgs, CheckArgs, CodemodArgs, CompdbArgs, ConfigArgs, ConvertArgs, ExamplesArgs, FmtArgs, GraphArgs, KeywordsArgs, LintArgs, ProgressArgs, ReplArgs, RunArgs, SelfUpdateArgs};
+use crate::cli::examples::{Example, EXAMPLES};
+use crate::compiler::graph::{self, GraphFormat, GraphOptions};
+use crate::compiler::platform;
+use crate::compiler::resolver::{ModuleGraph, ResolveOptions};
+use crate::compiler::sink::{FsSink, MemorySink};
+use crate::cli::blame;
+use crate::cli::heatmap;
+use crate::cli::editor::{self, ErrorLocation};
+use crate::cli::lsp::LanguageServer;
+use crate::cli::output;
+use crate::cli::prompt;
+use crate::cli::repl::{self, NodeSession, Repl, ReplSession};
 
 use crate::utils::commands::CommandUtils;
-use crate::utils::strings::StringUtils;
+#[cfg(feature = "engine")]
+use crate::utils::engine::{EngineSession, EngineUtils};
 use crate::utils::files::FileUtils;
+use crate::utils::git::GitUtils;
+use crate::utils::paths::PathUtils;
+use crate::utils::heap::{heap_hook_script, HeapSummary};
+use crate::utils::update::{self, Release};
 use colored::Colorize;
+use serde::Serialize;
+use serde_json::{json, Value};
+use globset::Glob;
+use std::collections::{BTreeMap, BTreeSet};
 use std::path::{Path, PathBuf};
+use std::io::Write;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
 use tokio::fs;
 
+const BLAME_FILES_PER_COMMIT: usize = 5;
+
 pub struct CliHandler {
     transpiler: NullScriptTranspiler,
 }
@@ -20,80 +66,460 @@ impl Default for CliHandler {
 
 impl CliHandler {
     pub fn new() -> Self {
-        Self {
-            transpiler: NullScriptTranspiler::new(),
-        }
+        Self::with_transpiler(NullScriptTranspiler::new())
+    }
+
+    pub fn with_transpiler(transpiler: NullScriptTranspiler) -> Self {
+        Self { transpiler }
+    }
+
+    fn transpiler_for(&self, config: &LoadedConfig) -> Result<NullScriptTranspiler, NullScriptError> {
+        Ok(self.transpiler
+            .clone()
+            .with_protected(config.config.protected.clone())
+            .with_keyword_typos(config.config.keyword_typos)
+            .with_migration(config.config.migration)
+            .with_aliases(PathAliases::from_config(config))
+            .with_banner(Banner::from_config(config)?))
+    }
+
+    pub async fn handle_build(&self, args: BuildArgs) -> Result<(), NullScriptError> {
+        let progress = progress_reporter(&args.progress)?;
+        let started = Instant::now();
+        let result = self.build(args, progress.clone()).await;
+        report_completed(progress.as_ref(), "build", started, &result);
+        result.map(|_| ())
     }
 
-    pub async fn handle_build(&self, path: PathBuf, out_dir: PathBuf) -> Result<(), NullScriptError> {
+    async fn build(&self, args: BuildArgs, progress: Option<Progress>) -> Result<(usize, usize), NullScriptError> {
+        let path = args.path.clone();
+        let out_dir = args.out_dir.clone();
+        let config = NsConfig::discover(&path)?;
+        let options = args.transpile_options(&config.config);
+
+        if !args.dry_run {
+            if config.config.generate.iter().any(|rule| rule.command.is_some()) {
+                self.forbid_in_hermetic_mode("nsc build")?;
+            }
+            self.run_generators(&config)?;
+        }
+
         self.show_build_info(&path, &out_dir)?;
-        println!();
+        output::blank();
 
         let metadata = fs::metadata(&path).await?;
+        let file_names = args
+            .file_names
+            .as_deref()
+            .or(config.config.file_names.as_deref())
+            .map(FileNames::parse)
+            .transpose()?;
+        let transpiler = self
+            .transpiler_for(&config)?
+            .with_options(options.clone())
+            .with_file_names(file_names.clone())
+            .with_progress(progress.clone())
+            .with_cache_dir((!args.dry_run && !args.no_cache).then(|| config.base_dir().join(CACHE_DIR)));
 
-        if metadata.is_dir() {
-            let outputs = self
-                .transpiler
-                .build_directory(&path, &out_dir)
-                .await?;
+        let platform = args.platform.or(config.config.platform.clone());
+        let mut case_warnings = self.check_import_case(&path, &config, platform.clone())?;
+        case_warnings.extend(self.external_findings(&path, &config)?);
 
-            println!(
-                "{}",
-                format!("✅ Transpiled {} file(s) to {}", outputs.len(), out_dir.display())
-                    .green()
-            );
+        let (sources, outputs, warning_count) = if args.bundle {
+            if !metadata.is_dir() {
+                return Err(NullScriptError::Io(std::io::Error::new(
+                    std::io::ErrorKind::InvalidInput,
+                    trf("build.bundle_needs_dir", &[("path", &path.display())]),
+                )));
+            }
+            let out_file = args.out.clone().unwrap_or_else(|| out_dir.join("bundle.js"));
+            let mut result = transpiler.bundle(&path, &out_file, platform.as_deref(), &config.entries()).await?;
+            result.warnings.extend(case_warnings);
+
+            output::line(trn("build.bundled", result.sources.len() as u64, &[("file", &out_file.display())]).green());
+            self.show_build_warnings(&result.warnings);
+            let outputs = result.sources.iter().map(|_| out_file.clone()).collect();
+            (result.sources, outputs, result.warnings.len())
+        } else if metadata.is_dir() {
 
-            for file in outputs {
-                println!("{}   → {}", "".clear(), file.display().to_string().bright_black());
+            let (mut result, dry_run) = if args.dry_run {
+                let (result, memory) = transpiler
+                    .build_into(&path, &out_dir, platform.as_deref(), MemorySink::new())
+                    .await?;
+                (result, Some(memory))
+            } else {
+                let result = transpiler
+                    .build_directory(&path, &out_dir, platform.as_deref())
+                    .await?;
+                (result, None)
+            };
+            result.warnings.extend(case_warnings);
+
+            if args.warn_unused || config.config.warn_unused {
+                let entries = config.entries();
+                if !entries.is_empty() {
+                    let module_graph = ModuleGraph::build(&path, &resolve_options(&config, platform.clone()))?;
+                    for file in module_graph.unreachable_from(&entries) {
+                        result.warnings.push(BuildWarning {
+                            file: path.join(module_graph.display_path(file)),
+                            message: tr("build.unused").to_string(),
+                        });
+                    }
+                }
             }
+
+            match dry_run {
+                Some(memory) => {
+                    output::line(trn("build.dry_run", memory.files().len() as u64, &[("dir", &out_dir.display())]).cyan());
+
+                    for (file, contents) in memory.files() {
+                        output::line(format!(
+                            "   → {} {}",
+                            file.display().to_string().bright_black(),
+                            trf("build.dry_run_size", &[("bytes", &contents.len())]).bright_black()
+                        ));
+                    }
+                }
+                None => {
+                    output::line(trn("build.transpiled_dir", result.outputs.len() as u64, &[("dir", &out_dir.display())]).green());
+
+                    for file in &result.outputs {
+                        output::line(format!("   → {}", file.display().to_string().bright_black()));
+                    }
+                    if result.cached > 0 {
+                        output::line(trn("build.cached", result.cached as u64, &[]).bright_black());
+                    }
+                }
+            }
+
+            self.show_build_warnings(&result.warnings);
+            (result.sources, result.outputs, result.warnings.len())
         } else {
-            let output_path = out_dir.join(
-                path.file_stem()
-                    .unwrap_or_default()
-                    .to_string_lossy()
-                    .to_string()
-                    + ".js",
-            );
+            let file_started = Instant::now();
+            if let Some(progress) = &progress {
+                progress.emit(ProgressEvent::TaskStarted { task: "build".to_string(), files: 1 });
+            }
+            let logical = platform::split_variant(&path)
+                .filter(|(_, variant)| Some(*variant) == platform.as_deref())
+                .map_or_else(|| path.clone(), |(base, _)| base);
+            let mut output_path = PathUtils::single_output_path(&logical, &out_dir, "js");
 
-            self.transpiler
-                .transpile_to_js(&path, &output_path)
-                .await?;
+            let mut warnings = match &file_names {
+                Some(file_names) => {
+                    let mut staged = MemorySink::new();
+                    let (_, warnings) = transpiler.transpile_into(&mut staged, &path, &output_path).await?;
+                    let renamed = file_names.rename(staged.into_files())?;
+                    for (file, contents) in renamed.files {
+                        if let Some(parent) = file.parent() {
+                            fs::create_dir_all(parent).await?;
+                        }
+                        fs::write(file, contents).await?;
+                    }
+                    output_path = renamed.names[&output_path].clone();
+                    warnings
+                }
+                None => transpiler.transpile_to_js(&path, &output_path).await?.1,
+            };
+            transpiler.write_runtime(&mut FsSink, output_path.parent().unwrap_or(Path::new("."))).await?;
+            warnings.extend(case_warnings);
 
-            println!(
-                "{}",
-                format!("✅ Transpiled {} → {}", path.display(), output_path.display())
+            output::line(
+                trf("build.transpiled_file", &[("input", &path.display()), ("output", &output_path.display())])
                     .green()
             );
+            self.show_build_warnings(&warnings);
+            if let Some(progress) = &progress {
+                progress.emit(ProgressEvent::FileFinished {
+                    task: "build".to_string(),
+                    file: path.clone(),
+                    output: Some(output_path.clone()),
+                    index: 1,
+                    total: 1,
+                    source_bytes: metadata.len() as usize,
+                    duration_ms: progress::millis(file_started.elapsed()),
+                    warnings: warnings.len(),
+                    cached: false,
+                });
+            }
+            (vec![path.clone()], vec![output_path], warnings.len())
+        };
+        let file_count = sources.len();
+
+        if let Some(destination) = args.explain_transforms {
+            self.explain_transforms(&sources, options, destination).await?;
+        }
+
+        if let Some(depfile_path) = args.depfile.filter(|_| !args.dry_run) {
+            let module_graph = ModuleGraph::build(&path, &resolve_options(&config, platform))?;
+            let targets: Vec<(PathBuf, PathBuf)> = outputs.into_iter().zip(sources).collect();
+
+            if let Some(parent) = depfile_path.parent() {
+                fs::create_dir_all(parent).await?;
+            }
+            fs::write(&depfile_path, depfile::render(&module_graph, &targets)).await?;
+            output::line(trn("build.depfile_written", targets.len() as u64, &[("path", &depfile_path.display())]).green());
+        }
+
+        Ok((file_count, warning_count))
+    }
+
+    fn check_import_case(
+        &self,
+        path: &Path,
+        config: &LoadedConfig,
+        platform: Option<String>,
+    ) -> Result<Vec<BuildWarning>, NullScriptError> {
+        let level = config.config.import_case;
+        if level == DiagnosticLevel::Off {
+            return Ok(Vec::new());
+        }
+
+        let module_graph = ModuleGraph::build(path, &resolve_options(config, platform))?;
+        let warnings: Vec<BuildWarning> = module_graph
+            .case_mismatches()
+            .into_iter()
+            .map(|mismatch| BuildWarning {
+                file: mismatch.file.clone(),
+                message: trf(
+                    "build.import_case",
+                    &[
+                        ("line", &mismatch.line),
+                        ("specifier", &mismatch.specifier),
+                        ("actual", &mismatch.on_disk.display()),
+                        ("fixed", &mismatch.fixed_specifier),
+                    ],
+                ),
+            })
+            .collect();
+
+        if level == DiagnosticLevel::Error && !warnings.is_empty() {
+            let mut message = trn("build.import_case_failed", warnings.len() as u64, &[]);
+            for warning in &warnings {
+                message.push_str(&format!("\n   {}: {}", warning.file.display(), warning.message));
+            }
+            return Err(NullScriptError::Io(std::io::Error::other(message)));
+        }
+
+        Ok(warnings)
+    }
+
+    fn external_findings(&self, path: &Path, config: &LoadedConfig) -> Result<Vec<BuildWarning>, NullScriptError> {
+        let root = PathUtils::absolute(path);
+        let mut warnings = Vec::new();
+
+        for report in config.external_diagnostics() {
+            for external in findings::load(&report, &config.base_dir())? {
+                if external.diagnostic.level == DiagnosticLevel::Off || !external.file.starts_with(&root) {
+                    continue;
+                }
+                let location = match external.diagnostic.column {
+                    Some(column) => format!("{}:{}", external.diagnostic.line, column),
+                    None => external.diagnostic.line.to_string(),
+                };
+                warnings.push(BuildWarning {
+                    file: external.file,
+                    message: trf("build.external_finding", &[("location", &location), ("message", &external.diagnostic.message)]),
+                });
+            }
+        }
+
+        Ok(warnings)
+    }
+
+    fn run_generators(&self, config: &LoadedConfig) -> Result<(), NullScriptError> {
+        if config.config.generate.is_empty() {
+            return Ok(());
+        }
+
+        let generated = codegen::run(config)?;
+        let regenerated: Vec<&Generated> = generated.iter().filter(|generated| generated.regenerated).collect();
+
+        if regenerated.is_empty() {
+            output::line(trn("generate.up_to_date", generated.len() as u64, &[]).bright_black());
+        } else {
+            output::line(trn("generate.regenerated", regenerated.len() as u64, &[]).cyan());
+            for generated in regenerated {
+                output::line(format!("   → {}", generated.output.display().to_string().bright_black()));
+            }
+        }
+        output::blank();
+
+        Ok(())
+    }
+
+    async fn explain_transforms(
+        &self,
+        files: &[PathBuf],
+        options: TranspileOptions,
+        destination: Option<PathBuf>,
+    ) -> Result<(), NullScriptError> {
+        let mut explained = Vec::new();
+        for file in files {
+            let source = fs::read_to_string(file).await?;
+            let transpiler = self.transpiler_for(&NsConfig::discover(file)?)?.with_options(options.clone());
+            let (_, records) = transpiler.explain(&source)?;
+            explained.push((file, records));
+        }
+
+        if let Some(destination) = destination {
+            #[derive(Serialize)]
+            struct ExplainedFile<'a> {
+                file: &'a Path,
+                transforms: &'a [TransformRecord],
+            }
+
+            let document: Vec<ExplainedFile> = explained
+                .iter()
+                .map(|(file, records)| ExplainedFile { file, transforms: records })
+                .collect();
+            fs::write(&destination, serde_json::to_string_pretty(&document)? + "\n").await?;
+
+            output::blank();
+            output::line(trf("explain.written", &[("path", &destination.display())]).cyan());
+            return Ok(());
+        }
+
+        output::blank();
+        output::section(tr("explain.title"));
+        for (file, records) in &explained {
+            output::line(file.display().to_string().cyan());
+            if records.is_empty() {
+                output::line(format!("   {}", tr("explain.none")).bright_black());
+            }
+
+            for record in records {
+                let after = match record.after.as_str() {
+                    "" => tr("explain.removed").to_string(),
+                    after => after.to_string(),
+                };
+                output::line(format!(
+                    "   {:<28} ×{:<4} {}  →  {}",
+                    record.pass.yellow(),
+                    record.count,
+                    record.before,
+                    after.bright_black()
+                ));
+            }
         }
 
         Ok(())
     }
 
-    pub async fn handle_run(&self, file: PathBuf) -> Result<(), NullScriptError> {
-        println!("{}", "🚀 Running NullScript...".cyan());
+    #[cfg(feature = "engine")]
+    async fn run_builtin(&self, file: &Path, config: &LoadedConfig, target: Target, quiet: bool) -> Result<(), NullScriptError> {
+        let project = match &config.path {
+            Some(_) => config.base_dir(),
+            None => file.parent().filter(|parent| !parent.as_os_str().is_empty()).unwrap_or(Path::new(".")).to_path_buf(),
+        };
+        let bundle_js = file.with_extension("temp.js");
+        let result = self
+            .transpiler_for(config)?
+            .with_options(
+                TranspileOptions::new()
+                    .with_target(target)
+                    .with_top_level_await(config.config.top_level_await)
+                    .with_operator_aliases(config.config.operator_aliases),
+            )
+            .bundle(&project, &bundle_js, config.config.platform.as_deref(), &[file.to_path_buf()])
+            .await;
+        let script = fs::read_to_string(&bundle_js).await;
+        let _ = fs::remove_file(&bundle_js).await;
+
+        let result = result?;
+        if !quiet {
+            self.show_build_warnings(&result.warnings);
+        }
+        EngineUtils::run(&script?, &file.display().to_string())
+    }
+
+    #[cfg(not(feature = "engine"))]
+    async fn run_builtin(&self, _file: &Path, _config: &LoadedConfig, _target: Target, _quiet: bool) -> Result<(), NullScriptError> {
+        Err(NullScriptError::Io(std::io::Error::other(tr("run.engine_unavailable"))))
+    }
+
+    pub async fn handle_run(&self, args: RunArgs) -> Result<(), NullScriptError> {
+        let file = args.file;
+        if !args.quiet {
+            output::line(tr("run.running").cyan());
+        }
 
         let temp_js = file.with_extension("temp.js");
 
-        self.transpiler
+        let config = NsConfig::discover(&file)?;
+        if !args.allow_outside {
+            self.warn_if_outside_project(&config, &file)?;
+        }
+
+        let target = config.config.target.unwrap_or_default();
+        if args.engine == "builtin" {
+            if args.inspect_memory {
+                return Err(NullScriptError::Io(std::io::Error::other(tr("run.engine_inspect"))));
+            }
+            return self.run_builtin(&file, &config, target, args.quiet).await;
+        }
+
+        let (source_map, warnings) = self.transpiler_for(&config)?
+            .with_options(
+                TranspileOptions::new()
+                    .with_target(target)
+                    .with_top_level_await(config.config.top_level_await)
+                    .with_operator_aliases(config.config.operator_aliases),
+            )
             .transpile_to_js(&file, &temp_js)
             .await?;
+        if let Some(warning) = CommandUtils::node_version().and_then(|node| node_too_old(node, target)) {
+            output::eline(warning.yellow());
+        }
+        if !args.quiet {
+            self.show_build_warnings(&warnings);
+        }
+
+        let inspection = if args.inspect_memory {
+            Some(MemoryInspection::prepare(&file, &args.reports_dir).await?)
+        } else {
+            None
+        };
 
-        let output = CommandUtils::execute_node(&temp_js);
+        let output = match &inspection {
+            Some(inspection) => CommandUtils::execute_node_with_args(&temp_js, &inspection.node_args),
+            None => CommandUtils::execute_node(&temp_js),
+        };
 
         let _ = fs::remove_file(&temp_js).await;
 
         match output {
             Ok(output) => {
+                print!("{}", String::from_utf8_lossy(&output.stdout));
+
                 if !output.status.success() {
-                    eprintln!("{}", "❌ Runtime error:".red());
-                    eprintln!("{}", String::from_utf8_lossy(&output.stderr));
-                    std::process::exit(1);
-                } else {
-                    print!("{}", String::from_utf8_lossy(&output.stdout));
+                    let stderr = source_map.rewrite_stack(&String::from_utf8_lossy(&output.stderr));
+                    if let Some(inspection) = &inspection {
+                        inspection.finish(&file).await;
+                    }
+                    if args.quiet {
+                        eprint!("{}", stderr);
+                        std::process::exit(exit_code(&output.status));
+                    }
+
+                    let script = temp_js.to_string_lossy();
+                    let node_args: Vec<&str> = inspection
+                        .iter()
+                        .flat_map(|inspection| inspection.node_args.iter().map(String::as_str))
+                        .chain([script.as_ref()])
+                        .collect();
+                    return Err(ExternalToolError::from_output("node", &node_args, &output).with_stderr(stderr).into());
+                }
+
+                eprint!("{}", String::from_utf8_lossy(&output.stderr));
+                if let Some(inspection) = &inspection {
+                    inspection.finish(&file).await;
                 }
             }
             Err(e) => {
-                eprintln!("{} {}", "❌ Failed to run:".red(), e);
+                if let Some(inspection) = &inspection {
+                    let _ = fs::remove_file(&inspection.hook_path).await;
+                }
+                output::eline(format!("{} {}", tr("run.failed").red(), e));
                 std::process::exit(1);
             }
         }
@@ -101,7 +527,1131 @@ impl CliHandler {
         Ok(())
     }
 
+    fn warn_if_outside_project(&self, config: &LoadedConfig, file: &Path) -> Result<(), NullScriptError> {
+        let (Some(exclusion), Some(config_path)) = (config.exclusion(file)?, &config.path) else {
+            return Ok(());
+        };
+
+        let reason = exclusion_reason(&exclusion);
+
+        output::eline(
+            trf("run.outside_project", &[("path", &file.display()), ("config", &config_path.display()), ("reason", &reason)])
+                .yellow()
+        );
+        output::eline(tr("run.outside_project_tip").bright_black());
+
+        Ok(())
+    }
+
+    pub fn show_build_warnings(&self, warnings: &[BuildWarning]) {
+        if warnings.is_empty() {
+            return;
+        }
+
+        output::blank();
+        output::line(trn("build.warnings", warnings.len() as u64, &[]).yellow());
+
+        for warning in warnings {
+            output::line(format!(
+                "   {} {}",
+                format!("{}:", warning.file.display()).yellow(),
+                warning.message.bright_black()
+            ));
+        }
+    }
+
+    pub async fn handle_graph(&self, args: GraphArgs) -> Result<(), NullScriptError> {
+        let config = NsConfig::discover(&args.path)?;
+        let module_graph = ModuleGraph::build(&args.path, &resolve_options(&config, None))?;
+
+        let format = match args.format.as_str() {
+            "mermaid" => GraphFormat::Mermaid,
+            "json" => GraphFormat::Json,
+            _ => GraphFormat::Dot,
+        };
+
+        let options = GraphOptions {
+            cluster: args.cluster,
+            highlight_cycles: args.cycles,
+            include_external: args.external,
+            entries: args.entry,
+            max_depth: args.depth,
+        };
+
+        let rendered = graph::render(&module_graph, format, &options);
+
+        match args.output {
+            Some(output_path) => {
+                if let Some(parent) = output_path.parent() {
+                    fs::create_dir_all(parent).await?;
+                }
+                fs::write(&output_path, rendered).await?;
+                output::line(
+                    trn("graph.written", module_graph.modules.len() as u64, &[("path", &output_path.display())]).green()
+                );
+            }
+            None => print!("{}", rendered),
+        }
+
+        Ok(())
+    }
+
+    pub async fn handle_compdb(&self, args: CompdbArgs) -> Result<(), NullScriptError> {
+        let config = NsConfig::discover(&args.path)?;
+        let module_graph = ModuleGraph::build(&args.path, &resolve_options(&config, args.platform.clone()))?;
+        let commands = compdb::generate(&module_graph, &args.path, &args.out_dir, &config, &args.emit.transpile_options(&config.config))?;
+        let database = serde_json::to_string_pretty(&commands)? + "\n";
+
+        match args.output {
+            Some(output_path) => {
+                if let Some(parent) = output_path.parent() {
+                    fs::create_dir_all(parent).await?;
+                }
+                fs::write(&output_path, database).await?;
+                output::line(
+                    trn("compdb.written", commands.len() as u64, &[("path", &output_path.display())]).green()
+                );
+            }
+            None => print!("{}", database),
+        }
+
+        Ok(())
+    }
+
+    pub fn handle_analyze(&self, args: AnalyzeArgs) -> Result<(), NullScriptError> {
+        let progress = progress_reporter(&args.progress)?;
+        let started = Instant::now();
+        let result = self.analyze(args, progress.as_ref());
+        report_completed(progress.as_ref(), "analyze", started, &result);
+        result.map(|_| ())
+    }
+
+    fn analyze(&self, args: AnalyzeArgs, progress: Option<&Progress>) -> Result<(usize, usize), NullScriptError> {
+        let config = NsConfig::discover(&args.path)?;
+        let mut entries = config.entries();
+        entries.extend(args.entry.iter().map(|entry| PathUtils::absolute(entry)));
+
+        let module_graph = ModuleGraph::build(&args.path, &resolve_options(&config, None))?;
+
+        let module_count = module_graph.modules.len();
+        if let Some(progress) = progress {
+            progress.emit(ProgressEvent::TaskStarted { task: "analyze".to_string(), files: module_count });
+        }
+
+        output::heading(tr("analyze.title"));
+        output::line(trf("analyze.modules", &[("count", &module_count)]));
+
+        if let Some(config_path) = &config.path {
+            output::line(trf("analyze.config", &[("path", &config_path.display())]));
+        }
+        if let Some(revision) = GitUtils::revision(&args.path) {
+            let branch = revision.branch.unwrap_or_else(|| tr("analyze.detached").to_string());
+            output::line(trf("analyze.revision", &[("commit", &revision.commit), ("branch", &branch)]));
+        }
+
+        let mut modules: Vec<&PathBuf> = module_graph.modules.keys().collect();
+        if let Some(reference) = &args.changed_only {
+            let changed = GitUtils::changed_lines(reference, &args.path)?;
+            modules.retain(|file| dunce::canonicalize(file).is_ok_and(|real| changed.contains_key(&real)));
+        }
+        if let Some(sample) = args.sample {
+            modules = sample.pick(modules);
+        }
+        if modules.len() < module_count {
+            output::line(trf("analyze.scope", &[("count", &modules.len()), ("total", &module_count)]));
+        }
+
+        if args.profile_build {
+            self.show_pass_timings(&module_graph, &modules, &config, progress)?;
+        }
+        if args.minify_sizes {
+            self.show_minify_sizes(&module_graph, &modules, &config)?;
+        }
+        if let Some(range) = &args.blame_size {
+            self.show_size_blame(&args.path, range, &config)?;
+        }
+        if let Some(file) = &args.heatmap {
+            let modules: Vec<PathBuf> = modules.iter().map(|file| file.to_path_buf()).collect();
+            let heatmap = match &args.heatmap_since {
+                Some(since) => heatmap::over_time(&args.path, since)?,
+                None => heatmap::by_directory(&module_graph.root, &modules)?,
+            };
+            std::fs::write(file, heatmap::render_html(&heatmap))?;
+            output::blank();
+            output::line(trf("analyze.heatmap_written", &[("path", &file.display())]).green());
+        }
+
+        output::blank();
+        output::section(tr("analyze.unused_title"));
+
+        if entries.is_empty() {
+            output::line(tr("analyze.no_entries").yellow());
+            return Ok((module_count, 1));
+        }
+
+        for entry in &entries {
+            output::line(trf("analyze.entry", &[("path", &module_graph.display_path(entry))]).bright_black());
+        }
+
+        let unused = module_graph.unreachable_from(&entries);
+        if unused.is_empty() {
+            output::line(tr("analyze.unused_none").green());
+        } else {
+            output::line(trn("analyze.unused_count", unused.len() as u64, &[]).yellow());
+            for file in &unused {
+                output::line(format!("   → {}", module_graph.display_path(file)));
+            }
+        }
+
+        Ok((module_count, unused.len()))
+    }
+
+    pub async fn handle_check(&self, args: CheckArgs) -> Result<(), NullScriptError> {
+        if !args.docs.is_empty() {
+            return self.check_docs(&args).await;
+        }
+
+        let config = NsConfig::discover(&args.path)?;
+        let changed = args
+            .changed
+            .as_deref()
+            .map(|reference| GitUtils::changed_lines(reference, &args.path))
+            .transpose()?;
+        let module_graph = ModuleGraph::build(&args.path, &resolve_options(&config, None))?;
+
+        let mut scopes = BTreeMap::new();
+        for file in module_graph.modules.keys() {
+            let scope = match &changed {
+                Some(changed) => match dunce::canonicalize(file).ok().and_then(|real| changed.get(&real)) {
+                    Some(change) => Some(change),
+                    None => continue,
+                },
+                None => None,
+            };
+            scopes.insert(file, scope);
+        }
+
+        if let (Some(reference), true) = (&args.changed, scopes.is_empty()) {
+            output::line(trf("check.no_changes", &[("reference", reference)]).green());
+            return Ok(());
+        }
+
+        let mut problems: BTreeMap<PathBuf, Vec<Diagnostic>> = BTreeMap::new();
+        let mut failures = Vec::new();
+        for file in scopes.keys() {
+            let source = std::fs::read_to_string(file)?;
+            match self.transpiler_for(&NsConfig::discover(file)?)?.diagnostics(&source, file) {
+                Ok(diagnostics) => problems.entry(file.to_path_buf()).or_default().extend(diagnostics),
+                Err(error) => failures.push(error),
+            }
+        }
+
+        if let Some(checker) = &args.types {
+            let files: Vec<&PathBuf> = scopes.keys().copied().collect();
+            for (file, diagnostic) in self.type_check(&config, &files, checker).await? {
+                problems.entry(file).or_default().push(diagnostic);
+            }
+        }
+
+        if config.config.import_case != DiagnosticLevel::Off {
+            for mismatch in module_graph.case_mismatches() {
+                let Some((file, _)) = scopes.get_key_value(&mismatch.file) else {
+                    continue;
+                };
+                problems.entry(file.to_path_buf()).or_default().push(Diagnostic {
+                    line: mismatch.line,
+                    column: Some(mismatch.column),
+                    message: trf(
+                        "check.import_case",
+                        &[
+                            ("specifier", &mismatch.specifier),
+                            ("actual", &mismatch.on_disk.display()),
+                            ("fixed", &mismatch.fixed_specifier),
+                        ],
+                    ),
+                    level: config.config.import_case,
+                });
+            }
+        }
+
+        let mut ignored = 0;
+        let mut reports = config.external_diagnostics();
+        reports.extend(args.diagnostics.iter().map(|report| PathUtils::absolute(report)));
+        for report in &reports {
+            for external in findings::load(report, &config.base_dir())? {
+                if changed.is_some() && !scopes.contains_key(&external.file) {
+                    ignored += 1;
+                    continue;
+                }
+                problems.entry(external.file).or_default().push(external.diagnostic);
+            }
+        }
+
+        let mut reported = 0;
+        let mut errors = failures.len();
+        let mut locations: Vec<ErrorLocation> = failures.iter().filter_map(ErrorLocation::of).collect();
+        for error in &failures {
+            output::eline(format_error(error).red());
+        }
+        for (file, diagnostics) in &mut problems {
+            let scope = scopes.get(file).copied().flatten();
+            diagnostics.sort_by_key(|diagnostic| (diagnostic.line, diagnostic.column));
+
+            for diagnostic in diagnostics.iter() {
+                if scope.is_some_and(|change| !change.contains(diagnostic.line)) {
+                    ignored += 1;
+                    continue;
+                }
+
+                let location = match diagnostic.column {
+                    Some(column) => format!("{}:{}:{}:", module_graph.display_path(file), diagnostic.line, column),
+                    None => format!("{}:{}:", module_graph.display_path(file), diagnostic.line),
+                };
+                let location = match diagnostic.level {
+                    DiagnosticLevel::Error => {
+                        errors += 1;
+                        locations.push(ErrorLocation {
+                            file: file.clone(),
+                            line: Some(diagnostic.line),
+                            column: diagnostic.column,
+                        });
+                        location.red()
+                    }
+                    _ => location.yellow(),
+                };
+                output::line(format!("   {} {}", location, diagnostic.message));
+                reported += 1;
+            }
+        }
+
+        if reported + failures.len() > 0 {
+            output::blank();
+        }
+        if ignored > 0 {
+            output::line(trn("check.ignored", ignored as u64, &[]).bright_black());
+        }
+        if reported + failures.len() == 0 {
+            output::line(trn("check.clean", scopes.len() as u64, &[]).green());
+        } else if errors == 0 {
+            output::line(trn("check.problems", reported as u64, &[]).yellow());
+        }
+
+        if errors > 0 {
+            editor::offer(&locations, args.open_editor).await?;
+            return Err(NullScriptError::Io(std::io::Error::other(trn("check.failed", errors as u64, &[]))));
+        }
+
+        Ok(())
+    }
+
+    async fn type_check(&self, config: &LoadedConfig, files: &[&PathBuf], checker: &str) -> Result<Vec<(PathBuf, Diagnostic)>, NullScriptError> {
+        let directory = std::env::temp_dir().join(format!("nsc-types-{}", std::process::id()));
+        let base_dir = PathUtils::absolute(&config.base_dir());
+
+        let mut outputs = Vec::new();
+        for file in files {
+            let file_config = NsConfig::discover(file)?;
+            let relative = PathUtils::relative_to(&PathUtils::absolute(file), &base_dir)
+                .unwrap_or_else(|| PathBuf::from(file.file_name().unwrap_or_default()));
+            let js_path = PathUtils::absolute(&directory.join(relative).with_extension("js"));
+            let transpiled = self
+                .transpiler_for(&file_config)?
+                .with_options(
+                    TranspileOptions::new()
+                        .with_target(file_config.config.target.unwrap_or_default())
+                        .with_top_level_await(file_config.config.top_level_await)
+                        .with_operator_aliases(file_config.config.operator_aliases),
+                )
+                .transpile_to_js(file, &js_path)
+                .await;
+            if let Ok((source_map, _)) = transpiled {
+                outputs.push((js_path, file.to_path_buf(), source_map));
+            }
+        }
+        if outputs.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let (command, mut command_args) = typecheck::checker_command(checker);
+        command_args.extend(outputs.iter().map(|(js_path, _, _)| js_path.display().to_string()));
+        let command_args: Vec<&str> = command_args.iter().map(String::as_str).collect();
+        let result = CommandUtils::execute_command(&command, &command_args);
+        let _ = fs::remove_dir_all(&directory).await;
+
+        let output = match result {
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => {
+                return Err(NullScriptError::Io(std::io::Error::new(
+                    std::io::ErrorKind::NotFound,
+                    trf("check.types_missing", &[("command", &command)]),
+                )));
+            }
+            result => result?,
+        };
+        let findings = typecheck::parse_output(&format!(
+            "{}\n{}",
+            String::from_utf8_lossy(&output.stdout),
+            String::from_utf8_lossy(&output.stderr)
+        ));
+        if findings.is_empty() && !output.status.success() {
+            return Err(ExternalToolError::from_output(&command, &command_args, &output).into());
+        }
+
+        Ok(findings
+            .into_iter()
+            .filter_map(|finding| {
+                let js_path = PathUtils::absolute(&finding.file);
+                let (_, file, source_map) = outputs.iter().find(|(output, _, _)| *output == js_path)?;
+                Some((
+                    file.clone(),
+                    Diagnostic {
+                        line: source_map.original_line(finding.line).unwrap_or(finding.line),
+                        column: source_map.original_column(finding.line, finding.column),
+                        message: finding.message,
+                        level: finding.level,
+                    },
+                ))
+            })
+            .collect())
+    }
+
+    pub async fn handle_lint(&self, args: LintArgs) -> Result<(), NullScriptError> {
+        if args.rules {
+            let levels = lint::rule_levels(&NsConfig::discover(Path::new("."))?.config.lint)?;
+            output::heading(tr("lint.title"));
+            for rule in LINT_RULES {
+                output::line(format!("{:<18} {:<6} {}", rule.name.cyan(), levels[rule.name].as_str(), rule.description));
+            }
+            return Ok(());
+        }
+
+        let files = source_files(&args.paths)?;
+        let mut reported = 0;
+        let mut errors = 0;
+        for file in &files {
+            let levels = lint::rule_levels(&NsConfig::discover(file)?.config.lint)?;
+            let source = fs::read_to_string(file).await?;
+
+            for finding in lint::lint(&source)? {
+                let location = format!("{}:{}:{}:", file.display(), finding.line, finding.column);
+                let location = match levels[finding.rule] {
+                    DiagnosticLevel::Off => continue,
+                    DiagnosticLevel::Warn => location.yellow(),
+                    DiagnosticLevel::Error => {
+                        errors += 1;
+                        location.red()
+                    }
+                };
+                output::line(format!("   {} {} [{}]", location, finding.message, finding.rule));
+                reported += 1;
+            }
+        }
+
+        if reported > 0 {
+            output::blank();
+        }
+        if reported == 0 {
+            output::line(trn("lint.clean", files.len() as u64, &[]).green());
+        } else if errors == 0 {
+            output::line(trn("check.problems", reported as u64, &[]).yellow());
+        }
+
+        if errors > 0 {
+            return Err(NullScriptError::Io(std::io::Error::other(trn("check.failed", errors as u64, &[]))));
+        }
+
+        Ok(())
+    }
+
+    async fn check_docs(&self, args: &CheckArgs) -> Result<(), NullScriptError> {
+        let files = matching_files(&args.docs, docs::is_markdown_file)?;
+        let temp_dir = std::env::temp_dir();
+        let mut blocks = 0;
+        let mut reported = 0;
+        let mut failed = 0;
+        let mut locations = Vec::new();
+
+        for file in &files {
+            let markdown = std::fs::read_to_string(file)?;
+            let transpiler = self.transpiler_for(&NsConfig::discover(file)?)?;
+
+            for block in docs::nullscript_blocks(&markdown) {
+                blocks += 1;
+                let result = match transpiler.diagnostics(&block.source, file) {
+                    Ok(diagnostics) => {
+                        let mut passed = true;
+                        for diagnostic in &diagnostics {
+                            let line = block.line + diagnostic.line - 1;
+                            let location = match diagnostic.column {
+                                Some(column) => format!("{}:{}:{}:", file.display(), line, column),
+                                None => format!("{}:{}:", file.display(), line),
+                            };
+                            let location = match diagnostic.level {
+                                DiagnosticLevel::Error => {
+                                    passed = false;
+                                    locations.push(ErrorLocation { file: file.clone(), line: Some(line), column: diagnostic.column });
+                                    location.red()
+                                }
+                                _ => location.yellow(),
+                            };
+                            output::line(format!("   {} {}", location, diagnostic.message));
+                            reported += 1;
+                        }
+
+                        if passed && args.run_docs && block.run {
+                            let run = self.run_doc_block(&transpiler, &block, file, &temp_dir, blocks).await;
+                            if let Err(NullScriptError::ExternalTool(_)) = &run {
+                                output::eline(format!("   {}:{}:", file.display(), block.line).red());
+                                locations.push(ErrorLocation { file: file.clone(), line: Some(block.line), column: None });
+                            }
+                            run.map(|_| true)
+                        } else {
+                            Ok(passed)
+                        }
+                    }
+                    Err(error) => Err(docs::relocate(error, file, block.line)),
+                };
+
+                match result {
+                    Ok(true) => {}
+                    Ok(false) => failed += 1,
+                    Err(error) => {
+                        output::eline(format_error(&error).red());
+                        locations.extend(ErrorLocation::of(&error));
+                        reported += 1;
+                        failed += 1;
+                    }
+                }
+            }
+        }
+
+        if reported > 0 {
+            output::blank();
+        }
+        if failed == 0 {
+            let summary = trn("check.docs_clean", blocks as u64, &[("files", &files.len())]);
+            output::line(if reported == 0 { summary.green() } else { summary.yellow() });
+            return Ok(());
+        }
+
+        editor::offer(&locations, args.open_editor).await?;
+        Err(NullScriptError::Io(std::io::Error::other(trn("check.docs_failed", failed as u64, &[]))))
+    }
+
+    async fn run_doc_block(
+        &self,
+        transpiler: &NullScriptTranspiler,
+        block: &DocBlock,
+        file: &Path,
+        temp_dir: &Path,
+        index: usize,
+    ) -> Result<(), NullScriptError> {
+        let js = transpiler.transpile(&block.source).map_err(|error| docs::relocate(error, file, block.line))?;
+        let script = temp_dir.join(format!("nsc-doc-{}-{}.js", std::process::id(), index));
+        fs::write(&script, js).await?;
+        let result = CommandUtils::execute_node(&script);
+        let _ = fs::remove_file(&script).await;
+
+        let output = result?;
+        if output.status.success() {
+            return Ok(());
+        }
+
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let stderr: Vec<&str> = stderr.lines().take_while(|line| !line.trim_start().starts_with("at ")).collect();
+        Err(ExternalToolError::from_output("node", &[&script.to_string_lossy()], &output)
+            .with_stderr(stderr.join("\n").trim().to_string())
+            .into())
+    }
+
+    fn show_size_blame(&self, path: &Path, range: &str, config: &LoadedConfig) -> Result<(), NullScriptError> {
+        let blame = blame::blame_size(path, range, &self.transpiler_for(config)?)?;
+
+        output::blank();
+        output::section(&trf("analyze.blame_title", &[("from", &blame.from), ("to", &blame.to)]));
+        output::line(format!(
+            "   {} → {} ({})",
+            FileUtils::format_file_size(blame.before),
+            FileUtils::format_file_size(blame.after),
+            blame::signed_size(blame.after as i64 - blame.before as i64)
+        ));
+        if blame.failed > 0 {
+            output::line(trn("analyze.blame_failed", blame.failed as u64, &[]).yellow());
+        }
+        if blame.commits.is_empty() {
+            output::line(tr("analyze.blame_unchanged").green());
+            return Ok(());
+        }
+
+        output::blank();
+        output::line(tr("analyze.blame_commits").bold());
+        for commit in &blame.commits {
+            let growth = format!("{:>10}", blame::signed_size(commit.growth));
+            let growth = if commit.growth > 0 { growth.red() } else { growth.green() };
+            output::line(format!("   {}  {}  {}", growth, commit.commit.yellow(), commit.subject));
+            for (file, delta) in commit.files.iter().take(BLAME_FILES_PER_COMMIT) {
+                output::line(format!("   {:>10}      {}", blame::signed_size(*delta), file).bright_black());
+            }
+            if commit.files.len() > BLAME_FILES_PER_COMMIT {
+                let more = commit.files.len() - BLAME_FILES_PER_COMMIT;
+                output::line(format!("   {:>10}      {}", "", trn("analyze.blame_more", more as u64, &[])).bright_black());
+            }
+        }
+
+        output::blank();
+        output::line(tr("analyze.blame_files").bold());
+        for (file, delta) in &blame.files {
+            output::line(format!("   {:>10}  {}", blame::signed_size(*delta), file));
+        }
+
+        Ok(())
+    }
+
+    fn show_minify_sizes(&self, module_graph: &ModuleGraph, modules: &[&PathBuf], config: &LoadedConfig) -> Result<(), NullScriptError> {
+        let options = TranspileOptions::new()
+            .with_target(config.config.target.unwrap_or_default())
+            .with_top_level_await(config.config.top_level_await)
+            .with_operator_aliases(config.config.operator_aliases);
+        let plain = self.transpiler_for(config)?.with_options(options.clone());
+        let minified = self.transpiler_for(config)?.with_options(options.with_minify(true));
+
+        let mut rows = Vec::new();
+        for file in modules {
+            let source = std::fs::read_to_string(file)?;
+            let before = plain.transpile(&source)?.len() as u64;
+            let after = minified.transpile(&source)?.len() as u64;
+            rows.push((module_graph.display_path(file), before, after));
+        }
+        rows.sort_by_key(|(_, before, after)| std::cmp::Reverse(*before as i64 - *after as i64));
+        let before: u64 = rows.iter().map(|(_, before, _)| before).sum();
+        let after: u64 = rows.iter().map(|(_, _, after)| after).sum();
+        rows.push((tr("analyze.profile_total").to_string(), before, after));
+
+        let width = rows.iter().map(|(file, _, _)| file.chars().count()).max().unwrap_or(0).max(tr("analyze.profile_file").chars().count());
+        let saved = |before: u64, after: u64| match before {
+            0 => "0.0%".to_string(),
+            _ => format!("{:.1}%", (before as f64 - after as f64) * 100.0 / before as f64),
+        };
+
+        output::blank();
+        output::section(tr("analyze.minify_title"));
+        output::line(
+            format!(
+                "   {:<width$} {:>10} {:>10} {:>8}",
+                tr("analyze.profile_file"),
+                tr("analyze.minify_before"),
+                tr("analyze.minify_after"),
+                tr("analyze.minify_saved"),
+                width = width
+            )
+            .bright_black(),
+        );
+        for (index, (file, before, after)) in rows.iter().enumerate() {
+            let line = format!(
+                "   {:<width$} {:>10} {:>10} {:>8}",
+                file,
+                FileUtils::format_file_size(*before),
+                FileUtils::format_file_size(*after),
+                saved(*before, *after),
+                width = width
+            );
+            if index + 1 == rows.len() {
+                output::line(line.bold());
+            } else {
+                output::line(line);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn show_pass_timings(
+        &self,
+        module_graph: &ModuleGraph,
+        modules: &[&PathBuf],
+        config: &LoadedConfig,
+        progress: Option<&Progress>,
+    ) -> Result<(), NullScriptError> {
+        let transpiler = self.transpiler_for(config)?;
+        let mut rows = Vec::new();
+        let mut totals = PassTimings::default();
+
+        let total = modules.len();
+        for (index, file) in modules.iter().enumerate() {
+            let source = std::fs::read_to_string(file)?;
+            let (_, timings) = transpiler.profile(&source)?;
+            if let Some(progress) = progress {
+                progress.emit(ProgressEvent::FileFinished {
+                    task: "analyze".to_string(),
+                    file: file.to_path_buf(),
+                    output: None,
+                    index: index + 1,
+                    total,
+                    source_bytes: source.len(),
+                    duration_ms: progress::millis(timings.total()),
+                    warnings: 0,
+                    cached: false,
+                });
+            }
+            totals.add(&timings);
+            rows.push((module_graph.display_path(file), timings));
+        }
+        rows.sort_by_key(|(_, timings)| std::cmp::Reverse(timings.total()));
+        rows.push((tr("analyze.profile_total").to_string(), totals));
+
+        let width = rows.iter().map(|(file, _)| file.chars().count()).max().unwrap_or(0).max(tr("analyze.profile_file").chars().count());
+        let millis = |duration: Duration| format!("{:.2}", duration.as_secs_f64() * 1000.0);
+
+        output::blank();
+        output::section(tr("analyze.profile_title"));
+
+        let mut header = format!("   {:<width$}", tr("analyze.profile_file"), width = width);
+        for (pass, _) in totals.passes() {
+            header.push_str(&format!(" {:>11}", pass));
+        }
+        header.push_str(&format!(" {:>11}", tr("analyze.profile_total")));
+        output::line(header.bright_black());
+
+        for (index, (file, timings)) in rows.iter().enumerate() {
+            let mut line = format!("   {:<width$}", file, width = width);
+            for (_, duration) in timings.passes() {
+                line.push_str(&format!(" {:>11}", millis(duration)));
+            }
+            line.push_str(&format!(" {:>11}", millis(timings.total())));
+
+            if index + 1 == rows.len() {
+                output::line(line.bold());
+            } else {
+                output::line(line);
+            }
+        }
+
+        Ok(())
+    }
+
+    pub async fn handle_examples(&self, args: ExamplesArgs) -> Result<(), NullScriptError> {
+        let Some(name) = args.name else {
+            output::heading(tr("examples.title"));
+            for example in EXAMPLES {
+                output::line(format!("  {:<10} {}", example.name.yellow(), tr(&example.summary_key())));
+            }
+            output::blank();
+            output::line(tr("examples.tip").bright_black());
+            return Ok(());
+        };
+
+        let example = Example::find(&name).ok_or_else(|| {
+            let names: Vec<&str> = EXAMPLES.iter().map(|example| example.name).collect();
+            NullScriptError::Io(std::io::Error::new(
+                std::io::ErrorKind::NotFound,
+                trf("examples.unknown", &[("name", &name), ("names", &names.join(", "))]),
+            ))
+        })?;
+
+        output::section(&trf("examples.source", &[("name", &example.name)]));
+        print!("{}", example.highlighted());
+        output::rule(40);
+
+        if !args.run {
+            output::line(trf("examples.run_tip", &[("name", &example.name)]).bright_black());
+            return Ok(());
+        }
+
+        let directory = std::env::temp_dir().join(format!("nsc-examples-{}", std::process::id()));
+        let file = directory.join(format!("{}.ns", example.name));
+        fs::create_dir_all(&directory).await?;
+        fs::write(&file, example.source).await?;
+
+        output::blank();
+        let result = self
+            .handle_run(RunArgs {
+                file,
+                inspect_memory: false,
+                reports_dir: PathBuf::from("reports"),
+                allow_outside: true,
+                quiet: false,
+                engine: "node".to_string(),
+            })
+            .await;
+
+        let _ = fs::remove_dir_all(&directory).await;
+        result
+    }
+
+    pub async fn handle_convert(&self, args: ConvertArgs) -> Result<(), NullScriptError> {
+        let output_path = args.output.clone().unwrap_or_else(|| args.input.with_extension("ns"));
+        let fixture_dir = args.emit_fixture.as_ref().map(|dir| {
+            dir.join(args.input.file_stem().map(|stem| stem.to_string_lossy()).unwrap_or_default().as_ref())
+        });
+
+        for path in [Some(&output_path), fixture_dir.as_ref()].into_iter().flatten() {
+            if path.exists() && !args.force && !prompt::confirm(&trf("convert.overwrite", &[("path", &path.display())]), false).await? {
+                return Err(NullScriptError::Io(std::io::Error::new(
+                    std::io::ErrorKind::AlreadyExists,
+                    trf("convert.exists", &[("path", &path.display())]),
+                )));
+            }
+        }
 
+        let js = fs::read_to_string(&args.input).await?;
+        let operator_aliases = !args.keep_operators && fixture_dir.is_none() && NsConfig::discover(&args.input)?.config.operator_aliases == OperatorAliases::On;
+        let conversion = converter::convert_with(&js, operator_aliases)?;
+        fs::write(&output_path, &conversion.source).await?;
+
+        output::line(
+            trf("convert.done", &[("input", &args.input.display()), ("output", &output_path.display())]).green()
+        );
+
+        let warnings: Vec<BuildWarning> = conversion
+            .collisions
+            .iter()
+            .map(|collision| BuildWarning {
+                file: output_path.clone(),
+                message: trf(
+                    &format!("convert.collision.{}", collision.context),
+                    &[("line", &collision.line), ("word", &collision.word)],
+                ),
+            })
+            .collect();
+        self.show_build_warnings(&warnings);
+        if !warnings.is_empty() {
+            output::line(tr("convert.collision_tip").bright_black());
+        }
+
+        let transpiler = match fixture_dir {
+            Some(_) => self.transpiler.clone(),
+            None => self.transpiler_for(&NsConfig::discover(&output_path)?)?,
+        };
+        let transpiled = transpiler
+            .validate_syntax(&conversion.source, Some(&output_path))
+            .and_then(|_| transpiler.transpile(&conversion.source));
+
+        output::blank();
+        let transpiled = match transpiled {
+            Ok(transpiled) => transpiled,
+            Err(e) => {
+                output::line(tr("convert.roundtrip_failed").yellow());
+                if fixture_dir.is_some() {
+                    output::eline(tr("convert.fixture_failed").red());
+                }
+                return Err(e);
+            }
+        };
+
+        match js.lines().zip(transpiled.lines()).position(|(original, roundtrip)| original != roundtrip) {
+            None if js.lines().count() == transpiled.lines().count() => {
+                output::line(tr("convert.roundtrip_exact").green())
+            }
+            mismatch => {
+                let line = mismatch.unwrap_or_else(|| js.lines().count().min(transpiled.lines().count())) + 1;
+                output::line(trf("convert.roundtrip_differs", &[("line", &line)]).yellow())
+            }
+        }
+
+        if let Some(dir) = fixture_dir {
+            fs::create_dir_all(&dir).await?;
+            fs::write(dir.join("input.js"), &js).await?;
+            fs::write(dir.join("converted.ns"), &conversion.source).await?;
+            fs::write(dir.join("output.js"), &transpiled).await?;
+            output::line(trf("convert.fixture", &[("dir", &dir.display())]).cyan());
+        }
+
+        Ok(())
+    }
+
+    pub async fn handle_fmt(&self, args: FmtArgs) -> Result<(), NullScriptError> {
+        let files = source_files(&args.paths)?;
+        let mut changed = Vec::new();
+
+        for file in &files {
+            let source = fs::read_to_string(file).await?;
+            let formatted = formatter::format(&source, Some(file))?;
+            if formatted == source {
+                continue;
+            }
+
+            if args.check {
+                output::line(trf("fmt.would_change", &[("path", &file.display())]).yellow());
+            } else {
+                fs::write(file, &formatted).await?;
+                output::line(trf("fmt.changed", &[("path", &file.display())]));
+            }
+            changed.push(file);
+        }
+
+        if args.check && !changed.is_empty() {
+            return Err(NullScriptError::Io(std::io::Error::other(trn("fmt.check_failed", changed.len() as u64, &[]))));
+        }
+
+        match changed.len() {
+            0 => output::line(trn("fmt.unchanged", files.len() as u64, &[]).green()),
+            count => output::line(trn("fmt.done", count as u64, &[]).green()),
+        }
+        Ok(())
+    }
+
+    pub async fn handle_codemod(&self, args: CodemodArgs) -> Result<(), NullScriptError> {
+        let Some(name) = &args.codemod else {
+            output::heading(tr("codemod.title"));
+            for (name, description) in BUILTIN_CODEMODS {
+                output::line(format!("{:<20} {}", name.cyan(), description));
+            }
+            return Ok(());
+        };
+
+        let protected = NsConfig::discover(Path::new("."))?.config.protected;
+        let codemod = match Codemod::builtin(name) {
+            Some(codemod) => codemod.with_protected(protected),
+            None if Path::new(name).is_file() => Codemod::parse(name, &fs::read_to_string(name).await?)?.with_protected(protected),
+            None => {
+                let builtins: Vec<&str> = BUILTIN_CODEMODS.iter().map(|(name, _)| *name).collect();
+                return Err(NullScriptError::Io(std::io::Error::new(
+                    std::io::ErrorKind::NotFound,
+                    trf("codemod.unknown", &[("name", &name), ("builtins", &builtins.join(", "))]),
+                )));
+            }
+        };
+
+        let mut changed = 0;
+        for file in source_files(&args.paths)? {
+            let source = fs::read_to_string(&file).await?;
+            let rewrite = codemod.apply(&source)?;
+            if rewrite.replacements == 0 {
+                continue;
+            }
+
+            changed += 1;
+            if args.write {
+                fs::write(&file, &rewrite.source).await?;
+                output::line(trn("codemod.rewrote", rewrite.replacements as u64, &[("path", &file.display())]));
+            } else {
+                print_diff(&file, &source, &rewrite.source);
+            }
+        }
+
+        match (changed, args.write) {
+            (0, _) => output::line(tr("codemod.unchanged").green()),
+            (count, true) => output::line(trn("codemod.done", count, &[]).green()),
+            (count, false) => output::line(trn("codemod.dry_run", count, &[]).yellow()),
+        }
+        Ok(())
+    }
+
+    pub fn handle_lsp(&self) -> Result<(), NullScriptError> {
+        let server = LanguageServer::new(|path: &Path| self.transpiler_for(&NsConfig::discover(path)?));
+
+        server.serve(std::io::stdin().lock(), std::io::stdout().lock())
+    }
+
+    pub async fn handle_repl(&self, args: ReplArgs) -> Result<(), NullScriptError> {
+        let config = NsConfig::discover(Path::new("."))?;
+        let transpiler = self.transpiler_for(&config)?.with_options(
+            TranspileOptions::new()
+                .with_target(config.config.target.unwrap_or_default())
+                .with_module(ModuleFormat::Cjs)
+                .with_operator_aliases(config.config.operator_aliases),
+        );
+        let session: Box<dyn ReplSession> = match args.engine.as_str() {
+            "builtin" => builtin_session()?,
+            _ => Box::new(NodeSession::start()?),
+        };
+        let mut repl = Repl::new(transpiler, session);
+
+        output::line(trf("repl.welcome", &[("version", &env!("CARGO_PKG_VERSION")), ("engine", &args.engine)]).cyan());
+        output::line(tr("repl.hint"));
+
+        let mut chunk = String::new();
+        loop {
+            prompt::ask(if chunk.is_empty() { "ns> " } else { "...> " })?;
+            let Some(line) = prompt::read_line().await? else {
+                return Ok(());
+            };
+
+            if chunk.is_empty() {
+                let command = line.trim();
+                match command.split_once(' ').map_or(command, |(name, _)| name) {
+                    "" => continue,
+                    ".exit" => return Ok(()),
+                    ".help" => {
+                        output::line(tr("repl.help"));
+                        continue;
+                    }
+                    ".keywords" => {
+                        let filter = command[".keywords".len()..].trim();
+                        self.handle_keywords(KeywordsArgs {
+                            category: None,
+                            filter: (!filter.is_empty()).then(|| filter.to_string()),
+                            columns: None,
+                            no_pager: true,
+                        })?;
+                        continue;
+                    }
+                    name if name.starts_with('.') => {
+                        output::eline(trf("repl.unknown_command", &[("command", &name)]).yellow());
+                        continue;
+                    }
+                    _ => {}
+                }
+            }
+
+            let submit = line.trim().is_empty();
+            chunk.push_str(&line);
+            if !submit && repl::needs_more(&chunk) {
+                continue;
+            }
+            match repl.eval(&std::mem::take(&mut chunk)) {
+                Ok(Some(value)) => output::line(value),
+                Ok(None) => {}
+                Err(error) => output::eline(format_error(&error).red()),
+            }
+        }
+    }
+
+    pub fn handle_config(&self, args: ConfigArgs) -> Result<(), NullScriptError> {
+        let config = NsConfig::discover(&args.resolve)?;
+        let exclusion = config.exclusion(&args.resolve)?;
+        let Value::Object(values) = serde_json::to_value(&config.config)? else {
+            return Ok(());
+        };
+
+        if args.json {
+            let resolved: serde_json::Map<String, Value> = values
+                .into_iter()
+                .map(|(key, value)| {
+                    let source = config.origins.get(&key).map(|origin| origin.display().to_string());
+                    (key, json!({ "value": value, "source": source }))
+                })
+                .collect();
+            let document = json!({
+                "path": PathUtils::absolute(&args.resolve),
+                "chain": config.chain,
+                "excluded": exclusion.as_ref().map(exclusion_reason),
+                "values": resolved,
+            });
+            println!("{}", serde_json::to_string_pretty(&document)?);
+            return Ok(());
+        }
+
+        output::heading(tr("config.title"));
+        output::line(trf("info.path", &[("path", &args.resolve.display())]));
+
+        match &exclusion {
+            Some(exclusion) => output::line(trf("config.status_excluded", &[("reason", &exclusion_reason(exclusion))]).yellow()),
+            None => output::line(tr("config.status_included").green()),
+        }
+
+        output::blank();
+        output::section(tr("config.chain"));
+        if config.chain.is_empty() {
+            output::line(tr("info.config_none").bright_black());
+        }
+        for path in &config.chain {
+            output::line(format!("   → {}", path.display()));
+        }
+
+        output::blank();
+        output::section(tr("config.values"));
+        let width = values.keys().map(String::len).max().unwrap_or(0);
+        for (key, value) in &values {
+            let source = match config.origins.get(key) {
+                Some(origin) => origin.display().to_string(),
+                None => tr("config.default").to_string(),
+            };
+            output::line(format!(
+                "  {:<width$}  {}  {}",
+                key.yellow(),
+                value,
+                format!("← {}", source).bright_black(),
+                width = width
+            ));
+        }
+
+        Ok(())
+    }
+
+    pub fn check_nsc_version(&self, start: &Path) -> Result<(), NullScriptError> {
+        let config = NsConfig::discover(start)?;
+        let current = Version::current();
+        let Some(range) = config.version_mismatch(&current)? else {
+            return Ok(());
+        };
+
+        let config_path = config.path.as_deref().unwrap_or(Path::new(CONFIG_FILE)).display();
+        let args: [(&str, &dyn std::fmt::Display); 3] = [("config", &config_path), ("range", &range), ("version", &current)];
+
+        if config.config.strict_version {
+            return Err(NullScriptError::Io(std::io::Error::other(trf("config.version_required", &args))));
+        }
+
+        output::eline(trf("config.version_mismatch", &args).yellow());
+        output::eline(tr("config.version_mismatch_tip").bright_black());
+        Ok(())
+    }
+
+    pub fn forbid_in_hermetic_mode(&self, feature: &str) -> Result<(), NullScriptError> {
+        if !CommandUtils::policy().hermetic {
+            return Ok(());
+        }
+
+        let tool = EXTERNAL_TOOLS.iter().find(|(name, _)| *name == feature).map_or("", |(_, tool)| tool);
+        let mut message = trf("hermetic.forbidden", &[("feature", &feature), ("tool", &tool)]);
+        message.push_str(&format!("\n{}", tr("hermetic.features")));
+        for (feature, tool) in EXTERNAL_TOOLS {
+            message.push_str(&format!("\n   • {}: {}", feature, tool));
+        }
+
+        Err(NullScriptError::Io(std::io::Error::new(std::io::ErrorKind::PermissionDenied, message)))
+    }
+
+    pub fn handle_self_update(&self, args: SelfUpdateArgs) -> Result<(), NullScriptError> {
+        let current = env!("CARGO_PKG_VERSION");
+
+        output::line(tr("update.checking").cyan());
+        let release = Release::fetch_latest()?;
+
+        output::line(trf("update.current", &[("version", &current)]));
+        output::line(trf("update.latest", &[("version", &release.version())]));
+
+        if !update::is_newer(release.version(), current) {
+            output::line(tr("update.up_to_date").green());
+            return Ok(());
+        }
+
+        output::line(trf("update.available", &[("version", &release.version())]).yellow());
+        if args.check {
+            return Ok(());
+        }
+
+        let exe = std::env::current_exe()?;
+        let exe = dunce::canonicalize(&exe).unwrap_or(exe);
+        if exe.components().any(|component| component.as_os_str() == "node_modules") {
+            output::line(tr("update.npm_managed").yellow());
+            return Ok(());
+        }
+
+        let asset_name = update::platform_asset_name();
+        let asset = release.asset(&asset_name)?;
+        let checksums = release.asset(update::CHECKSUMS_ASSET)?;
+
+        output::line(trf("update.downloading", &[("asset", &asset_name)]));
+        let bytes = update::download(&asset.browser_download_url)?;
+        let sums = update::download(&checksums.browser_download_url)?;
+        let expected = update::expected_checksum(&String::from_utf8_lossy(&sums), &asset_name).ok_or_else(|| {
+            NullScriptError::Io(std::io::Error::other(format!(
+                "{} does not list a checksum for {}",
+                update::CHECKSUMS_ASSET,
+                asset_name
+            )))
+        })?;
+        update::verify_checksum(&bytes, &expected)?;
+        output::line(tr("update.verified").green());
+
+        update::replace_executable(&exe, &bytes, |staged| {
+            CommandUtils::probe_command(&staged.to_string_lossy(), &["--version"]).is_ok_and(|o| o.status.success())
+        })?;
+
+        output::line(trf("update.installed", &[("version", &release.version()), ("path", &exe.display())]).green());
+        Ok(())
+    }
 
     pub fn get_file_stats(&self, path: &PathBuf) -> Result<(usize, usize), NullScriptError> {
         let mut total_files = 0;
@@ -132,44 +1682,306 @@ impl CliHandler {
     pub fn show_build_info(&self, path: &PathBuf, out_dir: &Path) -> Result<(), NullScriptError> {
         let (total_files, nullscript_files) = self.get_file_stats(path)?;
 
-        let title = StringUtils::capitalize("build information");
-        println!("{}", format!("📊 {}", title).cyan());
-        println!("{}", "=".repeat(30).bright_black());
-        println!("Input path: {}", path.display());
-        println!("Output directory: {}", out_dir.display());
-        println!("Total files: {}", total_files);
-        println!("NullScript files: {}", nullscript_files);
+        output::heading(tr("build.title"));
+        output::line(trf("build.input_path", &[("path", &path.display())]));
+        output::line(trf("build.output_dir", &[("path", &out_dir.display())]));
+        output::line(trf("info.total_files", &[("count", &total_files)]));
+        output::line(trf("info.nullscript_files", &[("count", &nullscript_files)]));
 
         if nullscript_files > 0 {
-            println!("Estimated output: {} JavaScript files", nullscript_files);
+            output::line(trn("build.estimated", nullscript_files as u64, &[]));
         }
 
         Ok(())
     }
 
-    pub fn handle_keywords(&self, _category: Option<String>) -> Result<(), NullScriptError> {
-        use crate::core::keywords::KEYWORDS;
+    pub fn handle_keywords(&self, args: KeywordsArgs) -> Result<(), NullScriptError> {
+        use crate::core::keywords::{keyword_categories, KeywordCategory};
+
+        let category = match args.category.as_deref() {
+            Some(name) => match KeywordCategory::parse(name) {
+                Some(category) => Some(category),
+                None => {
+                    let expected = KeywordCategory::ALL.map(KeywordCategory::as_str).join(", ");
+                    return Err(NullScriptError::Usage(trf(
+                        "keywords.unknown_category",
+                        &[("category", &name), ("expected", &expected)],
+                    )));
+                }
+            },
+            None => None,
+        };
 
-        println!("{}", "\n🎭 NullScript Keywords".cyan());
-        println!("{}", "=".repeat(50).bright_black());
+        let filter = args.filter.map(|filter| filter.to_lowercase());
+        let matches = |(nullscript_keyword, js_keyword): &&(&str, &str)| {
+            filter.as_deref().is_none_or(|filter| {
+                nullscript_keyword.to_lowercase().contains(filter) || js_keyword.to_lowercase().contains(filter)
+            })
+        };
+        let groups: Vec<(KeywordCategory, Vec<(&str, &str)>)> = keyword_categories()
+            .into_iter()
+            .filter(|(of, _)| category.is_none_or(|c| c == *of))
+            .map(|(name, keywords)| (name, keywords.iter().filter(matches).copied().collect::<Vec<_>>()))
+            .filter(|(_, keywords)| !keywords.is_empty())
+            .collect();
 
-        println!("{}", "\n📋 NullScript → JavaScript Keywords:".cyan());
-        println!("{}", "─".repeat(40).bright_black());
+        let entries = groups.iter().flat_map(|(_, keywords)| keywords.iter());
+        let keyword_width = entries.clone().map(|(keyword, _)| keyword.chars().count()).max().unwrap_or(0);
+        let js_width = entries.map(|(_, js)| js.chars().count()).max().unwrap_or(0);
+        let row_width = keyword_width + js_width + 6;
+        let two_columns = args.columns.or_else(output::terminal_width).is_some_and(|width| width >= row_width * 2 + 4);
 
-        for (nullscript_keyword, js_keyword) in KEYWORDS.iter() {
-            println!(
-                "{}{}",
-                format!("  {:<15}", nullscript_keyword).yellow(),
-                format!("→ {}", js_keyword).white()
-            );
-        }
+        let row = |(nullscript_keyword, js_keyword): &(&str, &str), pad: bool| {
+            let js = if pad { format!("→ {:<1$}", js_keyword, js_width) } else { format!("→ {}", js_keyword) };
+            format!("{}{}", format!("  {:<1$}  ", nullscript_keyword, keyword_width).yellow(), js.white())
+        };
 
-        println!(
-            "{}",
-            "\n💡 Tip: Use NullScript keywords in your .ns files, they will be transpiled to JavaScript"
-                .bright_black()
-        );
+        output::paged(!args.no_pager, || {
+            output::blank();
+            output::heading_with_width(tr("keywords.title"), 50);
+
+            if groups.is_empty() {
+                output::blank();
+                output::line(tr("keywords.no_matches").yellow());
+            }
+
+            for (category, keywords) in &groups {
+                output::blank();
+                output::section(&trn(&format!("keywords.category.{}", category.as_str()), keywords.len() as u64, &[]));
+
+                if two_columns {
+                    let (left, right) = keywords.split_at(keywords.len().div_ceil(2));
+                    for (index, entry) in left.iter().enumerate() {
+                        match right.get(index) {
+                            Some(other) => output::line(format!("{}    {}", row(entry, true), row(other, false))),
+                            None => output::line(row(entry, false)),
+                        }
+                    }
+                } else {
+                    for entry in keywords {
+                        output::line(row(entry, false));
+                    }
+                }
+            }
+
+            output::blank();
+            output::line(tr("keywords.tip").bright_black());
+        });
 
         Ok(())
     }
 }
+
+#[cfg(feature = "engine")]
+fn builtin_session() -> Result<Box<dyn ReplSession>, NullScriptError> {
+    Ok(Box::new(EngineSession::new()?))
+}
+
+#[cfg(not(feature = "engine"))]
+fn builtin_session() -> Result<Box<dyn ReplSession>, NullScriptError> {
+    Err(NullScriptError::Io(std::io::Error::other(tr("run.engine_unavailable"))))
+}
+
+fn exclusion_reason(exclusion: &Exclusion) -> String {
+    match exclusion {
+        Exclusion::OutsideRoot(root_dir) => trf("config.outside_root", &[("dir", &root_dir.display())]),
+        Exclusion::NotIncluded => tr("config.not_included").to_string(),
+        Exclusion::Excluded => tr("config.excluded").to_string(),
+    }
+}
+
+fn resolve_options(config: &LoadedConfig, platform: Option<String>) -> ResolveOptions {
+    ResolveOptions {
+        platform: platform.or_else(|| config.config.platform.clone()),
+        root_dir: config.path.as_ref().map(|_| config.root_dir()),
+        aliases: PathAliases::from_config(config),
+    }
+}
+
+fn source_files(paths: &[String]) -> Result<Vec<PathBuf>, NullScriptError> {
+    matching_files(paths, FileUtils::is_nullscript_file)
+}
+
+fn matching_files(paths: &[String], accept: fn(&Path) -> bool) -> Result<Vec<PathBuf>, NullScriptError> {
+    let mut files = BTreeSet::new();
+
+    for target in paths {
+        let path = Path::new(target);
+        if path.is_file() {
+            files.insert(path.to_path_buf());
+            continue;
+        }
+
+        let (base, matcher) = if path.is_dir() {
+            (path.to_path_buf(), None)
+        } else if target.contains(['*', '?', '[', '{']) {
+            let matcher = Glob::new(target)
+                .map_err(|e| NullScriptError::Io(std::io::Error::new(std::io::ErrorKind::InvalidInput, e)))?
+                .compile_matcher();
+            let base: PathBuf = path
+                .components()
+                .take_while(|component| !component.as_os_str().to_string_lossy().contains(['*', '?', '[', '{']))
+                .collect();
+            (base, Some(matcher))
+        } else {
+            return Err(NullScriptError::Io(std::io::Error::new(
+                std::io::ErrorKind::NotFound,
+                trf("fmt.missing", &[("path", &target)]),
+            )));
+        };
+
+        let root = if base.as_os_str().is_empty() { PathBuf::from(".") } else { base };
+        for entry in walkdir::WalkDir::new(&root).into_iter().filter_map(|e| e.ok()) {
+            let file = entry.path().strip_prefix(".").unwrap_or(entry.path());
+            let matches = matcher.as_ref().is_none_or(|matcher| matcher.is_match(PathUtils::to_slash(file)));
+            if entry.file_type().is_file() && accept(file) && matches {
+                files.insert(file.to_path_buf());
+            }
+        }
+    }
+
+    Ok(files.into_iter().collect())
+}
+
+fn progress_reporter(args: &ProgressArgs) -> Result<Option<Progress>, NullScriptError> {
+    if args.progress.is_none() {
+        return Ok(None);
+    }
+
+    let writer: Box<dyn Write + Send> = match &args.progress_file {
+        Some(path) => Box::new(std::fs::OpenOptions::new().create(true).append(true).open(path)?),
+        None => {
+            output::set_to_stderr(true);
+            Box::new(std::io::stdout())
+        }
+    };
+    let writer = Mutex::new(writer);
+
+    Ok(Some(Progress::new(move |event| {
+        let Ok(mut writer) = writer.lock() else {
+            return;
+        };
+        if let Ok(line) = serde_json::to_string(event) {
+            let _ = writeln!(writer, "{}", line).and_then(|_| writer.flush());
+        }
+    })))
+}
+
+fn report_completed(progress: Option<&Progress>, task: &str, started: Instant, result: &Result<(usize, usize), NullScriptError>) {
+    let Some(progress) = progress else {
+        return;
+    };
+
+    let (files, warnings) = result.as_ref().copied().unwrap_or_default();
+    progress.emit(ProgressEvent::TaskCompleted {
+        task: task.to_string(),
+        files,
+        warnings,
+        duration_ms: progress::millis(started.elapsed()),
+        success: result.is_ok(),
+        error: result.as_ref().err().map(|error| format_error(error).trim_start_matches('❌').trim().to_string()),
+        external_tool: match result {
+            Err(NullScriptError::ExternalTool(e)) => Some(e.clone()),
+            _ => None,
+        },
+    });
+}
+
+fn print_diff(path: &Path, old: &str, new: &str) {
+    let old: Vec<&str> = old.lines().collect();
+    let new: Vec<&str> = new.lines().collect();
+    let prefix = old.iter().zip(&new).take_while(|(a, b)| a == b).count();
+    let suffix = old[prefix..].iter().rev().zip(new[prefix..].iter().rev()).take_while(|(a, b)| a == b).count();
+    let (removed, added) = (&old[prefix..old.len() - suffix], &new[prefix..new.len() - suffix]);
+
+    println!("{}", format!("--- {}", path.display()).bold());
+    println!("{}", format!("+++ {}", path.display()).bold());
+
+    let hunks: Vec<(usize, &[&str], &[&str])> = if removed.len() == added.len() {
+        (0..removed.len())
+            .filter(|&i| removed[i] != added[i])
+            .map(|i| (prefix + i, &removed[i..=i], &added[i..=i]))
+            .collect()
+    } else {
+        vec![(prefix, removed, added)]
+    };
+
+    for (line, removed, added) in hunks {
+        println!("{}", format!("@@ -{},{} +{},{} @@", line + 1, removed.len(), line + 1, added.len()).cyan());
+        for text in removed {
+            println!("{}", format!("-{}", text).red());
+        }
+        for text in added {
+            println!("{}", format!("+{}", text).green());
+        }
+    }
+}
+
+struct MemoryInspection {
+    reports_dir: PathBuf,
+    hook_path: PathBuf,
+    snapshot_path: PathBuf,
+    report_path: PathBuf,
+    node_args: Vec<String>,
+}
+
+impl MemoryInspection {
+    async fn prepare(file: &Path, reports_dir: &Path) -> Result<Self, NullScriptError> {
+        fs::create_dir_all(reports_dir).await?;
+
+        let stamp = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+        let stem = FileUtils::get_stem(file).unwrap_or_else(|| "script".to_string());
+
+        let snapshot_path = reports_dir.join(format!("{}-{}.heapsnapshot", stem, stamp));
+        let report_path = reports_dir.join(format!("{}-{}.memory.txt", stem, stamp));
+        let hook_path = std::env::temp_dir().join(format!("nsc-heap-hook-{}.js", std::process::id()));
+
+        fs::write(&hook_path, heap_hook_script(&snapshot_path)?).await?;
+
+        let node_args = vec![
+            "--require".to_string(),
+            hook_path.to_string_lossy().to_string(),
+            "--heapsnapshot-signal=SIGUSR2".to_string(),
+            format!("--diagnostic-dir={}", reports_dir.to_string_lossy()),
+        ];
+
+        Ok(Self {
+            reports_dir: reports_dir.to_path_buf(),
+            hook_path,
+            snapshot_path,
+            report_path,
+            node_args,
+        })
+    }
+
+    async fn finish(&self, file: &Path) {
+        let _ = fs::remove_file(&self.hook_path).await;
+
+        output::blank();
+        output::heading(tr("memory.title"));
+
+        let summary = match HeapSummary::from_snapshot(&self.snapshot_path) {
+            Ok(summary) => summary,
+            Err(e) => {
+                output::eline(format!("{} {}", tr("memory.read_failed").red(), e));
+                return;
+            }
+        };
+
+        let report = summary.render(file, 20);
+        output::line(summary.render(file, 10).trim_end());
+
+        match fs::write(&self.report_path, report).await {
+            Ok(()) => {
+                output::blank();
+                output::line(trf("memory.report", &[("path", &self.report_path.display())]).bright_black());
+                output::line(trf("memory.snapshot", &[("path", &self.snapshot_path.display())]).bright_black());
+                output::line(trf("memory.signal_tip", &[("dir", &self.reports_dir.display())]).bright_black());
+            }
+            Err(e) => output::eline(format!("{} {}", tr("memory.write_failed").red(), e)),
+        }
+    }
+}
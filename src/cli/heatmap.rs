@@ -0,0 +1,197 @@
+use crate::compiler::lexer::{Lexer, TokenKind};
+use crate::core::keywords::{is_global_alias, JS_RESERVED_WORDS, KEYWORDS};
+use crate::core::NullScriptError;
+use crate::utils::files::FileUtils;
+use crate::utils::git::GitUtils;
+use crate::utils::paths::PathUtils;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+const MAX_COMMIT_COLUMNS: usize = 24;
+
+type Counts = BTreeMap<(bool, &'static str), usize>;
+
+pub struct Heatmap {
+    pub title: String,
+    pub columns: Vec<String>,
+    pub rows: BTreeMap<(bool, &'static str), Vec<usize>>,
+}
+
+pub fn keyword_counts(source: &str) -> Result<Counts, NullScriptError> {
+    let tokens = Lexer::new(source).with_max_depth(usize::MAX).tokenize()?;
+    let mut counts = Counts::new();
+    let mut member = false;
+
+    for token in tokens.iter().filter(|token| !token.is_trivia()) {
+        if token.kind == TokenKind::Identifier && !member {
+            let nullscript = KEYWORDS.iter().find(|(keyword, js_keyword)| *keyword == token.text && keyword != js_keyword);
+            let legacy = KEYWORDS.iter().find(|(keyword, js_keyword)| {
+                *js_keyword == token.text && keyword != js_keyword && (JS_RESERVED_WORDS.contains(js_keyword) || is_global_alias(js_keyword))
+            });
+            match (nullscript, legacy) {
+                (Some((keyword, _)), _) => *counts.entry((false, *keyword)).or_default() += 1,
+                (None, Some((_, js_keyword))) => *counts.entry((true, *js_keyword)).or_default() += 1,
+                _ => {}
+            }
+        }
+        member = token.text == ".";
+    }
+
+    Ok(counts)
+}
+
+pub fn by_directory(root: &Path, files: &[PathBuf]) -> Result<Heatmap, NullScriptError> {
+    let root = dunce::canonicalize(root)?;
+    let mut directories: BTreeMap<String, Counts> = BTreeMap::new();
+    for file in files {
+        let directory = dunce::canonicalize(file)?
+            .parent()
+            .and_then(|parent| PathUtils::relative_to(parent, &root))
+            .map(|relative| PathUtils::to_slash(&relative))
+            .filter(|relative| !relative.is_empty())
+            .unwrap_or_else(|| ".".to_string());
+        let counts = keyword_counts(&std::fs::read_to_string(file)?)?;
+        let totals = directories.entry(directory).or_default();
+        for (key, count) in counts {
+            *totals.entry(key).or_default() += count;
+        }
+    }
+
+    Ok(heatmap("Keyword usage by directory", directories.into_iter().collect()))
+}
+
+pub fn over_time(path: &Path, since: &str) -> Result<Heatmap, NullScriptError> {
+    let root = GitUtils::toplevel(path)?;
+    let pathspec = PathUtils::relative_to(&dunce::canonicalize(path)?, &dunce::canonicalize(&root)?)
+        .map(|relative| PathUtils::to_slash(&relative))
+        .filter(|relative| !relative.is_empty())
+        .unwrap_or_else(|| ".".to_string());
+
+    let measure = |commit: &str, file: &str| -> Result<Option<Counts>, NullScriptError> {
+        if !FileUtils::is_nullscript_file(Path::new(file)) {
+            return Ok(None);
+        }
+        match GitUtils::show(&root, commit, file)? {
+            Some(source) => Ok(keyword_counts(&source).ok()),
+            None => Ok(None),
+        }
+    };
+
+    let mut files: BTreeMap<String, Counts> = BTreeMap::new();
+    for file in GitUtils::tree_files(&root, since, &pathspec)? {
+        if let Some(counts) = measure(since, &file)? {
+            files.insert(file, counts);
+        }
+    }
+
+    let mut columns = vec![(since.to_string(), total(&files))];
+    let mut previous = since.to_string();
+    for (commit, _) in GitUtils::first_parent_commits(&root, since, "HEAD")? {
+        for file in GitUtils::changed_files(&root, &previous, &commit, &pathspec)? {
+            match measure(&commit, &file)? {
+                Some(counts) => files.insert(file, counts),
+                None => files.remove(&file),
+            };
+        }
+        columns.push((commit.clone(), total(&files)));
+        previous = commit;
+    }
+
+    if columns.len() > MAX_COMMIT_COLUMNS {
+        let last = columns.len() - 1;
+        columns = (0..MAX_COMMIT_COLUMNS).map(|i| columns[i * last / (MAX_COMMIT_COLUMNS - 1)].clone()).collect();
+    }
+
+    Ok(heatmap(&format!("Keyword usage since {}", since), columns))
+}
+
+fn total(files: &BTreeMap<String, Counts>) -> Counts {
+    let mut totals = Counts::new();
+    for (key, count) in files.values().flatten() {
+        *totals.entry(*key).or_default() += count;
+    }
+    totals
+}
+
+fn heatmap(title: &str, columns: Vec<(String, Counts)>) -> Heatmap {
+    let mut rows: BTreeMap<(bool, &'static str), Vec<usize>> = BTreeMap::new();
+    for (index, (_, counts)) in columns.iter().enumerate() {
+        for (key, count) in counts {
+            rows.entry(*key).or_insert_with(|| vec![0; columns.len()])[index] = *count;
+        }
+    }
+
+    Heatmap { title: title.to_string(), columns: columns.into_iter().map(|(name, _)| name).collect(), rows }
+}
+
+pub fn render_html(heatmap: &Heatmap) -> String {
+    let mut rows: Vec<(&(bool, &str), &Vec<usize>)> = heatmap.rows.iter().collect();
+    rows.sort_by_key(|((legacy, keyword), counts)| (*legacy, std::cmp::Reverse(counts.iter().sum::<usize>()), *keyword));
+
+    let mut html = format!(
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n<title>{title}</title>\n<style>\n\
+         body {{ font-family: system-ui, sans-serif; margin: 2rem; }}\n\
+         table {{ border-collapse: collapse; }}\n\
+         th, td {{ padding: 0.3rem 0.6rem; border: 1px solid #ddd; text-align: right; }}\n\
+         th {{ background: #f5f5f5; }}\n\
+         th.keyword {{ text-align: left; font-family: ui-monospace, monospace; }}\n\
+         </style>\n</head>\n<body>\n<h1>{title}</h1>\n\
+         <p>Green rows are NullScript keywords; red rows are the JavaScript spellings they replace.</p>\n\
+         <table>\n<thead>\n<tr><th class=\"keyword\">Keyword</th>",
+        title = escape(&heatmap.title)
+    );
+    for column in &heatmap.columns {
+        html.push_str(&format!("<th>{}</th>", escape(column)));
+    }
+    html.push_str("</tr>\n</thead>\n<tbody>\n");
+
+    for ((legacy, keyword), counts) in rows {
+        let max = counts.iter().copied().max().unwrap_or(0).max(1);
+        let color = if *legacy { "220, 38, 38" } else { "22, 163, 74" };
+        html.push_str(&format!("<tr><th class=\"keyword\">{}</th>", escape(keyword)));
+        for count in counts {
+            html.push_str(&format!(
+                "<td style=\"background: rgba({}, {:.2})\">{}</td>",
+                color,
+                *count as f64 / max as f64 * 0.8,
+                count
+            ));
+        }
+        html.push_str("</tr>\n");
+    }
+
+    html.push_str("</tbody>\n</table>\n</body>\n</html>\n");
+    html
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_keyword_counts_render_as_heatmap_rows() {
+        let counts = keyword_counts("run a() { speak.say(yes); }\nfunction b() { return true && console.count; }\nfixed s = \"run\";\n").unwrap();
+        assert_eq!(
+            counts.into_iter().collect::<Vec<_>>(),
+            vec![((false, "fixed"), 1), ((false, "run"), 1), ((false, "speak"), 1), ((false, "yes"), 1), ((true, "console"), 1), ((true, "function"), 1), ((true, "true"), 1)]
+        );
+
+        let map = heatmap(
+            "src <lib>",
+            vec![
+                ("a".to_string(), Counts::from([((false, "run"), 4), ((true, "function"), 1)])),
+                ("b".to_string(), Counts::from([((false, "run"), 2)])),
+            ],
+        );
+        assert_eq!(map.rows[&(true, "function")], vec![1, 0]);
+
+        let html = render_html(&map);
+        assert!(html.contains("<title>src &lt;lib&gt;</title>"));
+        assert!(html.contains("<tr><th class=\"keyword\">run</th><td style=\"background: rgba(22, 163, 74, 0.80)\">4</td><td style=\"background: rgba(22, 163, 74, 0.40)\">2</td></tr>"));
+        assert!(html.find(">run<") < html.find(">function<"));
+    }
+}
@@ -0,0 +1,431 @@
+use crate::compiler::NullScriptTranspiler;
+use crate::core::config::DiagnosticLevel;
+use crate::core::keywords::KEYWORDS;
+use crate::core::types::Location;
+use crate::core::NullScriptError;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::io::{BufRead, Write};
+use std::path::{Path, PathBuf};
+
+const ERROR: u32 = 1;
+const WARNING: u32 = 2;
+const KEYWORD_ITEM: u32 = 14;
+const SNIPPET_ITEM: u32 = 15;
+const SNIPPET_FORMAT: u32 = 2;
+
+static SNIPPETS: &[(&str, &str, &str)] = &[
+    ("test grab", "try { } catch { }", "test {\n\t$1\n} grab {\n\t$0\n}"),
+    ("test grab (error)", "try { } catch (error) { }", "test {\n\t$1\n} grab (${2:error}) {\n\t$0\n}"),
+    ("use everything as", "import * as name from", "use everything as ${1:name} from '${2:./module.js}';$0"),
+];
+const PARSE_ERROR: i64 = -32700;
+const METHOD_NOT_FOUND: i64 = -32601;
+const CACHE_CAPACITY: usize = 256;
+
+pub struct LanguageServer<F> {
+    setup: F,
+    documents: HashMap<String, String>,
+    cache: DiagnosticCache,
+}
+
+struct DiagnosticCache {
+    capacity: usize,
+    clock: u64,
+    entries: HashMap<String, (u64, Vec<Value>)>,
+}
+
+impl DiagnosticCache {
+    fn new(capacity: usize) -> Self {
+        Self { capacity, clock: 0, entries: HashMap::new() }
+    }
+
+    fn get(&mut self, key: &str) -> Option<Vec<Value>> {
+        self.clock += 1;
+        let (used, diagnostics) = self.entries.get_mut(key)?;
+        *used = self.clock;
+        Some(diagnostics.clone())
+    }
+
+    fn insert(&mut self, key: String, diagnostics: Vec<Value>) {
+        if self.entries.len() >= self.capacity && !self.entries.contains_key(&key) {
+            let oldest = self.entries.iter().min_by_key(|(_, (used, _))| *used).map(|(key, _)| key.clone());
+            if let Some(oldest) = oldest {
+                self.entries.remove(&oldest);
+            }
+        }
+
+        self.clock += 1;
+        self.entries.insert(key, (self.clock, diagnostics));
+    }
+}
+
+impl<F> LanguageServer<F>
+where
+    F: Fn(&Path) -> Result<NullScriptTranspiler, NullScriptError>,
+{
+    pub fn new(setup: F) -> Self {
+        Self { setup, documents: HashMap::new(), cache: DiagnosticCache::new(CACHE_CAPACITY) }
+    }
+
+    pub fn serve(mut self, mut input: impl BufRead, mut output: impl Write) -> Result<(), NullScriptError> {
+        while let Some(body) = read_body(&mut input)? {
+            let message: Value = match serde_json::from_slice(&body) {
+                Ok(message) => message,
+                Err(e) => {
+                    write_message(&mut output, &json!({
+                        "jsonrpc": "2.0",
+                        "id": Value::Null,
+                        "error": { "code": PARSE_ERROR, "message": format!("invalid JSON: {}", e) },
+                    }))?;
+                    continue;
+                }
+            };
+            if message["method"] == "exit" {
+                break;
+            }
+            for reply in self.handle(&message) {
+                write_message(&mut output, &reply)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn handle(&mut self, message: &Value) -> Vec<Value> {
+        let id = message.get("id").cloned();
+        let params = &message["params"];
+        let uri = params["textDocument"]["uri"].as_str().unwrap_or_default().to_string();
+
+        match message["method"].as_str() {
+            Some("initialize") => vec![response(id, json!({
+                "capabilities": {
+                    "textDocumentSync": { "openClose": true, "change": 1, "save": true },
+                    "completionProvider": {},
+                    "hoverProvider": true,
+                },
+                "serverInfo": { "name": "nsc", "version": env!("CARGO_PKG_VERSION") },
+            }))],
+            Some("shutdown") => vec![response(id, Value::Null)],
+            Some("textDocument/didOpen") => {
+                let text = params["textDocument"]["text"].as_str().unwrap_or_default();
+                self.documents.insert(uri.clone(), text.to_string());
+                vec![self.publish(&uri)]
+            }
+            Some("textDocument/didChange") => {
+                if let Some(text) = params["contentChanges"].as_array().and_then(|changes| changes.last()) {
+                    self.documents.insert(uri.clone(), text["text"].as_str().unwrap_or_default().to_string());
+                }
+                vec![self.publish(&uri)]
+            }
+            Some("textDocument/didSave") => vec![self.publish(&uri)],
+            Some("textDocument/didClose") => {
+                self.documents.remove(&uri);
+                vec![notification("textDocument/publishDiagnostics", json!({ "uri": uri, "diagnostics": [] }))]
+            }
+            Some("textDocument/completion") => vec![response(id, completions())],
+            Some("textDocument/hover") => {
+                let text = self.documents.get(&uri).map(String::as_str).unwrap_or_default();
+                vec![response(id, hover(text, &params["position"]))]
+            }
+            Some(method) if id.is_some() => vec![json!({
+                "jsonrpc": "2.0",
+                "id": id,
+                "error": { "code": METHOD_NOT_FOUND, "message": format!("unsupported method {}", method) },
+            })],
+            _ => Vec::new(),
+        }
+    }
+
+    fn publish(&mut self, uri: &str) -> Value {
+        let text = self.documents.get(uri).map(String::as_str).unwrap_or_default();
+        let path = uri_to_path(uri);
+        let diagnostics = match (self.setup)(&path) {
+            Ok(transpiler) => {
+                let key = transpiler.diagnostics_key(text, &path);
+                match self.cache.get(&key) {
+                    Some(cached) => cached,
+                    None => {
+                        let found = diagnostics(&transpiler, text, &path);
+                        self.cache.insert(key, found.clone());
+                        found
+                    }
+                }
+            }
+            Err(e) => vec![diagnostic(text, 1, None, None, ERROR, e.to_string())],
+        };
+
+        notification("textDocument/publishDiagnostics", json!({ "uri": uri, "diagnostics": diagnostics }))
+    }
+}
+
+fn diagnostics(transpiler: &NullScriptTranspiler, text: &str, path: &Path) -> Vec<Value> {
+    let found = match transpiler.diagnostics(text, path) {
+        Ok(found) => found,
+        Err(NullScriptError::SyntaxErrors { errors, .. }) => {
+            return errors
+                .into_iter()
+                .map(|e| diagnostic(text, e.location.line.unwrap_or(1), e.location.column, None, ERROR, e.message))
+                .collect();
+        }
+        Err(e) => {
+            let (message, location) = match e {
+                NullScriptError::Syntax(e) => (e.message, e.location),
+                NullScriptError::Transpile(e) => (e.message, e.location),
+                NullScriptError::Type(e) => (e.message, e.location),
+                e => (e.to_string(), Location::new(None, None, None)),
+            };
+            return vec![diagnostic(text, location.line.unwrap_or(1), location.column, None, ERROR, message)];
+        }
+    };
+
+    found
+        .into_iter()
+        .map(|found| {
+            let severity = if found.level == DiagnosticLevel::Error { ERROR } else { WARNING };
+            let length = found.column.map(|column| word_length(text, found.line, column));
+            diagnostic(text, found.line, found.column, length, severity, found.message)
+        })
+        .collect()
+}
+
+fn diagnostic(text: &str, line: u32, column: Option<u32>, length: Option<usize>, severity: u32, message: String) -> Value {
+    let line_text = text.split('\n').nth(line.saturating_sub(1) as usize).unwrap_or_default().trim_end_matches('\r');
+    let start = match column {
+        Some(column) => column.saturating_sub(1) as usize,
+        None => line_text.chars().take_while(|c| c.is_whitespace()).count(),
+    };
+    let end = length.map_or(line_text.chars().count(), |length| start + length);
+
+    json!({
+        "range": {
+            "start": { "line": line.saturating_sub(1), "character": utf16_column(line_text, start) },
+            "end": { "line": line.saturating_sub(1), "character": utf16_column(line_text, end) },
+        },
+        "severity": severity,
+        "source": "nsc",
+        "message": message,
+    })
+}
+
+fn word_length(text: &str, line: u32, column: u32) -> usize {
+    let line_text = text.split('\n').nth(line.saturating_sub(1) as usize).unwrap_or_default();
+    line_text.chars().skip(column.saturating_sub(1) as usize).take_while(|c| is_word(*c)).count()
+}
+
+fn is_word(c: char) -> bool {
+    c.is_alphanumeric() || c == '_' || c == '$'
+}
+
+fn completions() -> Value {
+    let mut seen = Vec::new();
+    let items: Vec<Value> = KEYWORDS
+        .iter()
+        .filter(|(keyword, _)| {
+            let first = !seen.contains(keyword);
+            seen.push(*keyword);
+            first
+        })
+        .map(|(keyword, js)| json!({ "label": keyword, "kind": KEYWORD_ITEM, "detail": js }))
+        .chain(SNIPPETS.iter().map(|(label, detail, body)| {
+            json!({ "label": label, "kind": SNIPPET_ITEM, "detail": detail, "insertText": body, "insertTextFormat": SNIPPET_FORMAT })
+        }))
+        .collect();
+
+    Value::Array(items)
+}
+
+fn hover(text: &str, position: &Value) -> Value {
+    let line = position["line"].as_u64().unwrap_or(0) as usize;
+    let character = position["character"].as_u64().unwrap_or(0) as usize;
+    let Some(line_text) = text.split('\n').nth(line) else {
+        return Value::Null;
+    };
+
+    let mut units = 0;
+    let offset = line_text
+        .char_indices()
+        .find(|(_, c)| {
+            units += c.len_utf16();
+            units > character
+        })
+        .map_or(line_text.len(), |(offset, _)| offset);
+
+    let start = line_text[..offset]
+        .char_indices()
+        .rev()
+        .take_while(|(_, c)| is_word(*c))
+        .last()
+        .map_or(offset, |(i, _)| i);
+    let end = line_text[offset..].find(|c| !is_word(c)).map_or(line_text.len(), |i| offset + i);
+
+    match KEYWORDS.iter().find(|(keyword, _)| *keyword == &line_text[start..end]) {
+        Some((keyword, js)) => json!({
+            "contents": { "kind": "markdown", "value": format!("**{}** → `{}`", keyword, js) },
+        }),
+        None => Value::Null,
+    }
+}
+
+fn utf16_column(line: &str, chars: usize) -> usize {
+    line.chars().take(chars).map(char::len_utf16).sum()
+}
+
+fn uri_to_path(uri: &str) -> PathBuf {
+    let path = uri.strip_prefix("file://").unwrap_or(uri);
+    let mut bytes = Vec::with_capacity(path.len());
+    let mut rest = path.as_bytes();
+
+    while let Some((&byte, tail)) = rest.split_first() {
+        let decoded = (byte == b'%')
+            .then(|| tail.get(..2))
+            .flatten()
+            .and_then(|hex| u8::from_str_radix(std::str::from_utf8(hex).ok()?, 16).ok());
+        match decoded {
+            Some(decoded) => {
+                bytes.push(decoded);
+                rest = &tail[2..];
+            }
+            None => {
+                bytes.push(byte);
+                rest = tail;
+            }
+        }
+    }
+
+    let path = String::from_utf8_lossy(&bytes).into_owned();
+    let drive = path.as_bytes().get(..3).is_some_and(|head| head[0] == b'/' && head[1].is_ascii_alphabetic() && head[2] == b':');
+    match path.strip_prefix('/') {
+        Some(windows) if drive => PathBuf::from(windows),
+        None if uri.starts_with("file://") && !path.is_empty() => PathBuf::from(format!("//{}", path)),
+        _ => PathBuf::from(path),
+    }
+}
+
+fn response(id: Option<Value>, result: Value) -> Value {
+    json!({ "jsonrpc": "2.0", "id": id, "result": result })
+}
+
+fn notification(method: &str, params: Value) -> Value {
+    json!({ "jsonrpc": "2.0", "method": method, "params": params })
+}
+
+fn read_body(input: &mut impl BufRead) -> Result<Option<Vec<u8>>, NullScriptError> {
+    let mut length = None;
+    loop {
+        let mut header = String::new();
+        if input.read_line(&mut header)? == 0 {
+            return Ok(None);
+        }
+
+        let header = header.trim_end();
+        if header.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = header.split_once(':') {
+            if name.eq_ignore_ascii_case("content-length") {
+                length = value.trim().parse::<usize>().ok();
+            }
+        }
+    }
+
+    let length = length.ok_or_else(|| {
+        NullScriptError::Io(std::io::Error::new(std::io::ErrorKind::InvalidData, "message without Content-Length"))
+    })?;
+    let mut body = vec![0; length];
+    input.read_exact(&mut body)?;
+
+    Ok(Some(body))
+}
+
+fn write_message(output: &mut impl Write, message: &Value) -> Result<(), NullScriptError> {
+    let body = serde_json::to_string(message)?;
+    write!(output, "Content-Length: {}\r\n\r\n{}", body.len(), body)?;
+    output.flush()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn read_message(input: &mut impl BufRead) -> Option<Value> {
+        read_body(input).unwrap().map(|body| serde_json::from_slice(&body).unwrap())
+    }
+
+    #[test]
+    fn test_server_publishes_diagnostics_and_answers_requests() {
+        let setup = |_: &Path| Ok(NullScriptTranspiler::new().with_keyword_typos(DiagnosticLevel::Warn));
+        let open = json!({ "method": "textDocument/didOpen", "params": { "textDocument": {
+            "uri": "file:///work/my%20app/main.ns",
+            "text": "fixed a = 1;\nretrun a;\n",
+        } } });
+        let hover_request = json!({ "id": 2, "method": "textDocument/hover", "params": {
+            "textDocument": { "uri": "file:///work/my%20app/main.ns" },
+            "position": { "line": 0, "character": 3 },
+        } });
+
+        let mut messages = vec![json!({ "id": 1, "method": "initialize", "params": {} }), open, hover_request];
+        messages.push(json!({ "id": 3, "method": "textDocument/definition", "params": {} }));
+        let input: String = messages
+            .iter()
+            .map(|message| format!("Content-Length: {}\r\n\r\n{}", message.to_string().len(), message))
+            .collect();
+
+        let mut output = Vec::new();
+        LanguageServer::new(setup).serve(input.as_bytes(), &mut output).unwrap();
+
+        let mut replies = Vec::new();
+        let mut reader = output.as_slice();
+        while let Some(reply) = read_message(&mut reader) {
+            replies.push(reply);
+        }
+
+        assert_eq!(replies[0]["result"]["capabilities"]["hoverProvider"], true);
+        let diagnostics = &replies[1]["params"]["diagnostics"];
+        assert_eq!(diagnostics.as_array().unwrap().len(), 1);
+        assert_eq!(diagnostics[0]["range"]["start"], json!({ "line": 1, "character": 0 }));
+        assert_eq!(diagnostics[0]["range"]["end"], json!({ "line": 1, "character": 6 }));
+        assert_eq!(replies[2]["result"]["contents"]["value"], "**fixed** → `const`");
+        assert_eq!(replies[3]["error"]["code"], METHOD_NOT_FOUND);
+        assert_eq!(uri_to_path("file:///work/my%20app/main.ns"), PathBuf::from("/work/my app/main.ns"));
+        assert_eq!(uri_to_path("file:///C:/work/main.ns"), PathBuf::from("C:/work/main.ns"));
+        assert_eq!(uri_to_path("file:///c%3A/my%20app/main.ns"), PathBuf::from("c:/my app/main.ns"));
+        assert_eq!(uri_to_path("file://server/share/main.ns"), PathBuf::from("//server/share/main.ns"));
+        let snippet = completions().as_array().unwrap().iter().find(|item| item["label"] == "test grab").cloned().unwrap();
+        assert_eq!((snippet["kind"].as_u64(), snippet["insertText"].as_str()), (Some(15), Some("test {\n\t$1\n} grab {\n\t$0\n}")));
+    }
+
+    #[test]
+    fn test_invalid_json_gets_a_parse_error_and_the_server_keeps_going() {
+        let setup = |_: &Path| Ok(NullScriptTranspiler::new());
+        let broken = "{ 1: 2 }";
+        let shutdown = json!({ "id": 7, "method": "shutdown" }).to_string();
+        let input = format!("Content-Length: {}\r\n\r\n{}Content-Length: {}\r\n\r\n{}", broken.len(), broken, shutdown.len(), shutdown);
+
+        let mut output = Vec::new();
+        LanguageServer::new(setup).serve(input.as_bytes(), &mut output).unwrap();
+
+        let mut reader = output.as_slice();
+        let error = read_message(&mut reader).unwrap();
+        assert_eq!((error["id"].clone(), error["error"]["code"].as_i64()), (Value::Null, Some(PARSE_ERROR)));
+        assert_eq!(read_message(&mut reader).unwrap()["id"], 7);
+    }
+
+    #[test]
+    fn test_diagnostic_cache_evicts_least_recently_used() {
+        let mut cache = DiagnosticCache::new(2);
+        cache.insert("a".to_string(), vec![json!(1)]);
+        cache.insert("b".to_string(), vec![json!(2)]);
+        assert_eq!(cache.get("a"), Some(vec![json!(1)]));
+
+        cache.insert("c".to_string(), vec![json!(3)]);
+        assert_eq!(cache.get("b"), None);
+        assert!(cache.get("a").is_some() && cache.get("c").is_some());
+
+        let transpiler = NullScriptTranspiler::new();
+        let key = transpiler.diagnostics_key("fixed a = 1;", Path::new("a.ns"));
+        assert_eq!(key, transpiler.diagnostics_key("fixed a = 1;", Path::new("a.ns")));
+        assert_ne!(key, transpiler.clone().with_keyword_typos(DiagnosticLevel::Error).diagnostics_key("fixed a = 1;", Path::new("a.ns")));
+    }
+}
@@ -1,4 +1,12 @@
+pub mod blame;
 pub mod commands;
+pub mod editor;
+pub mod examples;
 pub mod handler;
+pub mod heatmap;
+pub mod lsp;
+pub mod output;
+pub mod prompt;
+pub mod repl;
 
 pub use commands::*;
@@ -0,0 +1,243 @@
+use colored::Colorize;
+use std::cell::RefCell;
+use std::io::{IsTerminal, Write};
+use std::process::{Command, Stdio};
+use std::sync::atomic::{AtomicBool, AtomicU8, Ordering};
+
+static THEME: AtomicU8 = AtomicU8::new(Theme::Unicode as u8);
+
+static TO_STDERR: AtomicBool = AtomicBool::new(false);
+
+thread_local! {
+    static CAPTURED: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+const DEFAULT_PAGER: &str = "less -FRX";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Theme {
+    Unicode,
+    Ascii,
+    Plain,
+}
+
+impl Theme {
+    pub const NAMES: &'static [&'static str] = &["unicode", "ascii", "plain"];
+
+    pub fn parse(value: &str) -> Result<Self, String> {
+        match value.to_ascii_lowercase().as_str() {
+            "unicode" => Ok(Theme::Unicode),
+            "ascii" => Ok(Theme::Ascii),
+            "plain" => Ok(Theme::Plain),
+            other => Err(format!("unknown theme '{}', expected one of: {}", other, Self::NAMES.join(", "))),
+        }
+    }
+}
+
+pub fn set_theme(theme: Theme) {
+    THEME.store(theme as u8, Ordering::Relaxed);
+    if theme == Theme::Plain {
+        colored::control::set_override(false);
+    }
+}
+
+pub fn theme() -> Theme {
+    match THEME.load(Ordering::Relaxed) {
+        0 => Theme::Unicode,
+        1 => Theme::Ascii,
+        _ => Theme::Plain,
+    }
+}
+
+pub fn set_plain(plain: bool) {
+    set_theme(if plain { Theme::Plain } else { Theme::Unicode });
+}
+
+pub fn is_plain() -> bool {
+    theme() == Theme::Plain
+}
+
+pub fn plain_requested(flag: bool) -> bool {
+    flag
+        || std::env::var("NSC_PLAIN").is_ok_and(|v| !v.is_empty() && v != "0")
+        || std::env::var("TERM").is_ok_and(|term| term == "dumb")
+}
+
+pub fn theme_requested(theme: Option<Theme>, plain: bool) -> Theme {
+    if plain_requested(plain) {
+        return Theme::Plain;
+    }
+    if let Some(theme) = theme.or_else(|| std::env::var("NSC_THEME").ok().and_then(|name| Theme::parse(&name).ok())) {
+        return theme;
+    }
+
+    let locale = ["LC_ALL", "LC_CTYPE", "LANG"]
+        .iter()
+        .filter_map(|name| std::env::var(name).ok())
+        .find(|value| !value.is_empty());
+    match locale {
+        Some(locale) if !locale.to_ascii_lowercase().replace('-', "").contains("utf8") => Theme::Ascii,
+        _ => Theme::Unicode,
+    }
+}
+
+pub fn set_to_stderr(to_stderr: bool) {
+    TO_STDERR.store(to_stderr, Ordering::Relaxed);
+}
+
+fn emit(text: impl std::fmt::Display) {
+    let captured = CAPTURED.with_borrow_mut(|buffer| match buffer {
+        Some(buffer) => {
+            buffer.push_str(&format!("{}\n", text));
+            true
+        }
+        None => false,
+    });
+    if captured {
+        return;
+    }
+
+    if TO_STDERR.load(Ordering::Relaxed) {
+        eprintln!("{}", text);
+    } else {
+        println!("{}", text);
+    }
+}
+
+pub fn paged(enabled: bool, write: impl FnOnce()) {
+    if !enabled || TO_STDERR.load(Ordering::Relaxed) || !std::io::stdout().is_terminal() {
+        write();
+        return;
+    }
+
+    CAPTURED.set(Some(String::new()));
+    write();
+    let text = CAPTURED.take().unwrap_or_default();
+    if !page(&text) {
+        print!("{}", text);
+    }
+}
+
+fn page(text: &str) -> bool {
+    let pager = std::env::var("PAGER").ok().filter(|pager| !pager.trim().is_empty());
+    let pager = pager.as_deref().unwrap_or(DEFAULT_PAGER);
+    let mut parts = pager.split_whitespace();
+    let Some(program) = parts.next() else {
+        return false;
+    };
+
+    let Ok(mut child) = Command::new(program).args(parts).stdin(Stdio::piped()).spawn() else {
+        return false;
+    };
+    if let Some(mut stdin) = child.stdin.take() {
+        let _ = stdin.write_all(text.as_bytes());
+    }
+    child.wait().is_ok()
+}
+
+pub fn terminal_width() -> Option<usize> {
+    if !std::io::stdout().is_terminal() {
+        return None;
+    }
+    std::env::var("COLUMNS").ok().and_then(|columns| columns.trim().parse().ok()).or(Some(80))
+}
+
+pub fn render(text: &str) -> String {
+    render_with(theme(), text)
+}
+
+fn render_with(theme: Theme, text: &str) -> String {
+    if theme == Theme::Unicode {
+        return text.to_string();
+    }
+
+    let mut output = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '✅' | '✔' => output.push_str("[ok]"),
+            '❌' | '✖' => output.push_str("[error]"),
+            '⚠' => output.push_str("[warning]"),
+            '→' | '➜' => output.push_str("->"),
+            '←' => output.push_str("<-"),
+            '•' => output.push('*'),
+            '×' => output.push('x'),
+            '…' => output.push_str("..."),
+            '\u{2500}'..='\u{257F}' => output.push('-'),
+            c if is_decorative(c) => {
+                let at_word_start = output.is_empty() || output.ends_with(char::is_whitespace);
+                if at_word_start && chars.peek() == Some(&' ') {
+                    chars.next();
+                }
+            }
+            c => output.push(c),
+        }
+    }
+
+    output
+}
+
+fn is_decorative(c: char) -> bool {
+    matches!(
+        c as u32,
+        0x1F000..=0x1FAFF | 0x23E9..=0x23FA | 0x2600..=0x27BF | 0x2B00..=0x2BFF | 0xFE00..=0xFE0F | 0x200D
+    )
+}
+
+pub fn line(text: impl std::fmt::Display) {
+    emit(render(&text.to_string()));
+}
+
+pub fn eline(text: impl std::fmt::Display) {
+    eprintln!("{}", render(&text.to_string()));
+}
+
+pub fn blank() {
+    emit("");
+}
+
+pub fn heading(title: &str) {
+    heading_with_width(title, 30);
+}
+
+pub fn heading_with_width(title: &str, width: usize) {
+    line(title.cyan());
+    if !is_plain() {
+        emit("=".repeat(width).bright_black());
+    }
+}
+
+pub fn section(title: &str) {
+    line(title.cyan());
+    rule(40);
+}
+
+pub fn rule(width: usize) {
+    match theme() {
+        Theme::Unicode => emit("─".repeat(width).bright_black()),
+        Theme::Ascii => emit("-".repeat(width).bright_black()),
+        Theme::Plain => {}
+    }
+}
+
+pub fn rule_with(symbol: &str, width: usize) {
+    if !is_plain() {
+        emit(render(&symbol.repeat(width)));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ascii_themes_replace_symbols() {
+        let text = "⏱️ Timing → 3 × run • ✅ done ─ ⚠️  careful…";
+
+        assert_eq!(render_with(Theme::Unicode, text), text);
+        assert_eq!(render_with(Theme::Ascii, text), "Timing -> 3 x run * [ok] done - [warning]  careful...");
+        assert!(render_with(Theme::Plain, text).is_ascii());
+        assert_eq!(Theme::parse("ASCII"), Ok(Theme::Ascii));
+    }
+}
@@ -0,0 +1,157 @@
+use crate::core::NullScriptError;
+use crate::core::i18n::{tr, trf};
+use crate::cli::output;
+use colored::Colorize;
+use std::io::{BufRead, IsTerminal, Write};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static ASSUME_YES: AtomicBool = AtomicBool::new(false);
+
+pub fn set_assume_yes(yes: bool) {
+    ASSUME_YES.store(yes, Ordering::Relaxed);
+}
+
+pub fn assume_yes() -> bool {
+    ASSUME_YES.load(Ordering::Relaxed)
+}
+
+pub fn is_interactive() -> bool {
+    !assume_yes() && std::io::stdin().is_terminal() && std::io::stderr().is_terminal() && std::env::var_os("CI").is_none()
+}
+
+pub async fn confirm(question: &str, default: bool) -> Result<bool, NullScriptError> {
+    if assume_yes() {
+        return Ok(true);
+    }
+    if !is_interactive() {
+        return Ok(default);
+    }
+
+    let hint = if default { tr("prompt.confirm_default_yes") } else { tr("prompt.confirm_default_no") };
+    loop {
+        ask(&format!("{} {} ", question, hint))?;
+        let Some(answer) = read_line().await? else {
+            return Ok(false);
+        };
+
+        match parse_confirm(&answer, default) {
+            Some(answer) => return Ok(answer),
+            None => output::eline(tr("prompt.confirm_invalid").yellow()),
+        }
+    }
+}
+
+pub async fn select(question: &str, choices: &[String], default: Option<usize>) -> Result<Option<usize>, NullScriptError> {
+    if choices.is_empty() {
+        return Ok(None);
+    }
+    if !is_interactive() {
+        return Ok(default.filter(|index| *index < choices.len()));
+    }
+
+    loop {
+        output::eline("");
+        output::eline(question.cyan());
+        for (index, choice) in choices.iter().enumerate() {
+            output::eline(format!("   [{}] {}", index + 1, choice));
+        }
+        ask(tr("prompt.select"))?;
+
+        let Some(answer) = read_line().await? else {
+            return Ok(None);
+        };
+        let answer = answer.trim();
+        if answer.is_empty() {
+            return Ok(default);
+        }
+
+        match answer.parse::<usize>().ok().filter(|choice| (1..=choices.len()).contains(choice)) {
+            Some(choice) => return Ok(Some(choice - 1)),
+            None => output::eline(trf("prompt.select_invalid", &[("count", &choices.len())]).yellow()),
+        }
+    }
+}
+
+fn parse_confirm(answer: &str, default: bool) -> Option<bool> {
+    match answer.trim().to_lowercase().as_str() {
+        "" => Some(default),
+        "y" | "yes" | "s" | "si" | "sí" => Some(true),
+        "n" | "no" => Some(false),
+        _ => None,
+    }
+}
+
+pub fn ask(text: &str) -> Result<(), NullScriptError> {
+    eprint!("{}", output::render(text));
+    std::io::stderr().flush()?;
+    Ok(())
+}
+
+pub async fn read_line() -> Result<Option<String>, NullScriptError> {
+    let _interrupt = InterruptGuard::install();
+    let read = tokio::task::spawn_blocking(|| {
+        let mut line = String::new();
+        match std::io::stdin().lock().read_line(&mut line) {
+            Ok(0) => Ok(None),
+            Ok(_) => Ok(Some(line)),
+            Err(error) if error.kind() == std::io::ErrorKind::Interrupted => Ok(None),
+            Err(error) => Err(error),
+        }
+    });
+
+    let line = read.await.map_err(std::io::Error::other)??;
+    if line.is_none() {
+        output::eline("");
+    }
+    Ok(line)
+}
+
+struct InterruptGuard {
+    #[cfg(unix)]
+    previous: libc::sighandler_t,
+}
+
+impl InterruptGuard {
+    #[cfg(unix)]
+    fn install() -> Self {
+        extern "C" fn restore_and_exit(_: libc::c_int) {
+            const RESET: &[u8] = b"\x1b[0m\n";
+            unsafe {
+                libc::write(libc::STDERR_FILENO, RESET.as_ptr().cast(), RESET.len());
+                libc::_exit(130);
+            }
+        }
+
+        let handler = restore_and_exit as extern "C" fn(libc::c_int);
+        let previous = unsafe { libc::signal(libc::SIGINT, handler as libc::sighandler_t) };
+        Self { previous }
+    }
+
+    #[cfg(not(unix))]
+    fn install() -> Self {
+        Self {}
+    }
+}
+
+impl Drop for InterruptGuard {
+    fn drop(&mut self) {
+        #[cfg(unix)]
+        unsafe {
+            libc::signal(libc::SIGINT, self.previous);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_confirm_accepts_english_and_spanish() {
+        assert_eq!(parse_confirm("\n", true), Some(true));
+        assert_eq!(parse_confirm(" Yes\n", false), Some(true));
+        assert_eq!(parse_confirm("sí", false), Some(true));
+        assert_eq!(parse_confirm("N", true), Some(false));
+        assert_eq!(parse_confirm("maybe", true), None);
+    }
+}
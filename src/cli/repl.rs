@@ -0,0 +1,164 @@
+use crate::compiler::lexer::{Lexer, TokenKind};
+use crate::compiler::NullScriptTranspiler;
+use crate::core::i18n::tr;
+use crate::core::NullScriptError;
+use std::io::{BufRead, BufReader, Write};
+use std::process::{Child, ChildStdin, ChildStdout, Command, Stdio};
+
+const RESULT: char = '\u{2}';
+const THROWN: char = '\u{1}';
+const DONE: &str = "\u{0}";
+
+const NODE_DRIVER: &str = r#"
+const vm = require("vm");
+const util = require("util");
+const readline = require("readline");
+globalThis.require = require;
+const describe = (error) => (error instanceof Error ? String(error) : util.inspect(error));
+process.on("unhandledRejection", (error) => console.error("Uncaught (in promise) " + describe(error)));
+process.on("uncaughtException", (error) => console.error("Uncaught " + describe(error)));
+const reply = (marker, text) => process.stdout.write(marker + JSON.stringify(text) + "\n");
+let queue = Promise.resolve();
+readline.createInterface({ input: process.stdin }).on("line", (line) => {
+  queue = queue.then(async () => {
+    try {
+      let value = vm.runInThisContext(JSON.parse(line), { filename: "repl" });
+      if (value && typeof value.then === "function") value = await value;
+      if (value !== undefined) reply("\u0002", util.inspect(value));
+    } catch (error) {
+      reply("\u0001", "Uncaught " + describe(error));
+    }
+    process.stdout.write("\u0000\n");
+  });
+});
+"#;
+
+pub trait ReplSession {
+    fn eval(&mut self, code: &str) -> Result<Option<String>, NullScriptError>;
+}
+
+pub struct NodeSession {
+    child: Child,
+    stdin: ChildStdin,
+    stdout: BufReader<ChildStdout>,
+}
+
+impl NodeSession {
+    pub fn start() -> Result<Self, NullScriptError> {
+        let mut child = Command::new("node")
+            .args(["-e", NODE_DRIVER])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::inherit())
+            .spawn()?;
+
+        let (Some(stdin), Some(stdout)) = (child.stdin.take(), child.stdout.take()) else {
+            let _ = child.kill();
+            return Err(NullScriptError::Io(std::io::Error::other(tr("repl.node_exited"))));
+        };
+        Ok(Self { child, stdin, stdout: BufReader::new(stdout) })
+    }
+}
+
+impl ReplSession for NodeSession {
+    fn eval(&mut self, code: &str) -> Result<Option<String>, NullScriptError> {
+        writeln!(self.stdin, "{}", serde_json::to_string(code)?)?;
+        self.stdin.flush()?;
+
+        let mut result = Ok(None);
+        loop {
+            let mut line = String::new();
+            if self.stdout.read_line(&mut line)? == 0 {
+                return Err(NullScriptError::Io(std::io::Error::other(tr("repl.node_exited"))));
+            }
+            let line = line.trim_end_matches(['\r', '\n']);
+            if line == DONE {
+                return result;
+            }
+            if let Some(value) = line.strip_prefix(RESULT) {
+                result = Ok(Some(serde_json::from_str(value)?));
+            } else if let Some(error) = line.strip_prefix(THROWN) {
+                result = Err(NullScriptError::Io(std::io::Error::other(serde_json::from_str::<String>(error)?)));
+            } else {
+                println!("{}", line);
+            }
+        }
+    }
+}
+
+impl Drop for NodeSession {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+#[cfg(feature = "engine")]
+impl ReplSession for crate::utils::engine::EngineSession {
+    fn eval(&mut self, code: &str) -> Result<Option<String>, NullScriptError> {
+        crate::utils::engine::EngineSession::eval(self, code, "repl")
+    }
+}
+
+pub struct Repl {
+    transpiler: NullScriptTranspiler,
+    session: Box<dyn ReplSession>,
+}
+
+impl Repl {
+    pub fn new(transpiler: NullScriptTranspiler, session: Box<dyn ReplSession>) -> Self {
+        Self { transpiler, session }
+    }
+
+    pub fn eval(&mut self, source: &str) -> Result<Option<String>, NullScriptError> {
+        self.transpiler.validate_syntax(source, None)?;
+        let code = self.transpiler.transpile(source)?;
+        self.session.eval(&code)
+    }
+}
+
+pub fn needs_more(source: &str) -> bool {
+    let Ok(tokens) = Lexer::new(source).with_max_depth(usize::MAX).tokenize() else {
+        return false;
+    };
+
+    let mut depth = 0i32;
+    for token in &tokens {
+        match (token.kind, token.text) {
+            (TokenKind::Punct, "(" | "[" | "{") => depth += 1,
+            (TokenKind::Punct, ")" | "]" | "}") => depth -= 1,
+            (TokenKind::Template, text) => {
+                if text.starts_with('}') {
+                    depth -= 1;
+                }
+                if text.ends_with("${") {
+                    depth += 1;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let open_template = tokens
+        .iter()
+        .rev()
+        .find(|token| !token.is_trivia())
+        .is_some_and(|token| token.kind == TokenKind::Template && !token.text.ends_with('`') && !token.text.ends_with("${"));
+    depth > 0 || open_template
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_needs_more_waits_for_open_blocks() {
+        assert!(!needs_more("fixed answer = 42;\n"));
+        assert!(needs_more("model Box {\n"));
+        assert!(needs_more("run area(w, h) {\n\treturn w * h;\n"));
+        assert!(!needs_more("run area(w, h) {\n\treturn w * h;\n}\n"));
+        assert!(needs_more("speak.say(`total: ${\n"));
+        assert!(needs_more("fixed text = `first line\n"));
+        assert!(!needs_more("fixed text = `a ${1} b`;\n"));
+    }
+}
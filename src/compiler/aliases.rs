@@ -0,0 +1,155 @@
+use crate::compiler::lexer::{Lexer, Token, TokenKind};
+use crate::core::NullScriptError;
+use crate::core::config::LoadedConfig;
+use crate::utils::paths::PathUtils;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PathAliases {
+    entries: Vec<(String, Vec<PathBuf>)>,
+}
+
+impl PathAliases {
+    pub fn new(base: &Path, paths: &BTreeMap<String, Vec<String>>) -> Self {
+        let mut entries: Vec<(String, Vec<PathBuf>)> = paths
+            .iter()
+            .map(|(pattern, targets)| {
+                let targets = targets.iter().map(|target| PathUtils::normalize(&base.join(target))).collect();
+                (pattern.clone(), targets)
+            })
+            .collect();
+        entries.sort_by_key(|(pattern, _)| std::cmp::Reverse(pattern.split('*').next().map_or(0, str::len)));
+
+        Self { entries }
+    }
+
+    pub fn from_config(config: &LoadedConfig) -> Self {
+        Self::new(&config.origin_dir("paths"), &config.config.paths)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn targets(&self, specifier: &str) -> Vec<PathBuf> {
+        for (pattern, targets) in &self.entries {
+            let Some(captured) = capture(pattern, specifier) else {
+                continue;
+            };
+
+            return targets
+                .iter()
+                .map(|target| PathBuf::from(target.to_string_lossy().replacen('*', captured, 1)))
+                .collect();
+        }
+
+        Vec::new()
+    }
+
+    pub fn rewrite(&self, source: &str, from_file: &Path) -> Result<String, NullScriptError> {
+        let tokens: Vec<Token> = Lexer::new(source)
+            .with_max_depth(usize::MAX)
+            .tokenize()?
+            .into_iter()
+            .filter(|token| !token.is_trivia())
+            .collect();
+        let from_dir = PathUtils::absolute(from_file.parent().unwrap_or(Path::new(".")));
+        let mut rewritten = String::with_capacity(source.len());
+        let mut copied = 0;
+
+        for (index, token) in tokens.iter().enumerate() {
+            if token.kind != TokenKind::String || !is_specifier(&tokens, index) || token.text.len() < 2 {
+                continue;
+            }
+
+            let quote = &token.text[..1];
+            let targets = self.targets(&token.text[1..token.text.len() - 1]);
+            let Some(target) = targets.iter().find_map(|target| resolve(target)).or_else(|| targets.first().map(|target| module_file(target))) else {
+                continue;
+            };
+
+            let relative = PathUtils::to_slash(&PathUtils::relative_path(&target, &from_dir));
+            let relative = if relative.starts_with("../") { relative } else { format!("./{}", relative) };
+
+            rewritten.push_str(&source[copied..token.start]);
+            rewritten.push_str(&format!("{}{}{}", quote, relative, quote));
+            copied = token.start + token.text.len();
+        }
+
+        rewritten.push_str(&source[copied..]);
+        Ok(rewritten)
+    }
+}
+
+fn capture<'a>(pattern: &str, specifier: &'a str) -> Option<&'a str> {
+    match pattern.split_once('*') {
+        Some((prefix, suffix)) => specifier
+            .strip_prefix(prefix)
+            .and_then(|rest| rest.strip_suffix(suffix))
+            .filter(|_| specifier.len() >= prefix.len() + suffix.len()),
+        None => (pattern == specifier).then_some(""),
+    }
+}
+
+fn is_specifier(tokens: &[Token], index: usize) -> bool {
+    let text = |offset: usize| index.checked_sub(offset).map_or("", |i| tokens[i].text);
+
+    matches!(text(1), "from" | "use" | "import") || (text(1) == "(" && matches!(text(2), "use" | "import"))
+}
+
+fn resolve(target: &Path) -> Option<PathBuf> {
+    if target.is_file() {
+        return Some(target.to_path_buf());
+    }
+
+    let index = target.join("index.ns");
+    [module_file(target), index].into_iter().find(|candidate| candidate.is_file())
+}
+
+fn module_file(target: &Path) -> PathBuf {
+    if target.extension().is_some() {
+        return target.to_path_buf();
+    }
+
+    let mut with_ns = target.as_os_str().to_owned();
+    with_ns.push(".ns");
+    PathBuf::from(with_ns)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn test_aliases_rewrite_to_relative_specifiers() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir_all(dir.path().join("src/app")).unwrap();
+        fs::create_dir_all(dir.path().join("src/pages")).unwrap();
+        fs::write(dir.path().join("src/app/util.ns"), "share fixed x = 1;\n").unwrap();
+
+        let paths = BTreeMap::from([
+            ("@app/*".to_string(), vec!["src/missing/*".to_string(), "src/app/*".to_string()]),
+            ("@/*".to_string(), vec!["src/*".to_string()]),
+            ("config".to_string(), vec!["src/config.ns".to_string()]),
+        ]);
+        let aliases = PathAliases::new(dir.path(), &paths);
+        assert_eq!(aliases.targets("@app/util"), vec![dir.path().join("src/missing/util"), dir.path().join("src/app/util")]);
+        assert_eq!(aliases.targets("@/pages/home.ns"), vec![dir.path().join("src/pages/home.ns")]);
+        assert!(aliases.targets("lodash").is_empty());
+
+        let source = "use { x } from \"@app/util\";\nuse cfg from 'config';\nfixed s = \"@app/util\";\nfixed lazy = hold use(\"@/pages/home.ns\");\n";
+        let rewritten = aliases.rewrite(source, &dir.path().join("src/pages/home.ns")).unwrap();
+        assert_eq!(
+            rewritten,
+            "use { x } from \"../app/util.ns\";\nuse cfg from '../config.ns';\nfixed s = \"@app/util\";\nfixed lazy = hold use(\"./home.ns\");\n"
+        );
+
+        fs::create_dir_all(dir.path().join("src/widgets")).unwrap();
+        fs::write(dir.path().join("src/widgets/index.ns"), "share fixed y = 2;\n").unwrap();
+        let source = aliases.rewrite("use { x } from \"@app/util\";\nuse { y } from \"@/widgets\";\n", &dir.path().join("src/pages/home.ns")).unwrap();
+        let transpiled = crate::compiler::NullScriptTranspiler::new().transpile(&source).unwrap();
+        assert_eq!(transpiled, "import { x } from \"../app/util.js\";\nimport { y } from \"../widgets/index.js\";\n");
+    }
+}
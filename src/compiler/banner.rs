@@ -0,0 +1,146 @@
+use crate::core::NullScriptError;
+use crate::core::config::{LoadedConfig, CONFIG_FILE};
+use serde_json::Value;
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Banner {
+    template: String,
+    year: i64,
+    version: String,
+}
+
+impl Banner {
+    pub fn new(template: &str, year: i64, version: &str) -> Self {
+        let template = template.trim_end();
+        let template = if template.starts_with("/*") || template.starts_with("//") {
+            template.to_string()
+        } else {
+            let mut comment = "/*!\n".to_string();
+            for line in template.lines() {
+                comment.push_str(format!(" * {}", line).trim_end());
+                comment.push('\n');
+            }
+            comment.push_str(" */");
+            comment
+        };
+
+        Self { template, year, version: version.to_string() }
+    }
+
+    pub fn from_config(config: &LoadedConfig) -> Result<Option<Self>, NullScriptError> {
+        let Some(banner) = &config.config.banner else {
+            return Ok(None);
+        };
+
+        let base = config.origin_dir("banner");
+        let candidate = base.join(banner);
+        let template = if candidate.is_file() {
+            std::fs::read_to_string(&candidate)?
+        } else {
+            banner.clone()
+        };
+
+        let version = if template.contains("{version}") {
+            package_version(&base)?
+        } else {
+            String::new()
+        };
+
+        Ok(Some(Self::new(&template, current_year(), &version)))
+    }
+
+    pub fn render(&self, js_path: &Path) -> String {
+        let file = js_path.file_name().map(|name| name.to_string_lossy()).unwrap_or_default();
+
+        self.template
+            .replace("{year}", &self.year.to_string())
+            .replace("{file}", &file)
+            .replace("{version}", &self.version)
+    }
+
+    pub fn apply(&self, js: &str, js_path: &Path) -> (String, u32, u32) {
+        let banner = self.render(js_path);
+        let after = if js.starts_with("#!") {
+            js.find('\n').map_or(js.len(), |newline| newline + 1)
+        } else {
+            0
+        };
+
+        let mut emitted = String::with_capacity(js.len() + banner.len() + 2);
+        emitted.push_str(&js[..after]);
+        if after == js.len() && after > 0 && !js.ends_with('\n') {
+            emitted.push('\n');
+        }
+        emitted.push_str(&banner);
+        emitted.push('\n');
+        emitted.push_str(&js[after..]);
+
+        (emitted, u32::from(after > 0), banner.lines().count() as u32)
+    }
+}
+
+fn package_version(base: &Path) -> Result<String, NullScriptError> {
+    let package = base.join("package.json");
+    let version = std::fs::read_to_string(&package)
+        .ok()
+        .and_then(|text| serde_json::from_str::<Value>(&text).ok())
+        .and_then(|json| json.get("version").and_then(Value::as_str).map(str::to_string));
+
+    version.ok_or_else(|| {
+        NullScriptError::Io(std::io::Error::other(format!(
+            "the \"banner\" in {} uses {{version}}, but {} has no \"version\"",
+            CONFIG_FILE,
+            package.display()
+        )))
+    })
+}
+
+fn current_year() -> i64 {
+    let seconds = std::env::var("SOURCE_DATE_EPOCH")
+        .ok()
+        .and_then(|epoch| epoch.trim().parse::<i64>().ok())
+        .unwrap_or_else(|| {
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |elapsed| elapsed.as_secs() as i64)
+        });
+
+    year_of_day(seconds.div_euclid(86_400))
+}
+
+fn year_of_day(days: i64) -> i64 {
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era = (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month = (5 * day_of_year + 2) / 153;
+
+    year_of_era + era * 400 + i64::from(month >= 10)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_banner_goes_after_shebang_and_fills_placeholders() {
+        let banner = Banner::new("Copyright (c) {year} Acme\n{file} v{version}\n", 2024, "1.2.0");
+
+        let (emitted, at, lines) = banner.apply("#!/usr/bin/env node\nconsole.log(1);\n", Path::new("dist/cli.js"));
+        assert_eq!(
+            emitted,
+            "#!/usr/bin/env node\n/*!\n * Copyright (c) 2024 Acme\n * cli.js v1.2.0\n */\nconsole.log(1);\n"
+        );
+        assert_eq!((at, lines), (1, 4));
+
+        let (emitted, at, _) = Banner::new("// (c) {year}", 2024, "").apply("let a;\n", Path::new("a.js"));
+        assert_eq!((emitted.as_str(), at), ("// (c) 2024\nlet a;\n", 0));
+
+        assert_eq!(year_of_day(0), 1970);
+        assert_eq!(year_of_day(19_722), 2023);
+        assert_eq!(year_of_day(19_723), 2024);
+    }
+}
@@ -0,0 +1,82 @@
+use crate::compiler::lexer::{Lexer, TokenKind};
+use crate::compiler::resolver::ModuleGraph;
+use crate::core::NullScriptError;
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+
+pub struct BundledModule {
+    pub id: String,
+    pub code: String,
+    pub requires: BTreeMap<String, String>,
+}
+
+pub fn required_specifiers(code: &str) -> Result<Vec<String>, NullScriptError> {
+    let tokens = Lexer::new(code).with_max_depth(usize::MAX).tokenize()?;
+    let code: Vec<_> = tokens.iter().filter(|token| !token.is_trivia()).collect();
+
+    Ok(code
+        .windows(4)
+        .enumerate()
+        .filter(|(position, window)| {
+            (*position == 0 || code[position - 1].text != ".")
+                && window[0].text == "require"
+                && window[1].text == "("
+                && window[2].kind == TokenKind::String
+                && window[3].text == ")"
+        })
+        .map(|(_, window)| window[2].text[1..window[2].text.len() - 1].to_string())
+        .collect())
+}
+
+pub fn load_order(graph: &ModuleGraph, modules: &BTreeSet<PathBuf>, entries: &[PathBuf]) -> Vec<PathBuf> {
+    fn visit(graph: &ModuleGraph, modules: &BTreeSet<PathBuf>, path: &Path, seen: &mut BTreeSet<PathBuf>, order: &mut Vec<PathBuf>) {
+        if !modules.contains(path) || !seen.insert(path.to_path_buf()) {
+            return;
+        }
+        for dependency in graph.dependencies(path) {
+            visit(graph, modules, dependency, seen, order);
+        }
+        order.push(path.to_path_buf());
+    }
+
+    let mut seen = BTreeSet::new();
+    let mut order = Vec::new();
+    let roots: Vec<&PathBuf> = if entries.is_empty() { modules.iter().collect() } else { entries.iter().collect() };
+    for root in roots {
+        visit(graph, modules, root, &mut seen, &mut order);
+    }
+    order
+}
+
+pub fn render(modules: &[BundledModule], entries: &[String]) -> Result<String, NullScriptError> {
+    let mut bundle = String::from("(function () {\nvar modules = {};\nvar cache = {};\n");
+
+    for module in modules {
+        bundle.push_str(&format!(
+            "modules[{}] = [{}, function (module, exports, require) {{\n{}\n}}];\n",
+            serde_json::to_string(&module.id)?,
+            serde_json::to_string(&module.requires)?,
+            module.code.trim_end()
+        ));
+    }
+
+    bundle.push_str(
+        "function load(id) {\n\
+         \x20 if (cache[id]) return cache[id].exports;\n\
+         \x20 var module = cache[id] = { exports: {} };\n\
+         \x20 var requires = modules[id][0];\n\
+         \x20 modules[id][1].call(module.exports, module, module.exports, function (specifier) {\n\
+         \x20   if (Object.prototype.hasOwnProperty.call(requires, specifier)) return load(requires[specifier]);\n\
+         \x20   if (typeof require === \"function\") return require(specifier);\n\
+         \x20   throw new Error(\"Cannot find module '\" + specifier + \"' in the bundle\");\n\
+         \x20 });\n\
+         \x20 return module.exports;\n\
+         }\n",
+    );
+    for entry in entries {
+        bundle.push_str(&format!("load({});\n", serde_json::to_string(entry)?));
+    }
+    bundle.push_str("})();\n");
+
+    Ok(bundle)
+}
@@ -0,0 +1,75 @@
+use crate::core::NullScriptError;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+use tokio::fs;
+
+pub const CACHE_DIR: &str = ".ns-cache";
+pub const CACHE_FILE: &str = "build.json";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CachedOutput {
+    pub key: String,
+    pub outputs: Vec<PathBuf>,
+    #[serde(default)]
+    pub warnings: Vec<String>,
+    #[serde(default)]
+    pub unchanged: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BuildCache {
+    pub nsc_version: String,
+    pub files: BTreeMap<PathBuf, CachedOutput>,
+}
+
+impl BuildCache {
+    pub fn new() -> Self {
+        Self {
+            nsc_version: env!("CARGO_PKG_VERSION").to_string(),
+            files: BTreeMap::new(),
+        }
+    }
+
+    pub fn path(cache_dir: &Path) -> PathBuf {
+        cache_dir.join(CACHE_FILE)
+    }
+
+    pub async fn load(cache_dir: &Path) -> Self {
+        let content = fs::read_to_string(Self::path(cache_dir)).await.unwrap_or_default();
+        serde_json::from_str::<Self>(&content)
+            .ok()
+            .filter(|cache| cache.nsc_version == env!("CARGO_PKG_VERSION"))
+            .unwrap_or_default()
+    }
+
+    pub async fn save(&self, cache_dir: &Path) -> Result<(), NullScriptError> {
+        fs::create_dir_all(cache_dir).await?;
+        let content = serde_json::to_string_pretty(self)?;
+        fs::write(Self::path(cache_dir), content + "\n").await?;
+        Ok(())
+    }
+
+    pub fn fresh(&self, js_path: &Path, key: &str) -> Option<&CachedOutput> {
+        self.files
+            .get(js_path)
+            .filter(|cached| cached.key == key && cached.outputs.iter().all(|output| output.is_file()))
+    }
+
+    pub fn insert(&mut self, js_path: &Path, cached: CachedOutput) {
+        self.files.insert(js_path.to_path_buf(), cached);
+    }
+
+    pub fn retain_built(&mut self, output_dir: &Path, built: &BTreeSet<PathBuf>) {
+        self.files.retain(|js_path, _| !js_path.starts_with(output_dir) || built.contains(js_path));
+    }
+}
+
+impl Default for BuildCache {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
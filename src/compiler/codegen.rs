@@ -0,0 +1,302 @@
+use crate::compiler::cache::CACHE_DIR;
+use crate::core::{ExternalToolError, NullScriptError};
+use crate::core::config::{GenerateRule, LoadedConfig, CONFIG_FILE};
+use crate::core::keywords::{keyword_table_hash, KEYWORDS, KEYWORD_TABLE_VERSION};
+use crate::utils::commands::CommandUtils;
+use crate::utils::paths::PathUtils;
+use globset::Glob;
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+use walkdir::WalkDir;
+
+pub const BUILTIN_GENERATORS: &[&str] = &["keywords"];
+
+pub const STAMPS_FILE: &str = "generate.json";
+
+static SKIPPED_DIRS: &[&str] = &["node_modules", "dist", ".git", CACHE_DIR];
+
+pub struct Generated {
+    pub output: PathBuf,
+    pub regenerated: bool,
+}
+
+pub fn run(config: &LoadedConfig) -> Result<Vec<Generated>, NullScriptError> {
+    let base = config.origin_dir("generate");
+    let stamps_path = config.base_dir().join(CACHE_DIR).join(STAMPS_FILE);
+    let mut stamps: BTreeMap<PathBuf, String> = fs::read_to_string(&stamps_path)
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default();
+    let mut generated = Vec::new();
+
+    for rule in &config.config.generate {
+        match (rule.builtin.as_deref(), rule.command.as_deref()) {
+            (Some(builtin), None) => generated.push(run_builtin(builtin, &base.join(&rule.output))?),
+            (None, Some(command)) => {
+                for (output, inputs) in group_inputs(rule, &base)? {
+                    let stamp = rule_stamp(rule, command, &inputs);
+                    let regenerated = stamps.get(&output) != Some(&stamp) || is_stale(&output, &inputs);
+                    if regenerated {
+                        run_command(command, &output, &inputs)?;
+                        stamps.insert(output.clone(), stamp);
+                        save_stamps(&stamps_path, &stamps)?;
+                    }
+                    generated.push(Generated { output, regenerated });
+                }
+            }
+            _ => {
+                return Err(generate_error(format!(
+                    "every \"generate\" rule in {} needs either \"command\" or \"builtin\" (output: {})",
+                    CONFIG_FILE, rule.output
+                )))
+            }
+        }
+    }
+
+    Ok(generated)
+}
+
+pub fn keywords_module() -> String {
+    let mut module = format!(
+        "// Generated by nsc from keyword table v{} ({}). Edits will be overwritten.\nshare fixed KEYWORDS = {{\n",
+        KEYWORD_TABLE_VERSION,
+        keyword_table_hash()
+    );
+
+    for (nullscript_keyword, js_keyword) in KEYWORDS.iter() {
+        module.push_str(&format!("    {}: {},\n", escaped(nullscript_keyword), escaped(js_keyword)));
+    }
+
+    module.push_str("};\n");
+    module
+}
+
+fn escaped(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) if first.is_ascii_alphabetic() || first == '_' => {
+            format!("\"\\u{:04x}{}\"", first as u32, chars.as_str())
+        }
+        _ => format!("\"{}\"", word),
+    }
+}
+
+fn run_builtin(name: &str, output: &Path) -> Result<Generated, NullScriptError> {
+    let content = match name {
+        "keywords" => keywords_module(),
+        other => {
+            return Err(generate_error(format!(
+                "unknown built-in generator '{}', expected one of: {}",
+                other,
+                BUILTIN_GENERATORS.join(", ")
+            )))
+        }
+    };
+
+    let regenerated = fs::read_to_string(output).map_or(true, |existing| existing != content);
+    if regenerated {
+        if let Some(parent) = output.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(output, content)?;
+    }
+
+    Ok(Generated { output: output.to_path_buf(), regenerated })
+}
+
+fn group_inputs(rule: &GenerateRule, base: &Path) -> Result<BTreeMap<PathBuf, Vec<PathBuf>>, NullScriptError> {
+    let mut groups = BTreeMap::new();
+
+    let Some(pattern) = &rule.inputs else {
+        groups.insert(base.join(&rule.output), Vec::new());
+        return Ok(groups);
+    };
+
+    let matcher = Glob::new(pattern)
+        .map_err(|e| generate_error(format!("invalid \"inputs\" pattern in {}: {}", CONFIG_FILE, e)))?
+        .compile_matcher();
+
+    let walker = WalkDir::new(base).sort_by_file_name().into_iter().filter_entry(|entry| {
+        entry.depth() == 0 || !entry.file_type().is_dir() || !SKIPPED_DIRS.iter().any(|skipped| entry.file_name() == *skipped)
+    });
+    for entry in walker.filter_map(|e| e.ok()) {
+        let path = entry.path();
+        let matches = PathUtils::relative_to(path, base).is_some_and(|relative| matcher.is_match(PathUtils::to_slash(&relative)));
+        if !entry.file_type().is_file() || !matches {
+            continue;
+        }
+
+        let stem = path.file_stem().map(|s| s.to_string_lossy()).unwrap_or_default();
+        let name = path.file_name().map(|s| s.to_string_lossy()).unwrap_or_default();
+        let output = base.join(rule.output.replace("{stem}", &stem).replace("{name}", &name));
+        groups.entry(output).or_insert_with(Vec::new).push(path.to_path_buf());
+    }
+
+    Ok(groups)
+}
+
+fn is_stale(output: &Path, inputs: &[PathBuf]) -> bool {
+    let Some(generated_at) = modified(output) else {
+        return true;
+    };
+
+    inputs.iter().any(|input| modified(input).is_none_or(|changed_at| changed_at > generated_at))
+}
+
+fn rule_stamp(rule: &GenerateRule, command: &str, inputs: &[PathBuf]) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(format!("{}\0{:?}\0{}\0", command, rule.inputs, rule.output));
+    for input in inputs {
+        hasher.update(input.to_string_lossy().as_bytes());
+        hasher.update(b"\0");
+    }
+
+    hasher.finalize().iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+fn save_stamps(path: &Path, stamps: &BTreeMap<PathBuf, String>) -> Result<(), NullScriptError> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, serde_json::to_string_pretty(stamps)? + "\n")?;
+    Ok(())
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|metadata| metadata.modified()).ok()
+}
+
+fn run_command(command: &str, output: &Path, inputs: &[PathBuf]) -> Result<(), NullScriptError> {
+    let output_arg = output.to_string_lossy();
+    let first_input = inputs.first().map(|input| input.to_string_lossy()).unwrap_or_default();
+    let stem = inputs.first().and_then(|input| input.file_stem()).map(|s| s.to_string_lossy()).unwrap_or_default();
+
+    let mut words = Vec::new();
+    for word in command.split_whitespace() {
+        if word == "{inputs}" {
+            words.extend(inputs.iter().map(|input| input.to_string_lossy().to_string()));
+        } else {
+            words.push(
+                word.replace("{input}", &first_input)
+                    .replace("{output}", &output_arg)
+                    .replace("{stem}", &stem),
+            );
+        }
+    }
+
+    let Some((program, args)) = words.split_first() else {
+        return Err(generate_error(format!("empty \"command\" in {}", CONFIG_FILE)));
+    };
+
+    if let Some(parent) = output.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+    let result = CommandUtils::execute_command(program, &args)?;
+    if !result.status.success() {
+        return Err(ExternalToolError::from_output(program, &args, &result).into());
+    }
+
+    if !output.exists() {
+        return Err(generate_error(format!(
+            "generator '{}' did not write {}",
+            CommandUtils::command_line(program, &args),
+            output.display()
+        )));
+    }
+
+    Ok(())
+}
+
+fn generate_error(message: String) -> NullScriptError {
+    NullScriptError::Io(std::io::Error::other(message))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compiler::NullScriptTranspiler;
+    use crate::core::config::NsConfig;
+
+    #[test]
+    fn test_keywords_module_survives_transpilation() {
+        let transpiler = NullScriptTranspiler::new();
+        let module = keywords_module();
+        transpiler.validate_syntax(&module, None).unwrap();
+        let js = transpiler.transpile(&module).unwrap();
+        assert!(js.contains("export const KEYWORDS = {"));
+
+        if std::process::Command::new("node").arg("--version").output().is_err() {
+            return;
+        }
+
+        let dir = tempfile::tempdir().unwrap();
+        let script = dir.path().join("keywords.mjs");
+        fs::write(&script, js + "console.log(JSON.stringify(KEYWORDS));\n").unwrap();
+        let output = std::process::Command::new("node").arg(&script).output().unwrap();
+        let table: BTreeMap<String, String> = serde_json::from_slice(&output.stdout).unwrap();
+        assert_eq!(table.len(), KEYWORDS.len());
+        assert!(KEYWORDS.iter().all(|(keyword, js_keyword)| table[*keyword] == *js_keyword));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_command_generators_only_rerun_for_changed_inputs() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir_all(dir.path().join("schemas")).unwrap();
+        fs::write(dir.path().join("schemas/user.txt"), "fixed user = 1;\n").unwrap();
+        fs::write(
+            dir.path().join(CONFIG_FILE),
+            r#"{ "generate": [
+                { "inputs": "schemas/*.txt", "output": "src/gen/{stem}.ns", "command": "cp {input} {output}" },
+                { "builtin": "keywords", "output": "src/gen/keywords.ns" }
+            ] }"#,
+        ).unwrap();
+        let config = NsConfig::discover(dir.path()).unwrap();
+
+        let first = run(&config).unwrap();
+        assert_eq!(first.len(), 2);
+        assert!(first.iter().all(|generated| generated.regenerated));
+        assert_eq!(fs::read_to_string(dir.path().join("src/gen/user.ns")).unwrap(), "fixed user = 1;\n");
+
+        assert!(run(&config).unwrap().iter().all(|generated| !generated.regenerated));
+
+        fs::remove_file(dir.path().join("src/gen/user.ns")).unwrap();
+        let third = run(&config).unwrap();
+        assert!(third[0].regenerated && !third[1].regenerated);
+
+        fs::write(
+            dir.path().join(CONFIG_FILE),
+            r#"{ "generate": [
+                { "inputs": "schemas/*.txt", "output": "src/gen/{stem}.ns", "command": "cp -p {input} {output}" },
+                { "builtin": "keywords", "output": "src/gen/keywords.ns" }
+            ] }"#,
+        ).unwrap();
+        let changed = run(&NsConfig::discover(dir.path()).unwrap()).unwrap();
+        assert!(changed[0].regenerated && !changed[1].regenerated);
+        assert!(run(&NsConfig::discover(dir.path()).unwrap()).unwrap().iter().all(|generated| !generated.regenerated));
+    }
+
+    #[test]
+    fn test_inputs_skip_dependency_and_output_directories() {
+        let dir = tempfile::tempdir().unwrap();
+        for folder in ["schemas", "node_modules/pkg/schemas", "dist/schemas"] {
+            fs::create_dir_all(dir.path().join(folder)).unwrap();
+            fs::write(dir.path().join(folder).join("user.txt"), "").unwrap();
+        }
+
+        let rule = GenerateRule {
+            inputs: Some("**/*.txt".to_string()),
+            output: "src/gen/{stem}.ns".to_string(),
+            command: Some("cp {inputs} {output}".to_string()),
+            builtin: None,
+        };
+        let groups = group_inputs(&rule, dir.path()).unwrap();
+        assert_eq!(groups.len(), 1);
+        assert_eq!(groups[&dir.path().join("src/gen/user.ns")], vec![dir.path().join("schemas/user.txt")]);
+    }
+}
@@ -0,0 +1,392 @@
+use crate::compiler::lexer::{Lexer, Token, TokenKind};
+use crate::compiler::typos::{self, KeywordTypo};
+use crate::core::keywords::{JS_RESERVED_WORDS, KEYWORDS};
+use crate::core::NullScriptError;
+use std::collections::HashMap;
+
+pub const RULE_SEPARATOR: &str = "==>";
+
+static OPERATOR_CHARS: &str = "=!<>&|+-*/%^?:";
+
+static CONTEXTUAL_JS_KEYWORDS: &[&str] = &["async", "of"];
+
+pub static BUILTIN_CODEMODS: &[(&str, &str)] = &[
+    ("js-to-nullscript", "Rewrite JavaScript keywords and operators left in .ns files to their NullScript forms"),
+    ("keyword-typos", "Replace misspelled keywords such as 'modle' or 'Run' with the keyword they were meant to be"),
+];
+
+#[derive(Debug, Clone)]
+pub struct Codemod {
+    pub name: String,
+    rules: Vec<Rule>,
+    keyword_typos: bool,
+    protected: Vec<String>,
+}
+
+#[derive(Debug, Clone)]
+struct Rule {
+    pattern: Vec<Piece>,
+    replacement: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Piece {
+    Literal { text: String, glued: bool },
+    Hole(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rewrite {
+    pub source: String,
+    pub replacements: usize,
+}
+
+impl Codemod {
+    pub fn parse(name: &str, script: &str) -> Result<Self, NullScriptError> {
+        let mut rules = Vec::new();
+
+        for (number, line) in script.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let (pattern, replacement) = line.split_once(RULE_SEPARATOR).ok_or_else(|| {
+                codemod_error(format!("{} line {}: expected 'pattern {} replacement'", name, number + 1, RULE_SEPARATOR))
+            })?;
+            let rule = Rule::new(pattern.trim(), replacement.trim())
+                .map_err(|message| codemod_error(format!("{} line {}: {}", name, number + 1, message)))?;
+            rules.push(rule);
+        }
+
+        Ok(Self { name: name.to_string(), rules, keyword_typos: false, protected: Vec::new() })
+    }
+
+    pub fn builtin(name: &str) -> Option<Self> {
+        match name {
+            "js-to-nullscript" => Some(Self::js_to_nullscript()),
+            "keyword-typos" => Some(Self {
+                name: name.to_string(),
+                rules: Vec::new(),
+                keyword_typos: true,
+                protected: Vec::new(),
+            }),
+            _ => None,
+        }
+    }
+
+    pub fn with_protected(mut self, protected: Vec<String>) -> Self {
+        self.protected = protected;
+        self
+    }
+
+    fn js_to_nullscript() -> Self {
+        let mut pairs: Vec<(&str, &str)> = KEYWORDS
+            .iter()
+            .filter(|(keyword, js)| keyword != js)
+            .filter(|(_, js)| JS_RESERVED_WORDS.contains(js) || CONTEXTUAL_JS_KEYWORDS.contains(js) || is_operator(js))
+            .map(|(keyword, js)| (*js, *keyword))
+            .collect();
+        pairs.sort_by_key(|(js, _)| std::cmp::Reverse(js.len()));
+
+        let rules = pairs
+            .into_iter()
+            .filter_map(|(js, keyword)| Rule::new(js, keyword).ok())
+            .collect();
+        Self { name: "js-to-nullscript".to_string(), rules, keyword_typos: false, protected: Vec::new() }
+    }
+
+    pub fn apply(&self, source: &str) -> Result<Rewrite, NullScriptError> {
+        let mut source = source.to_string();
+        let mut replacements = 0;
+
+        for rule in self.rules.iter().filter(|rule| !rule.rewrites_any(&self.protected)) {
+            let (rewritten, count) = rule.apply(&source)?;
+            source = rewritten;
+            replacements += count;
+        }
+
+        if self.keyword_typos {
+            let found = typos::find(&source, &self.protected)?;
+            source = KeywordTypo::apply(&source, &found);
+            replacements += found.len();
+        }
+
+        Ok(Rewrite { source, replacements })
+    }
+}
+
+impl Rule {
+    fn new(pattern: &str, replacement: &str) -> Result<Self, String> {
+        let tokens = Lexer::new(pattern).tokenize().map_err(|e| e.to_string())?;
+        let mut pieces = Vec::new();
+        let mut glued = false;
+
+        for token in &tokens {
+            if token.is_trivia() {
+                glued = false;
+                continue;
+            }
+
+            match token.text.strip_prefix('$') {
+                Some(name) if token.kind == TokenKind::Identifier && !name.is_empty() => {
+                    if matches!(pieces.last(), Some(Piece::Hole(_))) {
+                        return Err(format!("placeholders '${}' and the one before it need a token between them", name));
+                    }
+                    pieces.push(Piece::Hole(name.to_string()));
+                }
+                _ => {
+                    let glued = glued
+                        && is_operator(token.text)
+                        && matches!(pieces.last(), Some(Piece::Literal { text, .. }) if is_operator(text));
+                    pieces.push(Piece::Literal { text: token.text.to_string(), glued });
+                }
+            }
+            glued = true;
+        }
+
+        if !matches!(pieces.first(), Some(Piece::Literal { .. })) {
+            return Err("a pattern must start with a token, not a placeholder".to_string());
+        }
+        for hole in replacement_holes(replacement) {
+            if !pieces.contains(&Piece::Hole(hole.to_string())) {
+                return Err(format!("'${}' is used in the replacement but not in the pattern", hole));
+            }
+        }
+
+        Ok(Self { pattern: pieces, replacement: replacement.to_string() })
+    }
+
+    fn rewrites_any(&self, names: &[String]) -> bool {
+        self.pattern
+            .iter()
+            .any(|piece| matches!(piece, Piece::Literal { text, .. } if names.contains(text)))
+    }
+
+    fn apply(&self, source: &str) -> Result<(String, usize), NullScriptError> {
+        let tokens: Vec<Token> = Lexer::new(source)
+            .with_max_depth(usize::MAX)
+            .tokenize()?
+            .into_iter()
+            .filter(|token| !token.is_trivia())
+            .collect();
+
+        let mut rewritten = String::with_capacity(source.len());
+        let mut copied = 0;
+        let mut count = 0;
+        let mut index = 0;
+
+        while index < tokens.len() {
+            let Some((end, captures)) = self.match_at(&tokens, index, source) else {
+                index += 1;
+                continue;
+            };
+
+            let start = tokens[index].start;
+            let last = &tokens[end - 1];
+            let stop = last.start + last.text.len();
+
+            rewritten.push_str(&source[copied..start]);
+            rewritten.push_str(&self.render(&captures, &source[..start], &source[stop..]));
+            copied = stop;
+            count += 1;
+            index = end;
+        }
+
+        rewritten.push_str(&source[copied..]);
+        Ok((rewritten, count))
+    }
+
+    fn match_at<'a>(&self, tokens: &[Token], index: usize, source: &'a str) -> Option<(usize, HashMap<&str, &'a str>)> {
+        let previous = index.checked_sub(1).map(|previous| &tokens[previous]);
+        let first = &tokens[index];
+        if first.kind == TokenKind::Identifier && previous.is_some_and(|previous| previous.text == ".") {
+            return None;
+        }
+        if previous.is_some_and(|previous| extends_operator(previous, first)) {
+            return None;
+        }
+
+        let mut captures = HashMap::new();
+        let end = self.match_pieces(0, tokens, index, &mut captures)?;
+        if tokens.get(end).is_some_and(|next| extends_operator(&tokens[end - 1], next)) {
+            return None;
+        }
+
+        let captured = captures
+            .into_iter()
+            .map(|(name, (from, to)): (&str, (usize, usize))| {
+                let last = &tokens[to - 1];
+                (name, &source[tokens[from].start..last.start + last.text.len()])
+            })
+            .collect();
+        Some((end, captured))
+    }
+
+    fn match_pieces<'p>(
+        &'p self,
+        piece: usize,
+        tokens: &[Token],
+        index: usize,
+        captures: &mut HashMap<&'p str, (usize, usize)>,
+    ) -> Option<usize> {
+        let Some(current) = self.pattern.get(piece) else {
+            return Some(index);
+        };
+
+        match current {
+            Piece::Literal { text, glued } => {
+                let token = tokens.get(index)?;
+                let adjacent = index > 0 && tokens[index - 1].start + tokens[index - 1].text.len() == token.start;
+                if token.text != text || (*glued && !adjacent) {
+                    return None;
+                }
+                self.match_pieces(piece + 1, tokens, index + 1, captures)
+            }
+            Piece::Hole(name) => {
+                let mut end = index;
+                loop {
+                    end = balanced_unit(tokens, end)?;
+                    captures.insert(name, (index, end));
+                    if let Some(matched) = self.match_pieces(piece + 1, tokens, end, captures) {
+                        return Some(matched);
+                    }
+                }
+            }
+        }
+    }
+
+    fn render(&self, captures: &HashMap<&str, &str>, before: &str, after: &str) -> String {
+        let mut rendered = String::with_capacity(self.replacement.len());
+        let mut rest = self.replacement.as_str();
+
+        while let Some(dollar) = rest.find('$') {
+            rendered.push_str(&rest[..dollar]);
+            let name_len = rest[dollar + 1..].find(|c: char| !is_word(c)).unwrap_or(rest.len() - dollar - 1);
+            let name = &rest[dollar + 1..dollar + 1 + name_len];
+            match captures.get(name) {
+                Some(captured) if !name.is_empty() => rendered.push_str(captured),
+                _ => rendered.push_str(&rest[dollar..dollar + 1 + name_len]),
+            }
+            rest = &rest[dollar + 1 + name_len..];
+        }
+        rendered.push_str(rest);
+
+        if before.ends_with(is_word) && rendered.starts_with(is_word) {
+            rendered.insert(0, ' ');
+        }
+        if after.starts_with(is_word) && rendered.ends_with(is_word) {
+            rendered.push(' ');
+        }
+        rendered
+    }
+}
+
+fn balanced_unit(tokens: &[Token], index: usize) -> Option<usize> {
+    let token = tokens.get(index)?;
+    if token.kind != TokenKind::Punct {
+        return Some(index + 1);
+    }
+
+    match token.text {
+        ")" | "]" | "}" | ";" => None,
+        "(" | "[" | "{" => {
+            let mut depth = 0;
+            for (offset, token) in tokens[index..].iter().enumerate() {
+                if token.kind != TokenKind::Punct {
+                    continue;
+                }
+                match token.text {
+                    "(" | "[" | "{" => depth += 1,
+                    ")" | "]" | "}" => {
+                        depth -= 1;
+                        if depth == 0 {
+                            return Some(index + offset + 1);
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            None
+        }
+        _ => Some(index + 1),
+    }
+}
+
+fn extends_operator(left: &Token, right: &Token) -> bool {
+    let punct_operator = |token: &Token| token.kind == TokenKind::Punct && is_operator(token.text);
+    punct_operator(left) && punct_operator(right) && left.start + left.text.len() == right.start
+}
+
+fn is_operator(text: &str) -> bool {
+    !text.is_empty() && text.chars().all(|c| OPERATOR_CHARS.contains(c))
+}
+
+fn replacement_holes(replacement: &str) -> impl Iterator<Item = &str> {
+    replacement
+        .split('$')
+        .skip(1)
+        .map(|rest| &rest[..rest.find(|c: char| !is_word(c)).unwrap_or(rest.len())])
+        .filter(|name| !name.is_empty())
+}
+
+fn is_word(c: char) -> bool {
+    c.is_alphanumeric() || c == '_' || c == '$'
+}
+
+fn codemod_error(message: String) -> NullScriptError {
+    NullScriptError::Io(std::io::Error::new(std::io::ErrorKind::InvalidInput, message))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_codemods_rewrite_code_tokens_only() {
+        let script = "# louder logging\nspeak.say($message) ==> speak.yell($message)\n";
+        let codemod = Codemod::parse("louder.ns-codemod", script).unwrap();
+        let rewrite = codemod
+            .apply("speak.say(f(a, \"speak.say(x)\"));\n// speak.say(y)\nother.speak.say(z);\n")
+            .unwrap();
+        assert_eq!(rewrite.source, "speak.yell(f(a, \"speak.say(x)\"));\n// speak.say(y)\nother.speak.say(z);\n");
+        assert_eq!(rewrite.replacements, 1);
+
+        let spaced = Codemod::parse("spaced", "speak.say($message) ==> speak.yell($message)").unwrap();
+        assert_eq!(spaced.apply("speak . say (1);").unwrap().source, "speak.yell(1);");
+
+        let migrate = Codemod::builtin("js-to-nullscript").unwrap();
+        let rewrite = migrate
+            .apply("const f = (a) => {\n    if (!a && a.value !== \"if\") return this.x >= 1;\n};\n")
+            .unwrap();
+        assert_eq!(
+            rewrite.source,
+            "fixed f = (a) => {\n    whatever (not a and a.value isnt \"if\") return self.x moreeq 1;\n};\n"
+        );
+
+        assert!(Codemod::parse("bad", "speak.say($a) ==> $b").is_err());
+    }
+
+    #[test]
+    fn test_keyword_typos_codemod() {
+        let source = "modle Shape {\n}\nwhateverr (ready) {\n    speek.say(\"modle\");\n}\n";
+        let rewrite = Codemod::builtin("keyword-typos").unwrap().apply(source).unwrap();
+        assert_eq!(rewrite.source, "model Shape {\n}\nwhatever (ready) {\n    speak.say(\"modle\");\n}\n");
+        assert_eq!(rewrite.replacements, 3);
+
+        let protected = Codemod::builtin("keyword-typos").unwrap().with_protected(vec!["modle".to_string()]);
+        assert_eq!(protected.apply(source).unwrap().replacements, 2);
+    }
+
+    #[test]
+    fn test_rule_files_leave_protected_identifiers_alone() {
+        let script = "modle ==> model\nlegacy.fetch($url) ==> speak.fetch($url)\nspeak.say($message) ==> speak.yell($message)\n";
+        let source = "modle Shape {\n}\nlegacy.fetch(modle);\nspeak.say(modle);\n";
+        let codemod = Codemod::parse("rename.ns-codemod", script).unwrap();
+        assert_eq!(codemod.apply(source).unwrap().replacements, 5);
+
+        let rewrite = codemod.with_protected(vec!["modle".to_string(), "legacy".to_string()]).apply(source).unwrap();
+        assert_eq!(rewrite.source, "modle Shape {\n}\nlegacy.fetch(modle);\nspeak.yell(modle);\n");
+        assert_eq!(rewrite.replacements, 1);
+    }
+}
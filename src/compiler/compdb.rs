@@ -0,0 +1,176 @@
+use crate::compiler::options::{ModuleFormat, Target, TranspileOptions};
+use crate::compiler::platform;
+use crate::compiler::resolver::ModuleGraph;
+use crate::core::NullScriptError;
+use crate::core::config::{DiagnosticLevel, LoadedConfig, NsConfig, OperatorAliases, TopLevelAwaitPolicy};
+use crate::utils::paths::PathUtils;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CompileCommand {
+    pub directory: PathBuf,
+    pub file: PathBuf,
+    pub output: PathBuf,
+    pub arguments: Vec<String>,
+    pub options: EffectiveOptions,
+    pub config: Vec<PathBuf>,
+    pub dependencies: Vec<PathBuf>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EffectiveOptions {
+    pub target: Target,
+    pub module: ModuleFormat,
+    pub source_map: bool,
+    pub minify: bool,
+    pub strict_ns: bool,
+    pub dialect: String,
+    pub defines: BTreeMap<String, String>,
+    pub top_level_await: TopLevelAwaitPolicy,
+    pub runtime_shim: bool,
+    pub operator_aliases: OperatorAliases,
+    pub platform: Option<String>,
+    pub protected: Vec<String>,
+    pub keyword_typos: DiagnosticLevel,
+}
+
+pub fn generate(
+    graph: &ModuleGraph,
+    input: &Path,
+    out_dir: &Path,
+    config: &LoadedConfig,
+    options: &TranspileOptions,
+) -> Result<Vec<CompileCommand>, NullScriptError> {
+    let directory = PathUtils::absolute(Path::new("."));
+    let out_dir = PathUtils::absolute(out_dir);
+    let single_file = PathUtils::absolute(input).is_file();
+    let files: Vec<PathBuf> = graph.modules.keys().cloned().collect();
+    let selection = platform::select(files, graph.platform.as_deref());
+    let mut commands = Vec::new();
+
+    for (file, logical) in selection.sources {
+        let output = if single_file {
+            PathUtils::single_output_path(&file, &out_dir, "js")
+        } else {
+            match PathUtils::output_path(&graph.root, &logical, &out_dir, "js") {
+                Some(output) => output,
+                None => continue,
+            }
+        };
+
+        let file_config = NsConfig::discover(&file)?;
+        let mut protected = config.config.protected.clone();
+        for name in file_config.config.protected {
+            if !protected.contains(&name) {
+                protected.push(name);
+            }
+        }
+
+        commands.push(CompileCommand {
+            directory: directory.clone(),
+            arguments: arguments(&file, &output, graph.platform.as_deref(), options),
+            options: EffectiveOptions {
+                target: options.target,
+                module: options.module,
+                source_map: options.source_map,
+                minify: options.minify,
+                strict_ns: options.strict_ns,
+                dialect: options.dialect().to_string(),
+                defines: options.defines.clone(),
+                top_level_await: options.top_level_await,
+                runtime_shim: options.runtime_shim,
+                operator_aliases: options.operator_aliases,
+                platform: graph.platform.clone(),
+                protected,
+                keyword_typos: config.config.keyword_typos,
+            },
+            config: file_config.chain,
+            dependencies: graph.dependencies(&file).into_iter().map(Path::to_path_buf).collect(),
+            file,
+            output,
+        });
+    }
+
+    Ok(commands)
+}
+
+fn arguments(file: &Path, output: &Path, platform: Option<&str>, options: &TranspileOptions) -> Vec<String> {
+    let out_dir = output.parent().unwrap_or(Path::new("."));
+    let mut arguments = vec![
+        "nsc".to_string(),
+        "build".to_string(),
+        file.to_string_lossy().to_string(),
+        "--outDir".to_string(),
+        out_dir.to_string_lossy().to_string(),
+        "--target".to_string(),
+        options.target.as_str().to_string(),
+        "--module".to_string(),
+        options.module.as_str().to_string(),
+    ];
+
+    if let Some(platform) = platform {
+        arguments.extend(["--platform".to_string(), platform.to_string()]);
+    }
+
+    for (flag, enabled) in [("--sourceMap", options.source_map), ("--minify", options.minify), ("--strict-ns", options.strict_ns), ("--runtime-shim", options.runtime_shim)] {
+        if enabled {
+            arguments.push(flag.to_string());
+        }
+    }
+    if let Some(dialect) = &options.dialect {
+        arguments.extend(["--dialect".to_string(), dialect.clone()]);
+    }
+    for (name, value) in &options.defines {
+        arguments.extend(["--define".to_string(), format!("{}={}", name, value)]);
+    }
+    if options.operator_aliases != OperatorAliases::default() {
+        arguments.extend(["--operator-aliases".to_string(), options.operator_aliases.as_str().to_string()]);
+    }
+
+    arguments
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compiler::resolver::ResolveOptions;
+    use std::fs;
+
+    #[test]
+    fn test_generate_describes_every_selected_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let src = dir.path().join("src");
+        fs::create_dir_all(src.join("lib")).unwrap();
+        fs::write(src.join("main.ns"), "use { helper } from \"./lib/helper.ns\";\nhelper();\n").unwrap();
+        fs::write(src.join("lib/helper.ns"), "share run helper() {}\n").unwrap();
+        fs::write(src.join("lib/nsconfig.json"), r#"{ "protected": ["list"] }"#).unwrap();
+        fs::write(src.join("io.node.ns"), "fixed io = 1;\n").unwrap();
+        fs::write(src.join("io.browser.ns"), "fixed io = 2;\n").unwrap();
+
+        let config = NsConfig::discover(&src).unwrap();
+        let options = ResolveOptions { platform: Some("node".to_string()), ..Default::default() };
+        let graph = ModuleGraph::build(&src, &options).unwrap();
+        let transpile = TranspileOptions::new().with_minify(true).with_define("DEBUG", "false");
+        let commands = generate(&graph, &src, &dir.path().join("dist"), &config, &transpile).unwrap();
+
+        let files: Vec<String> = commands.iter().map(|c| graph.display_path(&c.file)).collect();
+        assert_eq!(files, vec!["io.node.ns", "lib/helper.ns", "main.ns"]);
+
+        let main = &commands[2];
+        assert!(main.output.ends_with("dist/main.js"));
+        assert!(commands[0].output.ends_with("dist/io.js"));
+        assert!(commands[0].arguments.windows(2).any(|pair| pair == ["--platform", "node"]));
+        assert_eq!(main.dependencies, vec![src.join("lib/helper.ns")]);
+        assert!(main.arguments.ends_with(&["--minify".to_string(), "--define".to_string(), "DEBUG=false".to_string()]));
+        assert_eq!(commands[1].options.protected, vec!["list".to_string()]);
+        assert_eq!(commands[1].config.len(), 1);
+
+        let json = serde_json::to_value(&commands).unwrap();
+        assert_eq!(json[2]["options"]["target"], "esnext");
+        assert_eq!(json[2]["options"]["keywordTypos"], "warn");
+    }
+}
@@ -0,0 +1,251 @@
+use crate::compiler::lexer::{Lexer, Token, TokenKind};
+use crate::compiler::transpiler::rewrite_allowed;
+use crate::core::NullScriptError;
+use crate::core::keywords::{is_operator_alias, ARITHMETIC_ALIASES, KEYWORDS};
+
+pub struct Conversion {
+    pub source: String,
+    pub collisions: Vec<Collision>,
+}
+
+pub struct Collision {
+    pub line: u32,
+    pub word: String,
+    pub context: &'static str,
+}
+
+pub fn convert(js: &str) -> Result<Conversion, NullScriptError> {
+    convert_with(js, false)
+}
+
+pub fn convert_with(js: &str, operator_aliases: bool) -> Result<Conversion, NullScriptError> {
+    let tokens = Lexer::new(js).tokenize()?;
+    let mut source = String::with_capacity(js.len());
+    let mut collisions = Vec::new();
+    let mut skip_until = 0;
+
+    for (index, token) in tokens.iter().enumerate() {
+        if token.start < skip_until {
+            continue;
+        }
+
+        match token.kind {
+            TokenKind::Punct if is_namespace_star(&tokens, index) => {
+                source.push_str("everything");
+                continue;
+            }
+            TokenKind::Punct if operator_aliases => {
+                if let Some((alias, end)) = operator_alias(js, &tokens, index) {
+                    if source.ends_with(is_word) {
+                        source.push(' ');
+                    }
+                    source.push_str(alias);
+                    if js[end..].starts_with(is_word) {
+                        source.push(' ');
+                    }
+                    skip_until = end;
+                    continue;
+                }
+            }
+            TokenKind::Identifier if rewrite_allowed(&js[..token.start]) => {
+                if is_loop_label(&tokens, index) {
+                    source.push_str("mark ");
+                }
+                if let Some(keyword) = nullscript_keyword(token.text, &tokens, index) {
+                    source.push_str(keyword);
+                    continue;
+                }
+
+                if is_rewritten(token.text) {
+                    collisions.push(Collision {
+                        line: token.line,
+                        word: token.text.to_string(),
+                        context: "identifier",
+                    });
+                }
+            }
+            _ => {}
+        }
+
+        source.push_str(token.text);
+    }
+
+    collisions.sort_by_key(|collision| collision.line);
+    Ok(Conversion { source, collisions })
+}
+
+fn nullscript_keyword(word: &str, tokens: &[Token], index: usize) -> Option<&'static str> {
+    let next = tokens[index + 1..].iter().find(|token| !token.is_trivia());
+    let previous = tokens[..index].iter().rev().find(|token| !token.is_trivia());
+
+    match word {
+        "function" if !next.is_some_and(|token| token.kind == TokenKind::Identifier || token.text == "(") => return None,
+        "constructor" if previous.is_some_and(|token| token.text == ".") => return None,
+        "default" if previous.is_some_and(|token| token.text == "as") || next.is_some_and(|token| token.text == "as") => return None,
+        _ => {}
+    }
+
+    KEYWORDS
+        .iter()
+        .find(|(keyword, js_keyword)| *js_keyword == word && keyword != js_keyword)
+        .map(|(keyword, _)| *keyword)
+}
+
+fn is_namespace_star(tokens: &[Token], index: usize) -> bool {
+    let mut previous = tokens[..index].iter().rev().filter(|token| !token.is_trivia());
+    let next = tokens[index + 1..].iter().find(|token| !token.is_trivia());
+
+    tokens[index].text == "*"
+        && next.is_some_and(|token| matches!(token.text, "as" | "from"))
+        && match previous.next().map(|token| token.text) {
+            Some("import" | "export") => true,
+            Some(",") => previous.nth(1).is_some_and(|token| token.text == "import"),
+            _ => false,
+        }
+}
+
+fn is_loop_label(tokens: &[Token], index: usize) -> bool {
+    let mut next = tokens[index + 1..].iter().filter(|token| !token.is_trivia());
+    let previous = tokens[..index].iter().rev().find(|token| !token.is_trivia());
+
+    previous.is_none_or(|token| matches!(token.text, ";" | "{" | "}"))
+        && next.next().is_some_and(|token| token.text == ":")
+        && next.next().is_some_and(|token| matches!(token.text, "for" | "while" | "do" | "switch"))
+}
+
+fn operator_alias(js: &str, tokens: &[Token], index: usize) -> Option<(&'static str, usize)> {
+    let operator_char = |token: &Token| token.kind == TokenKind::Punct && "=!<>&|*%".contains(token.text);
+    if index > 0 && operator_char(&tokens[index - 1]) && tokens[index - 1].start + 1 == tokens[index].start {
+        return None;
+    }
+
+    let mut end = tokens[index].start;
+    for token in &tokens[index..] {
+        if !operator_char(token) || token.start != end {
+            break;
+        }
+        end = token.start + token.text.len();
+    }
+
+    let operator = &js[tokens[index].start..end];
+    KEYWORDS
+        .iter()
+        .find(|(keyword, js_keyword)| *js_keyword == operator && is_operator_alias(keyword))
+        .map(|(keyword, _)| (*keyword, end))
+}
+
+fn is_word(c: char) -> bool {
+    c.is_alphanumeric() || c == '_' || c == '$'
+}
+
+fn is_rewritten(word: &str) -> bool {
+    KEYWORDS
+        .iter()
+        .any(|(keyword, js_keyword)| *keyword == word && js_keyword != keyword && !ARITHMETIC_ALIASES.contains(keyword))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compiler::NullScriptTranspiler;
+    use crate::compiler::options::TranspileOptions;
+    use crate::core::config::OperatorAliases;
+    use std::path::Path;
+
+    #[test]
+    fn test_convert_round_trips() {
+        let js = "class Greeter extends Base {\n    constructor(name) {\n        super(name);\n    }\n}\nfunction hello(done) {\n    if (this.ready === true) {\n        console.log(\"done\");\n    }\n}\n";
+        let conversion = convert(js).unwrap();
+
+        assert!(conversion.source.starts_with("model Greeter inherits Base {\n    __init__(name) {"));
+        assert!(conversion.source.contains("run hello(done)"));
+        assert!(conversion.source.contains("whatever (self.ready === yes)"));
+        assert!(conversion.source.contains("speak.say(\"done\")"));
+        assert_eq!(convert("try { a(); } catch { b(); }\n").unwrap().source, "test { a(); } grab { b(); }\n");
+
+        let collisions: Vec<(u32, &str, &str)> = conversion
+            .collisions
+            .iter()
+            .map(|c| (c.line, c.word.as_str(), c.context))
+            .collect();
+        assert_eq!(collisions, vec![(6, "done", "identifier")]);
+    }
+
+    #[test]
+    fn test_convert_namespace_imports_and_exports() {
+        let js = "import * as utils from './utils.js';\nimport React, * as all from 'react';\nexport * from './helpers.js';\nexport * as tools from './tools.js';\nconst area = a * b;\n";
+        let converted = convert(js).unwrap().source;
+        assert_eq!(
+            converted,
+            "use everything as utils from './utils.js';\nuse React, everything as all from 'react';\nshare everything from './helpers.js';\nshare everything as tools from './tools.js';\nfixed area = a * b;\n"
+        );
+        assert_eq!(NullScriptTranspiler::new().transpile(&converted).unwrap(), js);
+    }
+
+    #[test]
+    fn test_convert_marks_loop_labels() {
+        let js = "outer: for (const row of rows) {\n    inner: while (row.length) {\n        if (row[0]) continue outer;\n        break inner;\n    }\n}\nconst point = { x: 1 };\n";
+        let converted = convert(js).unwrap().source;
+        assert_eq!(
+            converted,
+            "mark outer: since (fixed row part rows) {\n    mark inner: when (row.length) {\n        whatever (row[0]) keepgoing outer;\n        stop inner;\n    }\n}\nfixed point = { x: 1 };\n"
+        );
+
+        let transpiler = NullScriptTranspiler::new();
+        transpiler.validate_syntax(&converted, None).unwrap();
+        assert_eq!(transpiler.transpile(&converted).unwrap(), js);
+    }
+
+    #[test]
+    fn test_convert_with_operator_aliases() {
+        let js = "if (!ready && a === b || a<=b) { x = y => y !== 1; z = !!w; }\n";
+        assert_eq!(
+            convert_with(js, true).unwrap().source,
+            "whatever (not ready and a is b or a lesseq b) { x = y => y isnt 1; z = !!w; }\n"
+        );
+        assert_eq!(convert(js).unwrap().source, "whatever (!ready && a === b || a<=b) { x = y => y !== 1; z = !!w; }\n");
+
+        let transpiler = NullScriptTranspiler::new().with_options(
+            TranspileOptions::new().with_operator_aliases(OperatorAliases::Off),
+        );
+        assert_eq!(transpiler.transpile("fixed is = not && a;\n").unwrap(), "const is = not && a;\n");
+    }
+
+    #[test]
+    fn test_math_operator_aliases_and_compound_assignment_passthrough() {
+        let source = "fixed area = side power 2;\nlet odd = n leftover 2 isnt 0;\ntotal **= 2;\nrest %= 3;\nspeak.say(`${n % 2}% of ${a * b}`);\n";
+        let js = "const area = side ** 2;\nlet odd = n % 2 !== 0;\ntotal **= 2;\nrest %= 3;\nconsole.log(`${n % 2}% of ${a * b}`);\n";
+        assert_eq!(NullScriptTranspiler::new().transpile(source).unwrap(), js);
+
+        assert_eq!(
+            convert_with(js, true).unwrap().source,
+            "fixed area = side power 2;\nlet odd = n leftover 2 isnt 0;\ntotal **= 2;\nrest %= 3;\nspeak.say(`${n leftover 2}% of ${a * b}`);\n"
+        );
+
+        let names = "fixed car = { power: 150, leftover: 2 };\nfixed power = 2;\nlet leftover = car.leftover;\nreturn power;\nspeak.say(car.power, power power 2, 7 leftover (power));\n";
+        assert_eq!(
+            NullScriptTranspiler::new().transpile(names).unwrap(),
+            "const car = { power: 150, leftover: 2 };\nconst power = 2;\nlet leftover = car.leftover;\nreturn power;\nconsole.log(car.power, power ** 2, 7 % (power));\n"
+        );
+        assert!(convert("const power = 2;\n").unwrap().collisions.is_empty());
+    }
+
+    #[test]
+    fn test_roundtrip_fixtures() {
+        let fixtures = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/roundtrip");
+        let Ok(entries) = std::fs::read_dir(&fixtures) else {
+            return;
+        };
+        let transpiler = NullScriptTranspiler::new();
+
+        for entry in entries {
+            let dir = entry.unwrap().path();
+            let read = |name: &str| std::fs::read_to_string(dir.join(name)).unwrap();
+            let converted = read("converted.ns");
+
+            assert_eq!(convert(&read("input.js")).unwrap().source, converted, "{}: conversion changed", dir.display());
+            transpiler.validate_syntax(&converted, None).unwrap();
+            assert_eq!(transpiler.transpile(&converted).unwrap(), read("output.js"), "{}: output changed", dir.display());
+        }
+    }
+}
@@ -0,0 +1,75 @@
+use crate::compiler::resolver::{ImportTarget, ModuleGraph};
+use crate::utils::paths::PathUtils;
+use std::path::{Path, PathBuf};
+
+pub fn render(graph: &ModuleGraph, targets: &[(PathBuf, PathBuf)]) -> String {
+    let directory = PathUtils::absolute(Path::new("."));
+    let mut depfile = String::new();
+
+    for (output, source) in targets {
+        let module = PathUtils::normalize(&PathUtils::absolute(source));
+        let mut line = format!("{}: {}", escape(&PathUtils::to_slash(output)), escape(&PathUtils::to_slash(source)));
+
+        let imports = graph.modules.get(&module).map(|node| node.imports.as_slice()).unwrap_or_default();
+        for edge in imports {
+            let ImportTarget::Local(dependency) = &edge.target else {
+                continue;
+            };
+            let shown = PathUtils::relative_to(dependency, &directory).unwrap_or_else(|| dependency.to_path_buf());
+            line.push_str(" \\\n  ");
+            line.push_str(&escape(&PathUtils::to_slash(&shown)));
+        }
+
+        depfile.push_str(&line);
+        depfile.push('\n');
+    }
+
+    depfile
+}
+
+fn escape(path: &str) -> String {
+    let mut escaped = String::with_capacity(path.len());
+
+    for c in path.chars() {
+        match c {
+            ' ' | '#' | '\\' => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            '$' => escaped.push_str("$$"),
+            _ => escaped.push(c),
+        }
+    }
+
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compiler::resolver::ResolveOptions;
+    use std::fs;
+
+    #[test]
+    fn test_render_lists_imported_modules() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir_all(dir.path().join("my lib")).unwrap();
+        fs::write(dir.path().join("main.ns"), "use { a } from \"./my lib/a.ns\";\nuse fs from \"fs\";\n").unwrap();
+        fs::write(dir.path().join("my lib/a.ns"), "share fixed a = 1;\n").unwrap();
+
+        let graph = ModuleGraph::build(dir.path(), &ResolveOptions::default()).unwrap();
+        let targets = vec![
+            (PathBuf::from("dist/main.js"), dir.path().join("main.ns")),
+            (PathBuf::from("dist/my lib/a.js"), dir.path().join("my lib/a.ns")),
+        ];
+        let root = PathUtils::to_slash(dir.path());
+
+        assert_eq!(
+            render(&graph, &targets),
+            format!(
+                "dist/main.js: {root}/main.ns \\\n  {root}/my\\ lib/a.ns\ndist/my\\ lib/a.js: {root}/my\\ lib/a.ns\n",
+                root = root
+            )
+        );
+    }
+}
@@ -0,0 +1,117 @@
+use crate::core::types::Location;
+use crate::core::NullScriptError;
+use std::path::Path;
+
+static FENCE_LANGUAGES: &[&str] = &["nullscript", "ns"];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DocBlock {
+    pub line: u32,
+    pub source: String,
+    pub run: bool,
+}
+
+pub fn is_markdown_file(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("md") || ext.eq_ignore_ascii_case("markdown"))
+}
+
+pub fn nullscript_blocks(markdown: &str) -> Vec<DocBlock> {
+    let mut blocks = Vec::new();
+    let mut open: Option<(char, usize, usize, Option<DocBlock>)> = None;
+
+    for (index, line) in markdown.lines().enumerate() {
+        let indent = line.len() - line.trim_start_matches(' ').len();
+        let trimmed = &line[indent..];
+        let fence_char = trimmed.chars().next().filter(|c| (*c == '`' || *c == '~') && indent < 4);
+        let fence_len = fence_char.map_or(0, |c| trimmed.len() - trimmed.trim_start_matches(c).len());
+
+        match &mut open {
+            None => {
+                let Some(c) = fence_char.filter(|_| fence_len >= 3) else {
+                    continue;
+                };
+                let info = trimmed[fence_len..].trim();
+                if c == '`' && info.contains('`') {
+                    continue;
+                }
+
+                let mut words = info.split([' ', ',', '\t']).filter(|word| !word.is_empty());
+                let block = match words.next() {
+                    Some(language) if FENCE_LANGUAGES.contains(&language.to_ascii_lowercase().as_str()) => {
+                        let attributes: Vec<&str> = words.collect();
+                        (!attributes.contains(&"ignore")).then(|| DocBlock {
+                            line: index as u32 + 2,
+                            source: String::new(),
+                            run: !attributes.contains(&"no-run"),
+                        })
+                    }
+                    _ => None,
+                };
+                open = Some((c, fence_len, indent, block));
+            }
+            Some((c, len, fence_indent, block)) => {
+                let closes = fence_char == Some(*c) && fence_len >= *len && trimmed[fence_len..].trim().is_empty();
+                if closes {
+                    if let Some(block) = block.take() {
+                        blocks.push(block);
+                    }
+                    open = None;
+                } else if let Some(block) = block {
+                    let strip = indent.min(*fence_indent);
+                    block.source.push_str(&line[strip..]);
+                    block.source.push('\n');
+                }
+            }
+        }
+    }
+
+    blocks
+}
+
+pub fn relocate(error: NullScriptError, markdown: &Path, first_line: u32) -> NullScriptError {
+    let shift = |location: &mut Location| {
+        location.file_path = Some(markdown.to_path_buf());
+        location.line = Some(location.line.unwrap_or(1) + first_line - 1);
+    };
+
+    match error {
+        NullScriptError::Transpile(mut e) => {
+            shift(&mut e.location);
+            NullScriptError::Transpile(e)
+        }
+        NullScriptError::Syntax(mut e) => {
+            shift(&mut e.location);
+            NullScriptError::Syntax(e)
+        }
+        NullScriptError::SyntaxErrors { mut errors, omitted } => {
+            errors.iter_mut().for_each(|e| shift(&mut e.location));
+            NullScriptError::SyntaxErrors { errors, omitted }
+        }
+        NullScriptError::Type(mut e) => {
+            shift(&mut e.location);
+            NullScriptError::Type(e)
+        }
+        other => other,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_nullscript_blocks_track_markdown_lines() {
+        let markdown = "# Demo\n\n```nullscript\nspeak.say(\"hi\");\n```\n\n```js\nconsole.log(1);\n```\n\n  ~~~ns no-run\n  fixed a = 1;\n  ~~~\n\n```nullscript ignore\nbroken(\n```\n\n````ns\n```\n````\n";
+        let blocks = nullscript_blocks(markdown);
+
+        assert_eq!(
+            blocks,
+            vec![
+                DocBlock { line: 4, source: "speak.say(\"hi\");\n".to_string(), run: true },
+                DocBlock { line: 12, source: "fixed a = 1;\n".to_string(), run: false },
+                DocBlock { line: 20, source: "```\n".to_string(), run: true },
+            ]
+        );
+    }
+}
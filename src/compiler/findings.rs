@@ -0,0 +1,102 @@
+use crate::compiler::Diagnostic;
+use crate::core::config::DiagnosticLevel;
+use crate::core::NullScriptError;
+use crate::utils::paths::PathUtils;
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+
+pub struct ExternalDiagnostic {
+    pub file: PathBuf,
+    pub diagnostic: Diagnostic,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Report {
+    Tool {
+        tool: Option<String>,
+        diagnostics: Vec<Finding>,
+    },
+    List(Vec<Finding>),
+}
+
+#[derive(Deserialize)]
+struct Finding {
+    file: PathBuf,
+    line: u32,
+    column: Option<u32>,
+    #[serde(default, alias = "severity")]
+    level: DiagnosticLevel,
+    message: String,
+    rule: Option<String>,
+}
+
+pub fn load(report: &Path, base_dir: &Path) -> Result<Vec<ExternalDiagnostic>, NullScriptError> {
+    let content = std::fs::read_to_string(report)?;
+    let (tool, findings) = match serde_json::from_str(&content).map_err(|e| invalid(report, e))? {
+        Report::Tool { tool, diagnostics } => (tool, diagnostics),
+        Report::List(findings) => (None, findings),
+    };
+
+    findings
+        .into_iter()
+        .map(|finding| {
+            if finding.line == 0 {
+                return Err(invalid(report, format!("{}: lines start at 1", finding.file.display())));
+            }
+
+            let label = match (&tool, &finding.rule) {
+                (Some(tool), Some(rule)) => Some(format!("{}/{}", tool, rule)),
+                (Some(label), None) | (None, Some(label)) => Some(label.clone()),
+                (None, None) => None,
+            };
+            let message = match label {
+                Some(label) => format!("{} [{}]", finding.message, label),
+                None => finding.message,
+            };
+
+            Ok(ExternalDiagnostic {
+                file: PathUtils::absolute(&base_dir.join(&finding.file)),
+                diagnostic: Diagnostic { line: finding.line, column: finding.column, message, level: finding.level },
+            })
+        })
+        .collect()
+}
+
+fn invalid(report: &Path, error: impl std::fmt::Display) -> NullScriptError {
+    NullScriptError::Io(std::io::Error::new(
+        std::io::ErrorKind::InvalidData,
+        format!("invalid diagnostics report {}: {}", report.display(), error),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_load_reads_tool_reports_and_plain_lists() {
+        let dir = tempfile::tempdir().unwrap();
+        let report = dir.path().join("contracts.json");
+        std::fs::write(
+            &report,
+            r#"{ "tool": "contracts", "diagnostics": [
+                { "file": "src/api.ns", "line": 4, "column": 2, "severity": "error", "message": "missing field 'id'", "rule": "shape" }
+            ] }"#,
+        )
+        .unwrap();
+
+        let loaded = load(&report, dir.path()).unwrap();
+        assert_eq!(loaded[0].file, PathUtils::absolute(&dir.path().join("src/api.ns")));
+        assert_eq!(loaded[0].diagnostic.message, "missing field 'id' [contracts/shape]");
+        assert_eq!(loaded[0].diagnostic.level, DiagnosticLevel::Error);
+
+        std::fs::write(&report, r#"[{ "file": "a.ns", "line": 1, "message": "todo" }]"#).unwrap();
+        let loaded = load(&report, dir.path()).unwrap();
+        assert_eq!((loaded[0].diagnostic.column, loaded[0].diagnostic.level), (None, DiagnosticLevel::Warn));
+        assert_eq!(loaded[0].diagnostic.message, "todo");
+
+        std::fs::write(&report, r#"[{ "file": "a.ns", "line": 0, "message": "todo" }]"#).unwrap();
+        assert!(load(&report, dir.path()).is_err());
+    }
+}
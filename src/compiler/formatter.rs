@@ -0,0 +1,150 @@
+use crate::compiler::lexer::{Lexer, Token, TokenKind};
+use crate::core::keywords::KEYWORDS;
+use crate::core::NullScriptError;
+use std::path::Path;
+
+const INDENT: &str = "    ";
+
+static CONTINUATION_KEYWORDS: &[&str] = &["otherwise", "grab", "atLast"];
+
+static BLOCK_KEYWORDS: &[&str] = &["otherwise", "test", "grab", "atLast"];
+
+static CASE_KEYWORDS: &[&str] = &["case", "done"];
+
+struct Opener {
+    indent: usize,
+    in_case: bool,
+}
+
+pub fn format(source: &str, file_path: Option<&Path>) -> Result<String, NullScriptError> {
+    let (shebang, body) = match source.strip_prefix("#!") {
+        Some(_) => source.split_at(source.find('\n').map_or(source.len(), |newline| newline + 1)),
+        None => ("", source),
+    };
+    let newline = if source.contains("\r\n") { "\r\n" } else { "\n" };
+
+    let tokens = Lexer::new(body).with_file(file_path).tokenize()?;
+    let mut formatted = String::with_capacity(source.len());
+    let mut openers: Vec<Opener> = Vec::new();
+    let mut indent = 0;
+    let mut line_keyword: Option<&str> = None;
+
+    for (index, token) in tokens.iter().enumerate() {
+        let previous = index.checked_sub(1).map(|previous| &tokens[previous]);
+        let next = tokens.get(index + 1);
+
+        match (token.kind, token.text) {
+            (TokenKind::Whitespace, text) => {
+                let Some(next) = next.filter(|_| !formatted.is_empty()) else {
+                    continue;
+                };
+                let breaks = text.matches('\n').count();
+                if breaks == 0 || joins_line(previous, next, line_keyword) {
+                    formatted.push(' ');
+                    continue;
+                }
+
+                formatted.truncate(formatted.trim_end_matches([' ', '\t']).len());
+                formatted.push_str(&newline.repeat(breaks.min(2)));
+                indent = match (next.kind, next.text, openers.last()) {
+                    (_, _, None) => 0,
+                    (TokenKind::Punct, ")" | "]" | "}", Some(opener)) => opener.indent,
+                    (TokenKind::Identifier, word, Some(opener)) if CASE_KEYWORDS.contains(&word) => opener.indent + 1,
+                    (_, _, Some(opener)) => opener.indent + 1 + usize::from(opener.in_case),
+                };
+                formatted.push_str(&INDENT.repeat(indent));
+                line_keyword = None;
+            }
+            (TokenKind::Punct, "(" | "[" | "{") => {
+                openers.push(Opener { indent, in_case: false });
+                formatted.push_str(token.text);
+            }
+            (TokenKind::Punct, ")" | "]" | "}") => {
+                openers.pop();
+                formatted.push_str(token.text);
+            }
+            (TokenKind::Identifier, word) if is_binary_operator(&tokens, index) => {
+                if !formatted.is_empty() && !formatted.ends_with([' ', '\n']) {
+                    formatted.push(' ');
+                }
+                formatted.push_str(word);
+                if next.is_some_and(|next| next.kind != TokenKind::Whitespace) {
+                    formatted.push(' ');
+                }
+            }
+            (kind, text) => {
+                if kind == TokenKind::Identifier && line_keyword.is_none() {
+                    line_keyword = Some(text);
+                    if let Some(opener) = openers.last_mut().filter(|_| CASE_KEYWORDS.contains(&text)) {
+                        opener.in_case = true;
+                    }
+                }
+                formatted.push_str(text);
+            }
+        }
+    }
+
+    formatted.truncate(formatted.trim_end().len());
+    if !formatted.is_empty() {
+        formatted.push_str(newline);
+    }
+
+    Ok(format!("{}{}", shebang, formatted))
+}
+
+fn joins_line(previous: Option<&Token>, next: &Token, line_keyword: Option<&str>) -> bool {
+    let Some(previous) = previous else {
+        return false;
+    };
+
+    let opens_block = next.kind == TokenKind::Punct
+        && next.text == "{"
+        && match previous.kind {
+            TokenKind::Punct => previous.text == ")",
+            TokenKind::Identifier => BLOCK_KEYWORDS.contains(&previous.text) || line_keyword == Some("model"),
+            _ => false,
+        };
+    let continues_block = previous.kind == TokenKind::Punct
+        && previous.text == "}"
+        && next.kind == TokenKind::Identifier
+        && CONTINUATION_KEYWORDS.contains(&next.text);
+
+    opens_block || continues_block
+}
+
+fn is_binary_operator(tokens: &[Token], index: usize) -> bool {
+    let word = tokens[index].text;
+    let is_operator = KEYWORDS
+        .iter()
+        .any(|(keyword, js)| *keyword == word && js.chars().all(|c| "=!<>&|".contains(c)) && *js != "!");
+    if !is_operator {
+        return false;
+    }
+
+    let previous = tokens[..index].iter().rev().find(|token| !token.is_trivia());
+    let next = tokens[index + 1..].iter().find(|token| !token.is_trivia());
+    previous.is_some_and(|previous| previous.text != ".") && next.is_some_and(|next| next.text != ":")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_reindents_spaces_operators_and_joins_braces() {
+        let source = "run check(a,b)\n{\n  whatever (a   is(b)) {\n return yes;\n      }\n  otherwise {\n\n\n    speak.say(`keep   ${a}\n  as is`);   \n }\n}";
+        let formatted = format(source, None).unwrap();
+
+        assert_eq!(
+            formatted,
+            "run check(a,b) {\n    whatever (a is (b)) {\n        return yes;\n    } otherwise {\n\n        speak.say(`keep   ${a}\n  as is`);\n    }\n}\n"
+        );
+        assert_eq!(format(&formatted, None).unwrap(), formatted);
+
+        let switch = "switch (day) {\ncase 0:\nreturn \"Sunday\";\ndone:\nstop;\n}\n";
+        assert_eq!(
+            format(switch, None).unwrap(),
+            "switch (day) {\n    case 0:\n        return \"Sunday\";\n    done:\n        stop;\n}\n"
+        );
+    }
+}
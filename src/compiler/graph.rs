@@ -0,0 +1,414 @@
+use crate::compiler::resolver::{ImportTarget, ModuleGraph};
+use serde_json::json;
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::PathBuf;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GraphFormat {
+    Dot,
+    Mermaid,
+    Json,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct GraphOptions {
+    pub cluster: bool,
+    pub highlight_cycles: bool,
+    pub include_external: bool,
+    pub entries: Vec<PathBuf>,
+    pub max_depth: Option<usize>,
+}
+
+struct GraphView {
+    nodes: Vec<ViewNode>,
+    edges: Vec<ViewEdge>,
+    cycles: Vec<Vec<String>>,
+}
+
+struct ViewNode {
+    id: String,
+    directory: String,
+    depth: Option<usize>,
+    external: bool,
+    in_cycle: bool,
+}
+
+struct ViewEdge {
+    from: String,
+    to: String,
+    specifier: String,
+    line: u32,
+    column: u32,
+    dynamic: bool,
+    cycle: bool,
+}
+
+pub fn render(graph: &ModuleGraph, format: GraphFormat, options: &GraphOptions) -> String {
+    let view = build_view(graph, options);
+
+    match format {
+        GraphFormat::Dot => render_dot(&view, options),
+        GraphFormat::Mermaid => render_mermaid(&view, options),
+        GraphFormat::Json => render_json(&view),
+    }
+}
+
+fn build_view(graph: &ModuleGraph, options: &GraphOptions) -> GraphView {
+    let depths: Option<BTreeMap<PathBuf, usize>> = if options.entries.is_empty() {
+        None
+    } else {
+        Some(graph.reachable_from(&options.entries, options.max_depth))
+    };
+
+    let selected: Vec<&PathBuf> = graph
+        .modules
+        .keys()
+        .filter(|path| depths.as_ref().is_none_or(|d| d.contains_key(*path)))
+        .collect();
+    let selected_set: BTreeSet<&PathBuf> = selected.iter().copied().collect();
+
+    let cycles = graph.cycles();
+    let cycle_members = graph.cycle_members();
+    let component_of: BTreeMap<&PathBuf, usize> = cycles
+        .iter()
+        .enumerate()
+        .flat_map(|(i, members)| members.iter().map(move |m| (m, i)))
+        .collect();
+
+    let mut nodes: Vec<ViewNode> = selected
+        .iter()
+        .map(|path| {
+            let id = graph.display_path(path);
+            ViewNode {
+                directory: id.rsplit_once('/').map(|(dir, _)| dir.to_string()).unwrap_or_default(),
+                id,
+                depth: depths.as_ref().and_then(|d| d.get(*path).copied()),
+                external: false,
+                in_cycle: cycle_members.contains(*path),
+            }
+        })
+        .collect();
+
+    let mut edges = Vec::new();
+    let mut externals = BTreeSet::new();
+
+    for path in &selected {
+        let node = &graph.modules[*path];
+        let expand = match (&depths, options.max_depth) {
+            (Some(d), Some(max)) => d.get(*path).is_some_and(|depth| *depth < max),
+            _ => true,
+        };
+        if !expand {
+            continue;
+        }
+
+        for import in &node.imports {
+            let (to, cycle) = match &import.target {
+                ImportTarget::Local(target) if selected_set.contains(target) => {
+                    let same_cycle = component_of.contains_key(*path)
+                        && component_of.get(*path) == component_of.get(target);
+                    (graph.display_path(target), same_cycle)
+                }
+                ImportTarget::External(name) if options.include_external => {
+                    externals.insert(name.clone());
+                    (name.clone(), false)
+                }
+                _ => continue,
+            };
+
+            edges.push(ViewEdge {
+                from: graph.display_path(path),
+                to,
+                specifier: import.specifier.clone(),
+                line: import.line,
+                column: import.column,
+                dynamic: import.dynamic,
+                cycle,
+            });
+        }
+    }
+
+    for name in externals {
+        nodes.push(ViewNode {
+            id: name,
+            directory: String::new(),
+            depth: None,
+            external: true,
+            in_cycle: false,
+        });
+    }
+
+    let cycles = cycles
+        .iter()
+        .map(|members| members.iter().map(|m| graph.display_path(m)).collect())
+        .collect();
+
+    GraphView { nodes, edges, cycles }
+}
+
+fn quote(text: &str) -> String {
+    format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+fn render_dot(view: &GraphView, options: &GraphOptions) -> String {
+    let mut output = String::from("digraph modules {\n    rankdir=LR;\n    node [shape=box, fontname=\"Helvetica\"];\n");
+
+    let node_line = |node: &ViewNode| {
+        let mut attributes = vec![format!("label={}", quote(&node.id))];
+        if node.external {
+            attributes.push("shape=ellipse".to_string());
+            attributes.push("style=dashed".to_string());
+        }
+        if options.highlight_cycles && node.in_cycle {
+            attributes.push("color=red".to_string());
+            attributes.push("penwidth=2".to_string());
+        }
+        format!("{} [{}];", quote(&node.id), attributes.join(", "))
+    };
+
+    if options.cluster {
+        let mut clusters: BTreeMap<&str, Vec<&ViewNode>> = BTreeMap::new();
+        for node in &view.nodes {
+            clusters.entry(node.directory.as_str()).or_default().push(node);
+        }
+
+        for (directory, nodes) in clusters {
+            if directory.is_empty() {
+                for node in nodes {
+                    output.push_str(&format!("    {}\n", node_line(node)));
+                }
+                continue;
+            }
+
+            output.push_str(&format!("    subgraph {} {{\n", quote(&format!("cluster_{}", directory))));
+            output.push_str(&format!("        label={};\n", quote(directory)));
+            for node in nodes {
+                output.push_str(&format!("        {}\n", node_line(node)));
+            }
+            output.push_str("    }\n");
+        }
+    } else {
+        for node in &view.nodes {
+            output.push_str(&format!("    {}\n", node_line(node)));
+        }
+    }
+
+    for edge in &view.edges {
+        let mut attributes = Vec::new();
+        if edge.dynamic {
+            attributes.push("style=dashed");
+        }
+        if options.highlight_cycles && edge.cycle {
+            attributes.extend(["color=red", "penwidth=2"]);
+        }
+        let style = if attributes.is_empty() { String::new() } else { format!(" [{}]", attributes.join(", ")) };
+        output.push_str(&format!("    {} -> {}{};\n", quote(&edge.from), quote(&edge.to), style));
+    }
+
+    output.push_str("}\n");
+    output
+}
+
+fn render_mermaid(view: &GraphView, options: &GraphOptions) -> String {
+    let ids: BTreeMap<&str, String> = view
+        .nodes
+        .iter()
+        .enumerate()
+        .map(|(i, node)| (node.id.as_str(), format!("n{}", i)))
+        .collect();
+
+    let node_line = |node: &ViewNode| {
+        let label = node.id.replace('"', "'");
+        if node.external {
+            format!("{}([\"{}\"])", ids[node.id.as_str()], label)
+        } else {
+            format!("{}[\"{}\"]", ids[node.id.as_str()], label)
+        }
+    };
+
+    let mut output = String::from("graph LR\n");
+
+    if options.cluster {
+        let mut clusters: BTreeMap<&str, Vec<&ViewNode>> = BTreeMap::new();
+        for node in &view.nodes {
+            clusters.entry(node.directory.as_str()).or_default().push(node);
+        }
+
+        for (i, (directory, nodes)) in clusters.into_iter().enumerate() {
+            if directory.is_empty() {
+                for node in nodes {
+                    output.push_str(&format!("    {}\n", node_line(node)));
+                }
+                continue;
+            }
+
+            output.push_str(&format!("    subgraph c{} [\"{}\"]\n", i, directory));
+            for node in nodes {
+                output.push_str(&format!("        {}\n", node_line(node)));
+            }
+            output.push_str("    end\n");
+        }
+    } else {
+        for node in &view.nodes {
+            output.push_str(&format!("    {}\n", node_line(node)));
+        }
+    }
+
+    let mut cycle_links = Vec::new();
+    for (i, edge) in view.edges.iter().enumerate() {
+        let arrow = if edge.dynamic { "-.->" } else { "-->" };
+        output.push_str(&format!("    {} {} {}\n", ids[edge.from.as_str()], arrow, ids[edge.to.as_str()]));
+        if edge.cycle {
+            cycle_links.push(i.to_string());
+        }
+    }
+
+    if options.highlight_cycles {
+        let cyclic: Vec<&str> = view
+            .nodes
+            .iter()
+            .filter(|n| n.in_cycle)
+            .map(|n| ids[n.id.as_str()].as_str())
+            .collect();
+        if !cyclic.is_empty() {
+            output.push_str("    classDef cycle stroke:#d00,stroke-width:2px\n");
+            output.push_str(&format!("    class {} cycle\n", cyclic.join(",")));
+        }
+        if !cycle_links.is_empty() {
+            output.push_str(&format!("    linkStyle {} stroke:#d00,stroke-width:2px\n", cycle_links.join(",")));
+        }
+    }
+
+    output
+}
+
+fn render_json(view: &GraphView) -> String {
+    let nodes: Vec<_> = view
+        .nodes
+        .iter()
+        .map(|node| {
+            json!({
+                "id": node.id,
+                "directory": node.directory,
+                "external": node.external,
+                "depth": node.depth,
+                "inCycle": node.in_cycle,
+            })
+        })
+        .collect();
+
+    let edges: Vec<_> = view
+        .edges
+        .iter()
+        .map(|edge| {
+            json!({
+                "from": edge.from,
+                "to": edge.to,
+                "specifier": edge.specifier,
+                "line": edge.line,
+                "column": edge.column,
+                "dynamic": edge.dynamic,
+                "cycle": edge.cycle,
+            })
+        })
+        .collect();
+
+    let document = json!({
+        "nodes": nodes,
+        "edges": edges,
+        "cycles": view.cycles,
+    });
+
+    serde_json::to_string_pretty(&document).unwrap_or_default() + "\n"
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compiler::resolver::ResolveOptions;
+
+    fn project() -> (tempfile::TempDir, ModuleGraph) {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir(dir.path().join("lib")).unwrap();
+        std::fs::write(dir.path().join("main.ns"), "use { a } from \"./lib/a\";\n").unwrap();
+        std::fs::write(dir.path().join("lib/a.ns"), "use { b } from \"./b\";\n").unwrap();
+        std::fs::write(dir.path().join("lib/b.ns"), "use { a } from \"./a\";\nuse fs from \"fs\";\n").unwrap();
+
+        let graph = ModuleGraph::build(dir.path(), &ResolveOptions::default()).unwrap();
+        (dir, graph)
+    }
+
+    #[test]
+    fn test_render_dot() {
+        let (_dir, graph) = project();
+        let options = GraphOptions { cluster: true, highlight_cycles: true, include_external: true, ..GraphOptions::default() };
+
+        assert_eq!(
+            render(&graph, GraphFormat::Dot, &options),
+            "digraph modules {\n    rankdir=LR;\n    node [shape=box, fontname=\"Helvetica\"];\n\
+             \x20   \"main.ns\" [label=\"main.ns\"];\n\
+             \x20   \"fs\" [label=\"fs\", shape=ellipse, style=dashed];\n\
+             \x20   subgraph \"cluster_lib\" {\n\
+             \x20       label=\"lib\";\n\
+             \x20       \"lib/a.ns\" [label=\"lib/a.ns\", color=red, penwidth=2];\n\
+             \x20       \"lib/b.ns\" [label=\"lib/b.ns\", color=red, penwidth=2];\n\
+             \x20   }\n\
+             \x20   \"lib/a.ns\" -> \"lib/b.ns\" [color=red, penwidth=2];\n\
+             \x20   \"lib/b.ns\" -> \"lib/a.ns\" [color=red, penwidth=2];\n\
+             \x20   \"lib/b.ns\" -> \"fs\";\n\
+             \x20   \"main.ns\" -> \"lib/a.ns\";\n\
+             }\n"
+        );
+
+        let plain = render(&graph, GraphFormat::Dot, &GraphOptions::default());
+        assert!(!plain.contains("subgraph") && !plain.contains("color=red") && !plain.contains("\"fs\""));
+    }
+
+    #[test]
+    fn test_render_mermaid() {
+        let (_dir, graph) = project();
+        let options = GraphOptions { cluster: true, highlight_cycles: true, include_external: true, ..GraphOptions::default() };
+
+        assert_eq!(
+            render(&graph, GraphFormat::Mermaid, &options),
+            "graph LR\n\
+             \x20   n2[\"main.ns\"]\n\
+             \x20   n3([\"fs\"])\n\
+             \x20   subgraph c1 [\"lib\"]\n\
+             \x20       n0[\"lib/a.ns\"]\n\
+             \x20       n1[\"lib/b.ns\"]\n\
+             \x20   end\n\
+             \x20   n0 --> n1\n\
+             \x20   n1 --> n0\n\
+             \x20   n1 --> n3\n\
+             \x20   n2 --> n0\n\
+             \x20   classDef cycle stroke:#d00,stroke-width:2px\n\
+             \x20   class n0,n1 cycle\n\
+             \x20   linkStyle 0,1 stroke:#d00,stroke-width:2px\n"
+        );
+    }
+
+    #[test]
+    fn test_render_json() {
+        let (dir, graph) = project();
+        let document: serde_json::Value = serde_json::from_str(&render(&graph, GraphFormat::Json, &GraphOptions::default())).unwrap();
+
+        assert_eq!(document["cycles"], json!([["lib/a.ns", "lib/b.ns"]]));
+        assert_eq!(document["nodes"].as_array().unwrap().len(), 3);
+        assert_eq!(
+            document["edges"][2],
+            json!({ "from": "main.ns", "to": "lib/a.ns", "specifier": "./lib/a", "line": 1, "column": 16, "dynamic": false, "cycle": false })
+        );
+
+        let options = GraphOptions { entries: vec![dir.path().join("main.ns")], max_depth: Some(1), ..GraphOptions::default() };
+        let document: serde_json::Value = serde_json::from_str(&render(&graph, GraphFormat::Json, &options)).unwrap();
+        let nodes: Vec<(&str, u64)> = document["nodes"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|n| (n["id"].as_str().unwrap(), n["depth"].as_u64().unwrap()))
+            .collect();
+        assert_eq!(nodes, vec![("lib/a.ns", 1), ("main.ns", 0)]);
+        assert_eq!(document["edges"].as_array().unwrap().len(), 1);
+    }
+}
@@ -0,0 +1,397 @@
+use crate::core::{NullScriptError, NullScriptSyntaxError};
+use crate::core::types::{Location, WithLocation};
+use std::path::Path;
+
+pub const DEFAULT_MAX_NESTING_DEPTH: usize = 256;
+
+static REGEX_PRECEDING_WORDS: &[&str] = &[
+    "return", "typeof", "instanceof", "in", "of", "new", "delete", "void", "throw",
+    "case", "do", "else", "yield", "await",
+    "what", "kind", "inside", "part", "fresh", "remove", "nothing", "trigger",
+    "otherwise", "pause", "hold",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TokenKind {
+    Identifier,
+    Number,
+    String,
+    Template,
+    Regex,
+    LineComment,
+    BlockComment,
+    Punct,
+    Whitespace,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Token<'a> {
+    pub kind: TokenKind,
+    pub text: &'a str,
+    pub start: usize,
+    pub line: u32,
+    pub column: u32,
+}
+
+impl Token<'_> {
+    pub fn is_trivia(&self) -> bool {
+        matches!(
+            self.kind,
+            TokenKind::Whitespace | TokenKind::LineComment | TokenKind::BlockComment
+        )
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Frame {
+    Paren,
+    Bracket,
+    Brace,
+    TemplateExpr,
+}
+
+pub struct Lexer<'a> {
+    source: &'a str,
+    file_path: Option<&'a Path>,
+    max_depth: usize,
+    pos: usize,
+    line: u32,
+    column: u32,
+    stack: Vec<Frame>,
+    tokens: Vec<Token<'a>>,
+}
+
+impl<'a> Lexer<'a> {
+    pub fn new(source: &'a str) -> Self {
+        Self {
+            source,
+            file_path: None,
+            max_depth: DEFAULT_MAX_NESTING_DEPTH,
+            pos: 0,
+            line: 1,
+            column: 1,
+            stack: Vec::new(),
+            tokens: Vec::new(),
+        }
+    }
+
+    pub fn with_max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+
+    pub fn with_file(mut self, file_path: Option<&'a Path>) -> Self {
+        self.file_path = file_path;
+        self
+    }
+
+    pub fn tokenize(mut self) -> Result<Vec<Token<'a>>, NullScriptError> {
+        while let Some(c) = self.peek(0) {
+            let start = self.pos;
+
+            match c {
+                c if c.is_whitespace() => {
+                    let end = self.scan_while(start, char::is_whitespace);
+                    self.push(TokenKind::Whitespace, end);
+                }
+                '/' if self.peek(1) == Some('/') => {
+                    let end = self.source[start..]
+                        .find('\n')
+                        .map_or(self.source.len(), |i| start + i);
+                    self.push(TokenKind::LineComment, end);
+                }
+                '/' if self.peek(1) == Some('*') => {
+                    let end = self.source[start + 2..]
+                        .find("*/")
+                        .map_or(self.source.len(), |i| start + 2 + i + 2);
+                    self.push(TokenKind::BlockComment, end);
+                }
+                '/' if self.regex_allowed() => match self.scan_regex(start) {
+                    Some(end) => self.push(TokenKind::Regex, end),
+                    None => self.push(TokenKind::Punct, start + 1),
+                },
+                '"' | '\'' => {
+                    let end = self.scan_string(start, c);
+                    self.push(TokenKind::String, end);
+                }
+                '`' => self.scan_template(start + 1)?,
+                c if is_identifier_start(c) => {
+                    let end = self.scan_while(start, is_identifier_part);
+                    self.push(TokenKind::Identifier, end);
+                }
+                c if c.is_ascii_digit() || (c == '.' && self.peek(1).is_some_and(|n| n.is_ascii_digit())) => {
+                    let end = self.scan_number(start);
+                    self.push(TokenKind::Number, end);
+                }
+                '(' | '[' | '{' => {
+                    self.push(TokenKind::Punct, start + 1);
+                    let frame = match c {
+                        '(' => Frame::Paren,
+                        '[' => Frame::Bracket,
+                        _ => Frame::Brace,
+                    };
+                    self.open(frame)?;
+                }
+                '}' if self.stack.last() == Some(&Frame::TemplateExpr) => {
+                    self.stack.pop();
+                    self.scan_template(start + 1)?;
+                }
+                ')' | ']' | '}' => {
+                    let expected = match c {
+                        ')' => Frame::Paren,
+                        ']' => Frame::Bracket,
+                        _ => Frame::Brace,
+                    };
+                    if self.stack.last() == Some(&expected) {
+                        self.stack.pop();
+                    }
+                    self.push(TokenKind::Punct, start + 1);
+                }
+                c => self.push(TokenKind::Punct, start + c.len_utf8()),
+            }
+        }
+
+        Ok(self.tokens)
+    }
+
+    fn peek(&self, offset: usize) -> Option<char> {
+        self.source[self.pos..].chars().nth(offset)
+    }
+
+    fn push(&mut self, kind: TokenKind, end: usize) {
+        let text = &self.source[self.pos..end];
+        self.tokens.push(Token {
+            kind,
+            text,
+            start: self.pos,
+            line: self.line,
+            column: self.column,
+        });
+
+        for c in text.chars() {
+            if c == '\n' {
+                self.line += 1;
+                self.column = 1;
+            } else {
+                self.column += 1;
+            }
+        }
+
+        self.pos = end;
+    }
+
+    fn open(&mut self, frame: Frame) -> Result<(), NullScriptError> {
+        self.stack.push(frame);
+
+        if self.stack.len() > self.max_depth {
+            let opener = self.tokens.last().copied();
+            let message = format!(
+                "Nesting too deep: more than {} levels of brackets or template expressions.\n💡 Split this expression into smaller pieces or raise the limit with --max-nesting-depth.",
+                self.max_depth
+            );
+            let location = Location::new(
+                self.file_path.map(|p| p.to_path_buf()),
+                opener.map(|t| t.line),
+                opener.map(|t| t.column),
+            );
+            return Err(NullScriptError::Syntax(
+                NullScriptSyntaxError::with_location(message, location)
+            ));
+        }
+
+        Ok(())
+    }
+
+    fn scan_while(&self, start: usize, predicate: fn(char) -> bool) -> usize {
+        self.source[start..]
+            .char_indices()
+            .find(|(_, c)| !predicate(*c))
+            .map_or(self.source.len(), |(i, _)| start + i)
+    }
+
+    fn scan_string(&self, start: usize, quote: char) -> usize {
+        let mut chars = self.source[start + 1..].char_indices();
+
+        while let Some((i, c)) = chars.next() {
+            match c {
+                '\\' => {
+                    chars.next();
+                }
+                '\n' => return start + 1 + i,
+                c if c == quote => return start + 1 + i + 1,
+                _ => {}
+            }
+        }
+
+        self.source.len()
+    }
+
+    fn scan_number(&self, start: usize) -> usize {
+        let mut previous = '\0';
+
+        for (i, c) in self.source[start..].char_indices() {
+            let exponent_sign = (c == '+' || c == '-')
+                && (previous == 'e' || previous == 'E')
+                && !self.source[start..].starts_with("0x")
+                && !self.source[start..].starts_with("0X");
+
+            if !(c.is_ascii_alphanumeric() || c == '_' || c == '.' || exponent_sign) {
+                return start + i;
+            }
+
+            previous = c;
+        }
+
+        self.source.len()
+    }
+
+    fn scan_regex(&self, start: usize) -> Option<usize> {
+        let mut chars = self.source[start + 1..].char_indices();
+        let mut in_class = false;
+
+        while let Some((i, c)) = chars.next() {
+            match c {
+                '\\' => {
+                    chars.next();
+                }
+                '\n' => return None,
+                '[' => in_class = true,
+                ']' => in_class = false,
+                '/' if !in_class => {
+                    let body_end = start + 1 + i + 1;
+                    return Some(self.scan_while(body_end, |c| c.is_ascii_alphabetic()));
+                }
+                _ => {}
+            }
+        }
+
+        None
+    }
+
+    fn scan_template(&mut self, body_start: usize) -> Result<(), NullScriptError> {
+        let mut chars = self.source[body_start..].char_indices().peekable();
+
+        while let Some((i, c)) = chars.next() {
+            match c {
+                '\\' => {
+                    chars.next();
+                }
+                '`' => {
+                    self.push(TokenKind::Template, body_start + i + 1);
+                    return Ok(());
+                }
+                '$' if chars.peek().is_some_and(|(_, n)| *n == '{') => {
+                    self.push(TokenKind::Template, body_start + i + 2);
+                    return self.open(Frame::TemplateExpr);
+                }
+                _ => {}
+            }
+        }
+
+        self.push(TokenKind::Template, self.source.len());
+        Ok(())
+    }
+
+    fn regex_allowed(&self) -> bool {
+        let Some(previous) = self.tokens.iter().rev().find(|t| !t.is_trivia()) else {
+            return true;
+        };
+
+        match previous.kind {
+            TokenKind::Identifier => REGEX_PRECEDING_WORDS.contains(&previous.text),
+            TokenKind::Punct => !matches!(previous.text, ")" | "]"),
+            _ => false,
+        }
+    }
+}
+
+fn is_identifier_start(c: char) -> bool {
+    c.is_alphabetic() || c == '_' || c == '$'
+}
+
+fn is_identifier_part(c: char) -> bool {
+    c.is_alphanumeric() || c == '_' || c == '$'
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kinds(source: &str) -> Vec<(TokenKind, &str)> {
+        Lexer::new(source)
+            .tokenize()
+            .unwrap()
+            .into_iter()
+            .filter(|t| !t.is_trivia())
+            .map(|t| (t.kind, t.text))
+            .collect()
+    }
+
+    #[test]
+    fn test_strings_comments_and_templates() {
+        let tokens = kinds("fixed a = \"run later\"; // run\nlet b = `x ${run(1)} y`;");
+
+        assert!(tokens.contains(&(TokenKind::String, "\"run later\"")));
+        assert!(tokens.contains(&(TokenKind::Template, "`x ${")));
+        assert!(tokens.contains(&(TokenKind::Identifier, "run")));
+        assert!(tokens.contains(&(TokenKind::Template, "} y`")));
+        assert_eq!(tokens.iter().filter(|(k, _)| *k == TokenKind::Identifier).count(), 5);
+    }
+
+    #[test]
+    fn test_regex_versus_division() {
+        let tokens = kinds("let r = /a\\/b[/]/g; let d = x / 2 / y;");
+        assert!(tokens.contains(&(TokenKind::Regex, "/a\\/b[/]/g")));
+        assert_eq!(tokens.iter().filter(|t| t.1 == "/").count(), 2);
+    }
+
+    #[test]
+    fn test_positions_are_tracked() {
+        let tokens = Lexer::new("a\n  bé c").tokenize().unwrap();
+        let c = tokens.iter().find(|t| t.text == "c").unwrap();
+        assert_eq!((c.line, c.column), (2, 6));
+    }
+
+    #[test]
+    fn test_deep_nesting_reports_diagnostic() {
+        let parens = "(".repeat(100_000);
+        let result = Lexer::new(&parens).tokenize();
+        assert!(matches!(result, Err(NullScriptError::Syntax(ref e)) if e.message.contains("Nesting too deep")));
+
+        let mut templates = String::new();
+        for _ in 0..10_000 {
+            templates.push_str("`${");
+        }
+        let result = Lexer::new(&templates).with_max_depth(64).tokenize();
+        assert!(matches!(result, Err(NullScriptError::Syntax(ref e)) if e.location.line == Some(1)));
+
+        let shallow = format!("{}1{}", "[".repeat(64), "]".repeat(64));
+        assert!(Lexer::new(&shallow).with_max_depth(64).tokenize().is_ok());
+    }
+
+    #[test]
+    fn test_fuzz_pathological_inputs_are_lossless() {
+        let alphabet: Vec<char> = "`${}()[]/\\'\"\n*ab1 .+=é🎭".chars().collect();
+        let mut state: u64 = 0x9E37_79B9_7F4A_7C15;
+
+        for _ in 0..2000 {
+            let mut input = String::new();
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            let length = (state % 200) as usize;
+
+            for _ in 0..length {
+                state ^= state << 13;
+                state ^= state >> 7;
+                state ^= state << 17;
+                input.push(alphabet[(state % alphabet.len() as u64) as usize]);
+            }
+
+            if let Ok(tokens) = Lexer::new(&input).with_max_depth(32).tokenize() {
+                let rebuilt: String = tokens.iter().map(|t| t.text).collect();
+                assert_eq!(rebuilt, input);
+            }
+        }
+    }
+}
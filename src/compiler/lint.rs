@@ -0,0 +1,346 @@
+use crate::compiler::lexer::{Lexer, Token, TokenKind};
+use crate::compiler::syntax_rules::{self, CONTROL_KEYWORDS};
+use crate::core::config::DiagnosticLevel;
+use crate::core::keywords::{KeywordCategory, CATEGORIZED_KEYWORDS, GLOBAL_ALIASES, KEYWORDS};
+use crate::core::NullScriptError;
+use std::collections::BTreeMap;
+
+pub struct LintRule {
+    pub name: &'static str,
+    pub description: &'static str,
+}
+
+pub static LINT_RULES: &[LintRule] = &[
+    LintRule {
+        name: "unused-variable",
+        description: "A variable is declared but never read",
+    },
+    LintRule {
+        name: "shadowed-keyword",
+        description: "A declaration reuses the JavaScript name a NullScript keyword stands for, such as 'console' for 'speak'",
+    },
+    LintRule {
+        name: "prefer-fixed",
+        description: "A 'let' variable is never reassigned and can be 'fixed'",
+    },
+    LintRule {
+        name: "missing-hold",
+        description: "A call to a 'later' function is neither held nor chained, so its promise is dropped",
+    },
+];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LintFinding {
+    pub rule: &'static str,
+    pub line: u32,
+    pub column: u32,
+    pub message: String,
+}
+
+pub fn rule_levels(configured: &BTreeMap<String, DiagnosticLevel>) -> Result<BTreeMap<&'static str, DiagnosticLevel>, NullScriptError> {
+    if let Some(unknown) = configured.keys().find(|name| !LINT_RULES.iter().any(|rule| rule.name == *name)) {
+        return Err(NullScriptError::Config(format!(
+            "Unknown lint rule '{}' in nsconfig.json; expected one of {}",
+            unknown,
+            LINT_RULES.iter().map(|rule| rule.name).collect::<Vec<_>>().join(", ")
+        )));
+    }
+
+    Ok(LINT_RULES
+        .iter()
+        .map(|rule| (rule.name, configured.get(rule.name).copied().unwrap_or_default()))
+        .collect())
+}
+
+pub fn lint(source: &str) -> Result<Vec<LintFinding>, NullScriptError> {
+    let tokens: Vec<Token> = Lexer::new(source).with_max_depth(usize::MAX).tokenize()?;
+    let code: Vec<&Token> = tokens.iter().filter(|token| !token.is_trivia()).collect();
+    let text = |position: usize| code.get(position).map_or("", |token| token.text);
+    let member = |position: usize| position > 0 && text(position - 1) == ".";
+
+    let mut declarations = Vec::new();
+    let mut braces = Vec::new();
+    for (position, token) in code.iter().enumerate() {
+        match token.text {
+            "{" => braces.push(
+                (0..position)
+                    .rev()
+                    .take_while(|&before| !matches!(text(before), ";" | "{" | "}"))
+                    .any(|before| text(before) == "model"),
+            ),
+            "}" => {
+                braces.pop();
+            }
+            _ => {}
+        }
+        if token.kind != TokenKind::Identifier || member(position) || braces.last() == Some(&true) {
+            continue;
+        }
+        match token.text {
+            "let" | "fixed" | "var" if code.get(position + 1).is_some_and(|next| next.kind == TokenKind::Identifier) => {
+                declarations.push((position + 1, token.text));
+            }
+            "run" | "model" if code.get(position + 1).is_some_and(|next| next.kind == TokenKind::Identifier) => {
+                declarations.push((position + 1, token.text));
+            }
+            _ => {}
+        }
+    }
+    for (start, _) in syntax_rules::destructured_names(source)? {
+        if let Some(position) = code.iter().position(|token| token.start == start) {
+            declarations.push((position, "destructured"));
+        }
+    }
+    declarations.sort();
+
+    let enclosing = enclosing_brackets(&code);
+    let scopes: Vec<(usize, usize)> = declarations
+        .iter()
+        .map(|&(position, keyword)| declaring_scope(&code, &enclosing, position, keyword))
+        .collect();
+
+    let mut findings = Vec::new();
+    let later_functions = later_functions(&code);
+
+    for (index, &(position, keyword)) in declarations.iter().enumerate() {
+        let token = code[position];
+        let exported = keyword != "destructured" && position >= 2 && text(position - 2) == "share";
+
+        if let Some((nullscript, _)) = KEYWORDS.iter().find(|(nullscript, js)| *js == token.text && nullscript != js && stands_for_global(nullscript)) {
+            findings.push(LintFinding {
+                rule: "shadowed-keyword",
+                line: token.line,
+                column: token.column,
+                message: format!("'{}' shadows the global that '{}' stands for", token.text, nullscript),
+            });
+        }
+
+        if matches!(keyword, "run" | "model") || exported || token.text.starts_with('_') {
+            continue;
+        }
+
+        let scope = scopes[index];
+        let shadowed: Vec<(usize, usize)> = declarations
+            .iter()
+            .zip(&scopes)
+            .filter(|&(&(other, _), &inner)| {
+                other != position && code[other].text == token.text && inner != scope && scope.0 <= inner.0 && inner.1 <= scope.1
+            })
+            .map(|(_, &inner)| inner)
+            .collect();
+        let uses: Vec<usize> = (scope.0..scope.1.min(code.len()))
+            .filter(|&other| other != position && code[other].kind == TokenKind::Identifier && code[other].text == token.text && !member(other))
+            .filter(|&other| !property_key(&code, other))
+            .filter(|&other| !shadowed.iter().any(|&(start, end)| start <= other && other <= end))
+            .collect();
+        if uses.is_empty() {
+            findings.push(LintFinding {
+                rule: "unused-variable",
+                line: token.line,
+                column: token.column,
+                message: format!("'{}' is declared but never used", token.text),
+            });
+            continue;
+        }
+
+        if keyword == "let" && text(position + 1) == "=" && !uses.iter().any(|&other| reassigned(&code, other)) {
+            findings.push(LintFinding {
+                rule: "prefer-fixed",
+                line: token.line,
+                column: token.column,
+                message: format!("'{}' is never reassigned; declare it with 'fixed'", token.text),
+            });
+        }
+    }
+
+    for (position, token) in code.iter().enumerate() {
+        let statement_start = position == 0 || matches!(text(position - 1), ";" | "{" | "}") || code[position - 1].line < token.line;
+        if token.kind != TokenKind::Identifier
+            || !statement_start
+            || text(position + 1) != "("
+            || !(later_functions.contains(&token.text) || token.text == "pull")
+            || matches!(text(position.wrapping_sub(1)), "run" | "hold")
+        {
+            continue;
+        }
+
+        let close = closing_paren(&code, position + 1);
+        if matches!(text(close + 1), "." | "?") {
+            continue;
+        }
+        findings.push(LintFinding {
+            rule: "missing-hold",
+            line: token.line,
+            column: token.column,
+            message: format!("'{}' returns a promise that is never held; add 'hold' or handle it with .then()", token.text),
+        });
+    }
+
+    findings.sort_by_key(|finding| (finding.line, finding.column));
+    Ok(findings)
+}
+
+fn stands_for_global(nullscript: &str) -> bool {
+    GLOBAL_ALIASES.contains(&nullscript)
+        || CATEGORIZED_KEYWORDS
+            .iter()
+            .any(|(keyword, _, category)| *category == KeywordCategory::Functions && *keyword == nullscript)
+}
+
+fn later_functions<'a>(code: &[&Token<'a>]) -> Vec<&'a str> {
+    let mut names = Vec::new();
+    for (position, token) in code.iter().enumerate() {
+        if token.text != "later" {
+            continue;
+        }
+        match (code.get(position + 1), code.get(position + 2)) {
+            (Some(run), Some(name)) if run.text == "run" && name.kind == TokenKind::Identifier => names.push(name.text),
+            _ if position >= 2 && code[position - 1].text == "=" && code[position - 2].kind == TokenKind::Identifier => {
+                names.push(code[position - 2].text)
+            }
+            _ => {}
+        }
+    }
+    names
+}
+
+fn reassigned(code: &[&Token], position: usize) -> bool {
+    let mut after = String::new();
+    let mut end = None;
+    for token in &code[position + 1..] {
+        if token.kind != TokenKind::Punct || end.is_some_and(|end| token.start != end) || !"=+-*/%&|^<>!?".contains(token.text) {
+            break;
+        }
+        after.push_str(token.text);
+        end = Some(token.start + token.text.len());
+    }
+
+    let prefix = position >= 2 && matches!((code[position - 2].text, code[position - 1].text), ("+", "+") | ("-", "-"));
+    prefix
+        || matches!(after.as_str(), "=" | "++" | "--")
+        || (after.ends_with('=') && !matches!(after.as_str(), "==" | "===" | "!=" | "!==" | "<=" | ">="))
+}
+
+fn declaring_scope(code: &[&Token], enclosing: &[Option<usize>], position: usize, keyword: &str) -> (usize, usize) {
+    let text = |position: usize| code.get(position).map_or("", |token| token.text);
+    let anchor = match keyword {
+        "destructured" => (0..position)
+            .rev()
+            .find(|&before| matches!(text(before), "let" | "fixed" | "var") && closing_paren(code, before + 1) > position)
+            .unwrap_or(position),
+        _ => position,
+    };
+
+    let mut open = enclosing[anchor];
+    while let Some(bracket) = open {
+        let close = closing_paren(code, bracket);
+        match text(bracket) {
+            "{" if keyword != "var" || function_body(code, bracket) => return (bracket, close),
+            "(" if keyword != "var" && bracket > 0 && matches!(text(bracket - 1), "since" | "for") && text(close + 1) == "{" => {
+                return (bracket, closing_paren(code, close + 1));
+            }
+            _ => open = enclosing[bracket],
+        }
+    }
+    (0, code.len())
+}
+
+fn function_body(code: &[&Token], open: usize) -> bool {
+    if open >= 2 && code[open - 1].text == ">" && code[open - 2].text == "=" {
+        return true;
+    }
+    if open == 0 || code[open - 1].text != ")" {
+        return false;
+    }
+
+    let mut depth = 0;
+    for before in (0..open).rev() {
+        match code[before].text {
+            ")" | "]" | "}" => depth += 1,
+            "(" | "[" | "{" => {
+                depth -= 1;
+                if depth == 0 {
+                    return before > 0 && !CONTROL_KEYWORDS.contains(&code[before - 1].text);
+                }
+            }
+            _ => {}
+        }
+    }
+    false
+}
+
+fn enclosing_brackets(code: &[&Token]) -> Vec<Option<usize>> {
+    let mut stack = Vec::new();
+    let mut enclosing = Vec::with_capacity(code.len());
+
+    for (position, token) in code.iter().enumerate() {
+        if token.kind == TokenKind::Punct && matches!(token.text, ")" | "]" | "}") {
+            stack.pop();
+        }
+        enclosing.push(stack.last().copied());
+        if token.kind == TokenKind::Punct && matches!(token.text, "(" | "[" | "{") {
+            stack.push(position);
+        }
+    }
+
+    enclosing
+}
+
+fn property_key(code: &[&Token], position: usize) -> bool {
+    position > 0
+        && matches!(code[position - 1].text, "{" | ",")
+        && code.get(position + 1).is_some_and(|next| next.text == ":")
+}
+
+fn closing_paren(code: &[&Token], open: usize) -> usize {
+    let mut depth = 0;
+    for (position, token) in code.iter().enumerate().skip(open) {
+        match token.text {
+            "(" | "[" | "{" => depth += 1,
+            ")" | "]" | "}" => {
+                depth -= 1;
+                if depth == 0 {
+                    return position;
+                }
+            }
+            _ => {}
+        }
+    }
+    code.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lint_rules_report_locations() {
+        let source = "fixed console = 1;\nlet count = 0;\nlet total = 1;\ntotal += count;\nfixed { a, _b } = thing;\nlater run load() { return 1; }\nload();\nhold load();\nload().then(speak.say);\nshare fixed api = load;\nrun main(x) { return x; }\nmodel Box {\n\tfixed size;\n}\n";
+        let found: Vec<(&str, u32, u32)> = lint(source).unwrap().iter().map(|finding| (finding.rule, finding.line, finding.column)).collect();
+        assert_eq!(
+            found,
+            vec![
+                ("shadowed-keyword", 1, 7),
+                ("unused-variable", 1, 7),
+                ("prefer-fixed", 2, 5),
+                ("unused-variable", 5, 9),
+                ("missing-hold", 7, 1),
+            ]
+        );
+
+        let levels = rule_levels(&BTreeMap::from([("prefer-fixed".to_string(), DiagnosticLevel::Off)])).unwrap();
+        assert_eq!(levels["prefer-fixed"], DiagnosticLevel::Off);
+        assert_eq!(levels["missing-hold"], DiagnosticLevel::Warn);
+        assert!(matches!(
+            rule_levels(&BTreeMap::from([("no-such-rule".to_string(), DiagnosticLevel::Off)])),
+            Err(NullScriptError::Config(_))
+        ));
+    }
+
+    #[test]
+    fn test_uses_resolve_in_the_declaring_scope() {
+        let source = "whatever (ready) {\n    fixed label = 1;\n}\nspeak.say(label);\nfixed style = 2;\nfixed options = { style: 3 };\nspeak.say(options);\nfixed count = 0;\nrun show() {\n    fixed count = 1;\n    return count;\n}\nshow();\nsince (let step = 0; step less 3; step += 1) {\n    var total = step;\n}\nspeak.say(total);\n";
+        let found: Vec<(&str, u32)> = lint(source).unwrap().iter().map(|finding| (finding.rule, finding.line)).collect();
+        assert_eq!(found, vec![("unused-variable", 2), ("unused-variable", 5), ("unused-variable", 8)]);
+    }
+}
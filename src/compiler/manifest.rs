@@ -0,0 +1,79 @@
+use crate::compiler::options::{ModuleFormat, Target};
+use crate::core::keywords::{keyword_table_hash, KEYWORD_TABLE_VERSION};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+#[cfg(feature = "build")]
+use tokio::fs;
+
+pub const MANIFEST_FILE: &str = "nsc-manifest.json";
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct KeywordTableInfo {
+    pub version: u32,
+    pub hash: String,
+}
+
+impl KeywordTableInfo {
+    pub fn current() -> Self {
+        Self {
+            version: KEYWORD_TABLE_VERSION,
+            hash: keyword_table_hash(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ManifestEntry {
+    pub source: String,
+    pub output: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BuildManifest {
+    pub nsc_version: String,
+    pub keyword_table: KeywordTableInfo,
+    #[serde(default)]
+    pub target: Target,
+    #[serde(default)]
+    pub module: ModuleFormat,
+    pub files: Vec<ManifestEntry>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub names: BTreeMap<String, String>,
+}
+
+impl BuildManifest {
+    pub fn new() -> Self {
+        Self {
+            nsc_version: env!("CARGO_PKG_VERSION").to_string(),
+            keyword_table: KeywordTableInfo::current(),
+            target: Target::default(),
+            module: ModuleFormat::default(),
+            files: Vec::new(),
+            names: BTreeMap::new(),
+        }
+    }
+
+    pub fn path(out_dir: &Path) -> PathBuf {
+        out_dir.join(MANIFEST_FILE)
+    }
+
+    #[cfg(feature = "build")]
+    pub async fn load(out_dir: &Path) -> Option<Self> {
+        let content = fs::read_to_string(Self::path(out_dir)).await.ok()?;
+        serde_json::from_str(&content).ok()
+    }
+
+    pub fn is_current(&self) -> bool {
+        self.keyword_table == KeywordTableInfo::current()
+    }
+}
+
+impl Default for BuildManifest {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -1,3 +1,42 @@
+pub mod aliases;
+pub mod banner;
+#[cfg(feature = "build")]
+pub mod bundle;
+#[cfg(feature = "build")]
+pub mod cache;
+#[cfg(feature = "analyzer")]
+pub mod graph;
+#[cfg(feature = "build")]
+pub mod codegen;
+pub mod codemod;
+#[cfg(feature = "analyzer")]
+pub mod compdb;
+pub mod converter;
+#[cfg(feature = "build")]
+pub mod findings;
+#[cfg(feature = "analyzer")]
+pub mod depfile;
+pub mod docs;
+pub mod formatter;
+pub mod lexer;
+pub mod lint;
+pub mod manifest;
+pub mod naming;
+pub mod optimizer;
+pub mod options;
+pub mod passes;
+pub mod patterns;
+pub mod platform;
+pub mod progress;
+#[cfg(any(feature = "analyzer", feature = "build"))]
+pub mod resolver;
+pub mod runtime;
+#[cfg(feature = "build")]
+pub mod sink;
+pub mod sourcemap;
+pub mod syntax_rules;
 pub mod transpiler;
+pub mod typecheck;
+pub mod typos;
 
 pub use transpiler::*;
@@ -0,0 +1,207 @@
+use crate::compiler::patterns;
+use crate::core::NullScriptError;
+use crate::utils::paths::PathUtils;
+use regex::Captures;
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+
+const DEFAULT_HASH_LENGTH: usize = 8;
+
+static PLACEHOLDERS: &[&str] = &["name", "ext", "contenthash"];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileNames {
+    template: String,
+}
+
+pub struct Renamed {
+    pub files: BTreeMap<PathBuf, String>,
+    pub names: BTreeMap<PathBuf, PathBuf>,
+}
+
+impl FileNames {
+    pub fn parse(template: &str) -> Result<Self, NullScriptError> {
+        if template.is_empty() || template.contains(['/', '\\']) {
+            return Err(NullScriptError::Config(format!("file name template '{}' must be a plain file name", template)));
+        }
+
+        for placeholder in patterns::get("file_name_placeholder")?.captures_iter(template) {
+            let name = &placeholder[1];
+            let valid = PLACEHOLDERS.contains(&name)
+                && (placeholder.get(2).is_none() || name == "contenthash")
+                && placeholder.get(2).is_none_or(|length| (1..=64).contains(&length.as_str().parse::<usize>().unwrap_or(0)));
+            if !valid {
+                return Err(NullScriptError::Config(format!(
+                    "unknown placeholder '{}' in file name template, expected [name], [ext], [contenthash] or [contenthash:N]",
+                    &placeholder[0]
+                )));
+            }
+        }
+
+        Ok(Self { template: template.to_string() })
+    }
+
+    pub fn file_name(&self, name: &str, ext: &str, hash: &str) -> Result<String, NullScriptError> {
+        Ok(patterns::get("file_name_placeholder")?
+            .replace_all(&self.template, |placeholder: &Captures| match &placeholder[1] {
+                "name" => name.to_string(),
+                "ext" => ext.to_string(),
+                _ => {
+                    let length = placeholder.get(2).map_or(DEFAULT_HASH_LENGTH, |length| length.as_str().parse().unwrap_or(0));
+                    hash[..length.min(hash.len())].to_string()
+                }
+            })
+            .to_string())
+    }
+
+    pub fn rename(&self, files: BTreeMap<PathBuf, String>) -> Result<Renamed, NullScriptError> {
+        let specifiers = patterns::get("output_specifier")?;
+        let scripts: BTreeSet<PathBuf> = files.keys().filter(|path| is_script(path)).cloned().collect();
+        let mut own_hashes = BTreeMap::new();
+        let mut dependencies: BTreeMap<&PathBuf, Vec<PathBuf>> = BTreeMap::new();
+
+        for script in &scripts {
+            let contents = &files[script];
+            own_hashes.insert(script, hex(&Sha256::digest(without_map_comment(contents)?)));
+
+            let imported = specifiers
+                .captures_iter(contents)
+                .filter_map(|specifier| resolve(script, &specifier[3]))
+                .filter(|target| scripts.contains(target))
+                .collect();
+            dependencies.insert(script, imported);
+        }
+
+        let mut names = BTreeMap::new();
+        for script in &scripts {
+            let mut closure = BTreeSet::from([script.clone()]);
+            let mut pending = vec![script.clone()];
+            while let Some(current) = pending.pop() {
+                for dependency in &dependencies[&current] {
+                    if closure.insert(dependency.clone()) {
+                        pending.push(dependency.clone());
+                    }
+                }
+            }
+
+            let mut hasher = Sha256::new();
+            for member in &closure {
+                hasher.update(own_hashes[member].as_bytes());
+            }
+            let hash = hex(&hasher.finalize());
+
+            let stem = script.file_stem().map(|stem| stem.to_string_lossy()).unwrap_or_default();
+            names.insert(script.clone(), script.with_file_name(self.file_name(&stem, "js", &hash)?));
+        }
+
+        let mut renamed = BTreeMap::new();
+        for (path, contents) in files {
+            if let Some(new_path) = names.get(&path) {
+                let contents = rewrite_script(&path, new_path, &contents, &names)?;
+                renamed.insert(new_path.clone(), contents);
+            } else if let Some(new_path) = map_owner(&path).and_then(|script| names.get(&script)) {
+                let map_path = with_suffix(new_path, ".map");
+                renamed.insert(map_path, rewrite_map(&contents, new_path));
+            } else {
+                renamed.insert(path, contents);
+            }
+        }
+
+        Ok(Renamed { files: renamed, names })
+    }
+}
+
+fn rewrite_script(path: &Path, new_path: &Path, contents: &str, names: &BTreeMap<PathBuf, PathBuf>) -> Result<String, NullScriptError> {
+    let directory = new_path.parent().unwrap_or(Path::new(""));
+    let rewritten = patterns::get("output_specifier")?.replace_all(contents, |specifier: &Captures| {
+        let Some(target) = resolve(path, &specifier[3]).and_then(|target| names.get(&target)) else {
+            return specifier[0].to_string();
+        };
+        let relative = PathUtils::to_slash(&PathUtils::relative_path(target, directory));
+        let relative = if relative.starts_with("../") { relative } else { format!("./{}", relative) };
+        format!("{}{}{}{}", &specifier[1], &specifier[2], relative, &specifier[2])
+    });
+
+    let map_name = format!("{}.map", new_path.file_name().map(|name| name.to_string_lossy()).unwrap_or_default());
+    Ok(patterns::get("source_map_comment")?
+        .replace(&rewritten, format!("${{1}}{}", map_name).as_str())
+        .into_owned())
+}
+
+fn rewrite_map(contents: &str, new_script: &Path) -> String {
+    let Ok(mut map) = serde_json::from_str::<Value>(contents) else {
+        return contents.to_string();
+    };
+
+    if let Some(name) = new_script.file_name() {
+        map["file"] = Value::String(name.to_string_lossy().to_string());
+    }
+    serde_json::to_string(&map).unwrap_or_else(|_| contents.to_string())
+}
+
+fn resolve(from: &Path, specifier: &str) -> Option<PathBuf> {
+    let directory = from.parent()?;
+    Some(PathUtils::normalize(&directory.join(specifier)))
+}
+
+fn is_script(path: &Path) -> bool {
+    path.extension().is_some_and(|ext| ext == "js")
+}
+
+fn map_owner(path: &Path) -> Option<PathBuf> {
+    let name = path.file_name()?.to_string_lossy();
+    let script = name.strip_suffix(".map")?;
+    Some(path.with_file_name(script)).filter(|script| is_script(script))
+}
+
+fn with_suffix(path: &Path, suffix: &str) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(suffix);
+    PathBuf::from(name)
+}
+
+fn without_map_comment(contents: &str) -> Result<String, NullScriptError> {
+    Ok(patterns::get("source_map_comment")?.replace(contents, "").into_owned())
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rename_hashes_outputs_and_rewrites_imports() {
+        let names = FileNames::parse("[name].[contenthash:8].[ext]").unwrap();
+        assert!(matches!(FileNames::parse("[name].[hash].js"), Err(NullScriptError::Config(_))));
+
+        let files = BTreeMap::from([
+            (PathBuf::from("dist/main.js"), "import { a } from \"./lib/a.js\";\n//# sourceMappingURL=main.js.map\n".to_string()),
+            (PathBuf::from("dist/main.js.map"), r#"{"version":3,"file":"main.js"}"#.to_string()),
+            (PathBuf::from("dist/lib/a.js"), "export const a = 1;\n".to_string()),
+        ]);
+        let first = names.rename(files.clone()).unwrap();
+
+        let main = &first.names[Path::new("dist/main.js")];
+        let lib = &first.names[Path::new("dist/lib/a.js")];
+        assert!(lib.to_string_lossy().starts_with("dist/lib/a.") && lib.to_string_lossy().len() == "dist/lib/a.12345678.js".len());
+
+        let lib_name = lib.file_name().unwrap().to_string_lossy();
+        let main_name = main.file_name().unwrap().to_string_lossy();
+        assert_eq!(
+            first.files[main],
+            format!("import {{ a }} from \"./lib/{}\";\n//# sourceMappingURL={}.map\n", lib_name, main_name)
+        );
+        assert!(first.files[&with_suffix(main, ".map")].contains(&format!("\"file\":\"{}\"", main_name)));
+
+        let mut changed = files;
+        changed.insert(PathBuf::from("dist/lib/a.js"), "export const a = 2;\n".to_string());
+        let second = names.rename(changed).unwrap();
+        assert_ne!(&second.names[Path::new("dist/main.js")], main);
+        assert_ne!(&second.names[Path::new("dist/lib/a.js")], lib);
+    }
+}
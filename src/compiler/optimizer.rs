@@ -0,0 +1,457 @@
+use crate::compiler::lexer::{Lexer, Token, TokenKind};
+use crate::compiler::options::{TranspileExtension, TranspileOptions};
+use crate::core::keywords::JS_RESERVED_WORDS;
+use crate::core::NullScriptError;
+use std::collections::{BTreeMap, BTreeSet};
+
+pub struct DeadBranches;
+pub struct ShortenLocals;
+
+const OBJECT_AFTER: &[&str] = &["=", "(", ",", "[", "?", "&", "|", "!", "return"];
+
+impl TranspileExtension for DeadBranches {
+    fn name(&self) -> &'static str {
+        "dead_branches"
+    }
+
+    fn enabled(&self, options: &TranspileOptions) -> bool {
+        options.minify
+    }
+
+    fn apply(&self, mut output: String, _options: &TranspileOptions) -> Result<String, NullScriptError> {
+        loop {
+            let tokens = Lexer::new(&output).with_max_depth(usize::MAX).tokenize()?;
+            let code: Vec<&Token> = tokens.iter().filter(|token| !token.is_trivia()).collect();
+            let text = |position: usize| code.get(position).map_or("", |token| token.text);
+
+            let mut edits = Vec::new();
+            let mut position = 0;
+            while position < code.len() {
+                let constant = matches!(text(position + 2), "true" | "false") && text(position + 3) == ")";
+                let Some(end) = if_end(&code, position).filter(|_| constant && (position == 0 || text(position - 1) != ".")) else {
+                    position += 1;
+                    continue;
+                };
+
+                let then_end = matching(&code, position + 4).unwrap_or(end);
+                let kept = match text(position + 2) {
+                    "true" => Some((position + 4, then_end)),
+                    _ if then_end < end => Some((then_end + 2, end)),
+                    _ => None,
+                };
+
+                let start = code[position].start;
+                let stop = end_of(code[end]);
+                let replacement = match kept {
+                    Some((first, last)) => format!(
+                        "{}{}{}",
+                        newlines(&output[start..code[first].start]),
+                        &output[code[first].start..end_of(code[last])],
+                        newlines(&output[end_of(code[last])..stop])
+                    ),
+                    None if position > 0 && !matches!(text(position - 1), ";" | "{" | "}") => format!("{{}}{}", newlines(&output[start..stop])),
+                    None => newlines(&output[start..stop]),
+                };
+                edits.push((start, stop, replacement));
+                position = end + 1;
+            }
+
+            if edits.is_empty() {
+                return Ok(output);
+            }
+            output = apply_edits(&output, edits);
+        }
+    }
+}
+
+impl TranspileExtension for ShortenLocals {
+    fn name(&self) -> &'static str {
+        "shorten_locals"
+    }
+
+    fn enabled(&self, options: &TranspileOptions) -> bool {
+        options.minify
+    }
+
+    fn apply(&self, output: String, _options: &TranspileOptions) -> Result<String, NullScriptError> {
+        let tokens = Lexer::new(&output).with_max_depth(usize::MAX).tokenize()?;
+        let code: Vec<&Token> = tokens.iter().filter(|token| !token.is_trivia()).collect();
+        let text = |position: usize| code.get(position).map_or("", |token| token.text);
+
+        let identifiers: BTreeSet<&str> = code.iter().filter(|token| token.kind == TokenKind::Identifier).map(|token| token.text).collect();
+        if identifiers.contains("eval") || identifiers.contains("with") {
+            return Ok(output);
+        }
+
+        let roles = roles(&code);
+        let functions = outermost_functions(&code);
+        let scopes = function_scopes(&code);
+        let enclosing = enclosing_brackets(&code);
+        let inside = |position: usize| functions.iter().any(|&(start, end)| (start..=end).contains(&position));
+
+        let mut excluded: BTreeSet<&str> = BTreeSet::new();
+        for (position, token) in code.iter().enumerate() {
+            if token.kind != TokenKind::Identifier {
+                continue;
+            }
+            if !inside(position) || matches!(roles[position], Role::Label | Role::Ambiguous) {
+                excluded.insert(token.text);
+            }
+        }
+
+        let mut edits = Vec::new();
+        for &(start, end) in &functions {
+            if (start..=end).any(|position| text(position) == "class") {
+                continue;
+            }
+
+            let mut declared: Vec<&str> = Vec::new();
+            let mut bound: BTreeMap<&str, Vec<Option<(usize, usize)>>> = BTreeMap::new();
+            for position in start..=end {
+                let token = code[position];
+                if token.kind != TokenKind::Identifier || roles[position] != Role::Reference || excluded.contains(token.text) {
+                    continue;
+                }
+                let innermost_function = || scopes.iter().copied().filter(|&(open, close)| (open..=close).contains(&position)).min_by_key(|&(open, close)| close - open);
+                let scope = match text(position - 1) {
+                    "let" | "const" => Some(block_scope(&code, &enclosing, position)),
+                    "var" => Some(innermost_function()),
+                    _ if parameter(&code, position) => Some(innermost_function()),
+                    _ => None,
+                };
+                if let Some(scope) = scope {
+                    bound.entry(token.text).or_default().push(scope);
+                    if !declared.contains(&token.text) {
+                        declared.push(token.text);
+                    }
+                }
+            }
+            declared.retain(|name| {
+                let scopes = &bound[name];
+                (start..=end).all(|position| {
+                    code[position].text != *name
+                        || code[position].kind != TokenKind::Identifier
+                        || !matches!(roles[position], Role::Reference | Role::Shorthand)
+                        || scopes.iter().any(|scope| scope.is_some_and(|(open, close)| (open..=close).contains(&position)))
+                })
+            });
+
+            let mut names = ShortNames::new(&identifiers);
+            let renames: BTreeMap<&str, String> = declared
+                .into_iter()
+                .filter_map(|name| {
+                    let short = names.next();
+                    (short.len() < name.len()).then_some((name, short))
+                })
+                .collect();
+
+            for (position, token) in code.iter().enumerate().take(end + 1).skip(start) {
+                let Some(short) = renames.get(token.text).filter(|_| token.kind == TokenKind::Identifier) else {
+                    continue;
+                };
+                match roles[position] {
+                    Role::Reference => edits.push((token.start, end_of(token), short.clone())),
+                    Role::Shorthand => edits.push((token.start, end_of(token), format!("{}: {}", token.text, short))),
+                    _ => {}
+                }
+            }
+        }
+
+        Ok(apply_edits(&output, edits))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Role {
+    Reference,
+    Property,
+    Shorthand,
+    Label,
+    Ambiguous,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Bracket {
+    Paren,
+    Object,
+    Block,
+}
+
+fn roles(code: &[&Token]) -> Vec<Role> {
+    let text = |position: usize| code.get(position).map_or("", |token| token.text);
+    let mut stack: Vec<Bracket> = Vec::new();
+    let mut roles = Vec::with_capacity(code.len());
+
+    for (position, token) in code.iter().enumerate() {
+        let previous = if position == 0 { "" } else { text(position - 1) };
+        let next = text(position + 1);
+        let innermost = stack.last().copied();
+
+        roles.push(match token.kind {
+            TokenKind::Identifier if previous == "." && !spread(code, position) => Role::Property,
+            TokenKind::Identifier if matches!(previous, "break" | "continue") => Role::Label,
+            TokenKind::Identifier if innermost == Some(Bracket::Object) && matches!(previous, "{" | ",") => match next {
+                ":" | "(" => Role::Property,
+                "," | "}" | "=" => Role::Shorthand,
+                _ => Role::Reference,
+            },
+            TokenKind::Identifier if innermost == Some(Bracket::Object) && matches!(previous, "get" | "set" | "async" | "*") && next == "(" => {
+                Role::Property
+            }
+            TokenKind::Identifier if innermost != Some(Bracket::Object) && matches!(previous, "" | "{" | ";" | "}") && next == ":" => Role::Label,
+            TokenKind::Identifier if innermost == Some(Bracket::Block) && previous == "{" && matches!(next, "}" | ",") => Role::Ambiguous,
+            _ => Role::Reference,
+        });
+
+        match token.text {
+            "(" | "[" => stack.push(Bracket::Paren),
+            "{" => {
+                let object = OBJECT_AFTER.contains(&previous) || (previous == ":" && innermost != Some(Bracket::Block) && innermost.is_some());
+                stack.push(if object { Bracket::Object } else { Bracket::Block });
+            }
+            ")" | "]" | "}" => {
+                stack.pop();
+            }
+            _ => {}
+        }
+    }
+
+    roles
+}
+
+fn outermost_functions(code: &[&Token]) -> Vec<(usize, usize)> {
+    let mut functions = Vec::new();
+    let mut position = 0;
+
+    while position < code.len() {
+        if code[position].text != "function" || (position > 0 && code[position - 1].text == ".") {
+            position += 1;
+            continue;
+        }
+
+        let mut open = position + 1;
+        if code.get(open).is_some_and(|token| token.text == "*") {
+            open += 1;
+        }
+        if code.get(open).is_some_and(|token| token.kind == TokenKind::Identifier) {
+            open += 1;
+        }
+        let body = code.get(open).filter(|token| token.text == "(").and_then(|_| matching(code, open)).map(|close| close + 1);
+        match body.filter(|&body| code.get(body).is_some_and(|token| token.text == "{")).and_then(|body| matching(code, body)) {
+            Some(end) => {
+                functions.push((open, end));
+                position = end + 1;
+            }
+            None => position = open,
+        }
+    }
+
+    functions
+}
+
+fn function_scopes(code: &[&Token]) -> Vec<(usize, usize)> {
+    let mut scopes = Vec::new();
+
+    for (position, token) in code.iter().enumerate() {
+        if token.text == "function" && (position == 0 || code[position - 1].text != ".") {
+            let open = (position + 1..code.len().min(position + 4)).find(|&open| code[open].text == "(");
+            let body = open.and_then(|open| matching(code, open)).map(|close| close + 1);
+            if let (Some(open), Some(end)) = (open, body.filter(|&body| code.get(body).is_some_and(|token| token.text == "{")).and_then(|body| matching(code, body))) {
+                scopes.push((open, end));
+            }
+        } else if token.text == "{" && position >= 2 && code[position - 1].text == ">" && code[position - 2].text == "=" {
+            if let Some(end) = matching(code, position) {
+                scopes.push((position, end));
+            }
+        }
+    }
+
+    scopes
+}
+
+fn enclosing_brackets(code: &[&Token]) -> Vec<Option<usize>> {
+    let mut stack = Vec::new();
+    let mut enclosing = Vec::with_capacity(code.len());
+
+    for (position, token) in code.iter().enumerate() {
+        if token.kind == TokenKind::Punct && matches!(token.text, ")" | "]" | "}") {
+            stack.pop();
+        }
+        enclosing.push(stack.last().copied());
+        if token.kind == TokenKind::Punct && matches!(token.text, "(" | "[" | "{") {
+            stack.push(position);
+        }
+    }
+
+    enclosing
+}
+
+fn block_scope(code: &[&Token], enclosing: &[Option<usize>], position: usize) -> Option<(usize, usize)> {
+    let open = enclosing[position]?;
+    match code[open].text {
+        "{" => Some((open, matching(code, open)?)),
+        "(" if open > 0 && code[open - 1].text == "for" => {
+            let close = matching(code, open)?;
+            match code.get(close + 1) {
+                Some(token) if token.text == "{" => Some((open, matching(code, close + 1)?)),
+                _ => block_scope(code, enclosing, open),
+            }
+        }
+        _ => None,
+    }
+}
+
+fn parameter(code: &[&Token], position: usize) -> bool {
+    let after = matches!(code[position - 1].text, "(" | ",") || spread(code, position);
+    if !after || !matches!(code.get(position + 1).map(|token| token.text), Some(")" | "," | "=")) {
+        return false;
+    }
+
+    let mut depth = 0;
+    for before in (0..position).rev() {
+        match code[before].text {
+            ")" | "]" | "}" => depth += 1,
+            "[" | "{" if depth == 0 => return false,
+            "(" | "[" | "{" => depth -= 1,
+            _ => {}
+        }
+        if depth < 0 {
+            let mut name = before;
+            if name > 0 && code[name - 1].kind == TokenKind::Identifier {
+                name -= 1;
+            }
+            if name > 0 && code[name - 1].text == "*" {
+                name -= 1;
+            }
+            return name > 0 && code[name - 1].text == "function";
+        }
+    }
+    false
+}
+
+fn spread(code: &[&Token], position: usize) -> bool {
+    position >= 3 && code[position - 3..position].iter().all(|token| token.text == ".")
+}
+
+fn if_end(code: &[&Token], position: usize) -> Option<usize> {
+    if code.get(position)?.text != "if" || code.get(position + 1)?.text != "(" {
+        return None;
+    }
+    let open = matching(code, position + 1)? + 1;
+    if code.get(open)?.text != "{" {
+        return None;
+    }
+    let close = matching(code, open)?;
+    if code.get(close + 1).map(|token| token.text) != Some("else") {
+        return Some(close);
+    }
+    match code.get(close + 2)?.text {
+        "{" => matching(code, close + 2),
+        "if" => if_end(code, close + 2),
+        _ => None,
+    }
+}
+
+fn matching(code: &[&Token], open: usize) -> Option<usize> {
+    let mut depth = 0;
+    for (position, token) in code.iter().enumerate().skip(open) {
+        if token.kind != TokenKind::Punct {
+            continue;
+        }
+        match token.text {
+            "(" | "[" | "{" => depth += 1,
+            ")" | "]" | "}" => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(position);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+struct ShortNames<'a> {
+    taken: &'a BTreeSet<&'a str>,
+    index: usize,
+}
+
+impl<'a> ShortNames<'a> {
+    fn new(taken: &'a BTreeSet<&'a str>) -> Self {
+        Self { taken, index: 0 }
+    }
+
+    fn next(&mut self) -> String {
+        const LETTERS: &[u8] = b"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ";
+        loop {
+            let mut index = self.index;
+            self.index += 1;
+
+            let mut name = String::new();
+            loop {
+                name.insert(0, LETTERS[index % LETTERS.len()] as char);
+                index /= LETTERS.len();
+                if index == 0 {
+                    break;
+                }
+                index -= 1;
+            }
+            if !self.taken.contains(name.as_str()) && !JS_RESERVED_WORDS.contains(&name.as_str()) {
+                return name;
+            }
+        }
+    }
+}
+
+fn end_of(token: &Token) -> usize {
+    token.start + token.text.len()
+}
+
+fn newlines(text: &str) -> String {
+    text.chars().filter(|c| *c == '\n').collect()
+}
+
+fn apply_edits(source: &str, mut edits: Vec<(usize, usize, String)>) -> String {
+    edits.sort_by_key(|(start, ..)| *start);
+    let mut result = String::with_capacity(source.len());
+    let mut copied = 0;
+    for (start, end, replacement) in edits {
+        result.push_str(&source[copied..start]);
+        result.push_str(&replacement);
+        copied = end;
+    }
+    result.push_str(&source[copied..]);
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dead_branches_keep_lines() {
+        let options = TranspileOptions::new().with_minify(true);
+        let source = "if (false) {\n    debug();\n} else {\n    run();\n}\nif (x) {\n    a();\n} else if (false) {\n    b();\n}\nif (true) {\n    c();\n}\n";
+        assert_eq!(
+            DeadBranches.apply(source.to_string(), &options).unwrap(),
+            "\n\n{\n    run();\n}\nif (x) {\n    a();\n} else {}\n\n\n{\n    c();\n}\n"
+        );
+    }
+
+    #[test]
+    fn test_shorten_locals_skips_properties_and_globals() {
+        let options = TranspileOptions::new().with_minify(true);
+        let source = "const total = 1;\nfunction area(width, height = 2, ...rest) {\n    const result = { width, height: height };\n    return result.width * height + total + rest.length;\n}\n";
+        assert_eq!(
+            ShortenLocals.apply(source.to_string(), &options).unwrap(),
+            "const total = 1;\nfunction area(a, b = 2, ...c) {\n    const d = { width: a, height: b };\n    return d.width * b + total + c.length;\n}\n"
+        );
+
+        let source = "function f() {\n    { const process = 1; }\n    for (let index = 0; index < 2; index++) { var count = index; }\n    return typeof process + count;\n}\n";
+        assert_eq!(
+            ShortenLocals.apply(source.to_string(), &options).unwrap(),
+            "function f() {\n    { const process = 1; }\n    for (let a = 0; a < 2; a++) { var b = a; }\n    return typeof process + b;\n}\n"
+        );
+    }
+}
@@ -0,0 +1,172 @@
+use crate::core::NullScriptError;
+use crate::core::config::{OperatorAliases, TopLevelAwaitPolicy};
+use crate::core::version::Version;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+pub const DEFAULT_DIALECT: &str = "nullscript";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Target {
+    Es2015,
+    Es2017,
+    Es2020,
+    Es2022,
+    #[default]
+    EsNext,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ModuleFormat {
+    #[default]
+    Esm,
+    Cjs,
+}
+
+impl Target {
+    pub fn parse(value: &str) -> Result<Self, String> {
+        match value.to_ascii_lowercase().as_str() {
+            "es2015" | "es6" => Ok(Target::Es2015),
+            "es2017" => Ok(Target::Es2017),
+            "es2020" => Ok(Target::Es2020),
+            "es2022" => Ok(Target::Es2022),
+            "esnext" => Ok(Target::EsNext),
+            other => Err(format!("unknown target '{}', expected es2015, es2017, es2020, es2022 or esnext", other)),
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Target::Es2015 => "es2015",
+            Target::Es2017 => "es2017",
+            Target::Es2020 => "es2020",
+            Target::Es2022 => "es2022",
+            Target::EsNext => "esnext",
+        }
+    }
+
+    pub fn min_node_version(&self) -> Version {
+        match self {
+            Target::Es2015 => Version::new(6, 0, 0),
+            Target::Es2017 => Version::new(8, 10, 0),
+            Target::Es2020 => Version::new(14, 0, 0),
+            Target::Es2022 => Version::new(16, 11, 0),
+            Target::EsNext => Version::new(18, 0, 0),
+        }
+    }
+}
+
+impl ModuleFormat {
+    pub fn parse(value: &str) -> Result<Self, String> {
+        match value.to_ascii_lowercase().as_str() {
+            "esm" | "es" | "module" => Ok(ModuleFormat::Esm),
+            "cjs" | "commonjs" => Ok(ModuleFormat::Cjs),
+            other => Err(format!("unknown module format '{}', expected esm or cjs", other)),
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ModuleFormat::Esm => "esm",
+            ModuleFormat::Cjs => "cjs",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct TranspileOptions {
+    pub target: Target,
+    pub module: ModuleFormat,
+    pub source_map: bool,
+    pub minify: bool,
+    pub strict_ns: bool,
+    pub dialect: Option<String>,
+    pub defines: BTreeMap<String, String>,
+    pub top_level_await: TopLevelAwaitPolicy,
+    pub runtime_shim: bool,
+    pub operator_aliases: OperatorAliases,
+}
+
+impl TranspileOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_target(mut self, target: Target) -> Self {
+        self.target = target;
+        self
+    }
+
+    pub fn with_module(mut self, module: ModuleFormat) -> Self {
+        self.module = module;
+        self
+    }
+
+    pub fn with_source_map(mut self, source_map: bool) -> Self {
+        self.source_map = source_map;
+        self
+    }
+
+    pub fn with_minify(mut self, minify: bool) -> Self {
+        self.minify = minify;
+        self
+    }
+
+    pub fn with_strict_ns(mut self, strict_ns: bool) -> Self {
+        self.strict_ns = strict_ns;
+        self
+    }
+
+    pub fn with_dialect(mut self, dialect: Option<String>) -> Self {
+        self.dialect = dialect;
+        self
+    }
+
+    pub fn with_define(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.defines.insert(name.into(), value.into());
+        self
+    }
+
+    pub fn with_top_level_await(mut self, top_level_await: TopLevelAwaitPolicy) -> Self {
+        self.top_level_await = top_level_await;
+        self
+    }
+
+    pub fn with_runtime_shim(mut self, runtime_shim: bool) -> Self {
+        self.runtime_shim = runtime_shim;
+        self
+    }
+
+    pub fn with_operator_aliases(mut self, operator_aliases: OperatorAliases) -> Self {
+        self.operator_aliases = operator_aliases;
+        self
+    }
+
+    pub fn allows_top_level_await(&self) -> bool {
+        self.module == ModuleFormat::Esm && self.target == Target::EsNext
+    }
+
+    pub fn dialect(&self) -> &str {
+        self.dialect.as_deref().unwrap_or(DEFAULT_DIALECT)
+    }
+
+    pub fn parse_define(value: &str) -> Result<(String, String), String> {
+        match value.split_once('=') {
+            Some((name, replacement)) if !name.trim().is_empty() => {
+                Ok((name.trim().to_string(), replacement.trim().to_string()))
+            }
+            None if !value.trim().is_empty() => Ok((value.trim().to_string(), "true".to_string())),
+            _ => Err(format!("invalid define '{}', expected NAME=VALUE", value)),
+        }
+    }
+}
+
+pub trait TranspileExtension: Send + Sync {
+    fn name(&self) -> &'static str;
+
+    fn enabled(&self, options: &TranspileOptions) -> bool;
+
+    fn apply(&self, output: String, options: &TranspileOptions) -> Result<String, NullScriptError>;
+}
@@ -0,0 +1,732 @@
+use crate::compiler::lexer::{Lexer, Token, TokenKind};
+use crate::compiler::optimizer::{DeadBranches, ShortenLocals};
+use crate::compiler::options::{ModuleFormat, Target, TranspileExtension, TranspileOptions};
+use crate::compiler::patterns;
+use crate::compiler::runtime::RUNTIME_SPECIFIER;
+use crate::core::{NullScriptError, NullScriptTranspileError};
+use crate::core::config::TopLevelAwaitPolicy;
+use crate::core::keywords::RUNTIME_SHIM_ALIASES;
+use crate::core::types::{Location, WithLocation};
+use regex::Captures;
+
+pub struct Defines;
+pub struct TargetSyntax;
+pub struct TopLevelAwait;
+pub struct Minify;
+pub struct CommonJs;
+pub struct RuntimeShim;
+
+pub static BUILTIN_EXTENSIONS: &[&dyn TranspileExtension] = &[&Defines, &TargetSyntax, &TopLevelAwait, &CommonJs, &RuntimeShim, &DeadBranches, &ShortenLocals, &Minify];
+
+static CONTROL_KEYWORDS: &[&str] = &["if", "for", "while", "switch", "catch", "with"];
+
+impl TranspileExtension for Defines {
+    fn name(&self) -> &'static str {
+        "defines"
+    }
+
+    fn enabled(&self, options: &TranspileOptions) -> bool {
+        !options.defines.is_empty()
+    }
+
+    fn apply(&self, output: String, options: &TranspileOptions) -> Result<String, NullScriptError> {
+        let tokens = Lexer::new(&output).with_max_depth(usize::MAX).tokenize()?;
+        let mut result = String::with_capacity(output.len());
+        let mut previous = "";
+
+        for token in &tokens {
+            match options.defines.get(token.text) {
+                Some(value) if token.kind == TokenKind::Identifier && previous != "." => result.push_str(value),
+                _ => result.push_str(token.text),
+            }
+
+            if !token.is_trivia() {
+                previous = token.text;
+            }
+        }
+
+        Ok(result)
+    }
+}
+
+impl TranspileExtension for TargetSyntax {
+    fn name(&self) -> &'static str {
+        "target_syntax"
+    }
+
+    fn enabled(&self, options: &TranspileOptions) -> bool {
+        options.target < Target::EsNext
+    }
+
+    fn apply(&self, output: String, options: &TranspileOptions) -> Result<String, NullScriptError> {
+        let output = if options.target < Target::Es2020 { bind_catch_parameters(&output)? } else { output };
+        let tokens: Vec<Token> = Lexer::new(&output)
+            .with_max_depth(usize::MAX)
+            .tokenize()?
+            .into_iter()
+            .filter(|token| !token.is_trivia())
+            .collect();
+
+        let Some((token, feature, needs)) = newer_syntax(&tokens).into_iter().find(|(_, _, needs)| *needs > options.target) else {
+            return Ok(output);
+        };
+
+        Err(NullScriptError::Transpile(NullScriptTranspileError::with_location(
+            format!(
+                "{} needs --target {} or later, but this build targets {}.\n💡 Rewrite it for {}, or raise \"target\" in nsconfig.json ({} runs on Node.js {}+).",
+                feature,
+                needs.as_str(),
+                options.target.as_str(),
+                options.target.as_str(),
+                needs.as_str(),
+                needs.min_node_version().major
+            ),
+            Location::new(None, Some(token.line), Some(token.column)),
+        )))
+    }
+}
+
+fn bind_catch_parameters(output: &str) -> Result<String, NullScriptError> {
+    let tokens: Vec<Token> = Lexer::new(output)
+        .with_max_depth(usize::MAX)
+        .tokenize()?
+        .into_iter()
+        .filter(|token| !token.is_trivia())
+        .collect();
+
+    let mut lowered = String::with_capacity(output.len());
+    let mut copied = 0;
+    for (index, token) in tokens.iter().enumerate() {
+        let bare = token.text == "catch"
+            && index > 0
+            && tokens[index - 1].text == "}"
+            && tokens.get(index + 1).is_some_and(|next| next.text == "{");
+        if bare {
+            let end = token.start + token.text.len();
+            lowered.push_str(&output[copied..end]);
+            lowered.push_str(" (_ignored)");
+            copied = end;
+        }
+    }
+
+    if copied == 0 {
+        return Ok(output.to_string());
+    }
+    lowered.push_str(&output[copied..]);
+    Ok(lowered)
+}
+
+#[cfg(feature = "build")]
+pub(crate) fn newer_literals(source: &str) -> Result<Vec<(u32, u32, &'static str, Target)>, NullScriptError> {
+    Ok(Lexer::new(source)
+        .with_max_depth(usize::MAX)
+        .tokenize()?
+        .into_iter()
+        .filter(|token| token.kind == TokenKind::Number)
+        .filter_map(|token| literal_feature(token.text).map(|(feature, needs)| (token.line, token.column, feature, needs)))
+        .collect())
+}
+
+fn literal_feature(text: &str) -> Option<(&'static str, Target)> {
+    if text.contains('_') {
+        Some(("Numeric separators", Target::Es2022))
+    } else if text.split('.').next().is_some_and(|literal| literal.ends_with('n')) {
+        Some(("BigInt literals", Target::Es2020))
+    } else {
+        None
+    }
+}
+
+fn newer_syntax<'a, 'b>(tokens: &'b [Token<'a>]) -> Vec<(&'b Token<'a>, &'static str, Target)> {
+    let glued = |index: usize, text: &str| {
+        text.chars().enumerate().all(|(offset, c)| {
+            tokens.get(index + offset).is_some_and(|token| {
+                token.text.len() == 1
+                    && token.text.starts_with(c)
+                    && (offset == 0 || tokens[index + offset - 1].start + 1 == token.start)
+            })
+        })
+    };
+    let text = |index: usize| tokens.get(index).map_or("", |token| token.text);
+
+    let mut found = Vec::new();
+    let mut braces: Vec<bool> = Vec::new();
+    let mut pending_class = false;
+
+    for (index, token) in tokens.iter().enumerate() {
+        let previous = index.checked_sub(1).map_or("", text);
+        let in_class_body = braces.last() == Some(&true);
+
+        let feature = match token.kind {
+            TokenKind::Number => literal_feature(token.text),
+            TokenKind::Identifier if previous == "." => None,
+            TokenKind::Identifier if token.text == "async" && (text(index + 1) == "*" || text(index + 1) == "function" && text(index + 2) == "*") => {
+                Some(("Async generators", Target::Es2020))
+            }
+            TokenKind::Identifier if token.text == "async" && matches!(text(index + 1), "function" | "(")
+                || token.text == "async" && tokens.get(index + 1).is_some_and(|next| next.kind == TokenKind::Identifier) && text(index + 2) == "=" =>
+            {
+                Some(("Async functions", Target::Es2017))
+            }
+            TokenKind::Identifier if token.text == "catch" && text(index + 1) == "{" => Some(("Optional catch binding", Target::Es2020)),
+            TokenKind::Identifier if token.text == "static" && in_class_body && text(index + 1) == "{" => {
+                Some(("Class static blocks", Target::Es2022))
+            }
+            TokenKind::Identifier | TokenKind::String
+                if in_class_body
+                    && matches!(previous, "{" | ";" | "}" | "static")
+                    && (text(index + 1) == ";" || text(index + 1) == "=" && !matches!(text(index + 2), "=" | ">")) =>
+            {
+                Some(("Class fields", Target::Es2022))
+            }
+            TokenKind::Punct if glued(index, "??=") || glued(index, "||=") || glued(index, "&&=") => {
+                Some(("Logical assignment", Target::Es2022))
+            }
+            TokenKind::Punct if glued(index, "??") && previous != "?" => Some(("Nullish coalescing (??)", Target::Es2020)),
+            TokenKind::Punct if glued(index, "?.") && !text(index + 2).starts_with(|c: char| c.is_ascii_digit()) => {
+                Some(("Optional chaining (?.)", Target::Es2020))
+            }
+            TokenKind::Punct if glued(index, "**") && previous != "*" => Some(("The ** operator", Target::Es2017)),
+            TokenKind::Punct
+                if token.text == "#"
+                    && tokens.get(index + 1).is_some_and(|next| next.kind == TokenKind::Identifier && next.start == token.start + 1) =>
+            {
+                Some(("Private class members (#name)", Target::Es2022))
+            }
+            _ => None,
+        };
+        if let Some((feature, needs)) = feature {
+            found.push((token, feature, needs));
+        }
+
+        match token.text {
+            "class" if token.kind == TokenKind::Identifier && previous != "." => pending_class = true,
+            "{" => braces.push(std::mem::take(&mut pending_class)),
+            "}" => {
+                braces.pop();
+            }
+            _ => {}
+        }
+    }
+
+    found
+}
+
+impl TranspileExtension for TopLevelAwait {
+    fn name(&self) -> &'static str {
+        "top_level_await"
+    }
+
+    fn enabled(&self, options: &TranspileOptions) -> bool {
+        !options.allows_top_level_await()
+    }
+
+    fn apply(&self, output: String, options: &TranspileOptions) -> Result<String, NullScriptError> {
+        let tokens: Vec<Token> = Lexer::new(&output)
+            .with_max_depth(usize::MAX)
+            .tokenize()?
+            .into_iter()
+            .filter(|token| !token.is_trivia())
+            .collect();
+        let Some(first) = top_level_awaits(&tokens).into_iter().next() else {
+            return Ok(output);
+        };
+
+        let unsupported = match (options.module, options.target) {
+            (ModuleFormat::Cjs, _) => "--module cjs".to_string(),
+            (_, target) => format!("--target {}", target.as_str()),
+        };
+        let location = |token: &Token| Location::new(None, Some(token.line), Some(token.column));
+
+        if options.top_level_await == TopLevelAwaitPolicy::Error {
+            return Err(NullScriptError::Transpile(NullScriptTranspileError::with_location(
+                format!(
+                    "Top-level 'hold' needs ES modules and --target {}, but this build uses {}.\n💡 Build with --module esm --target {}, or set \"topLevelAwait\": \"wrap\" in nsconfig.json to run the file inside an async function.",
+                    Target::EsNext.as_str(),
+                    unsupported,
+                    Target::EsNext.as_str()
+                ),
+                location(first),
+            )));
+        }
+
+        let export = tokens
+            .iter()
+            .enumerate()
+            .find(|(index, token)| token.text == "export" && (*index == 0 || tokens[index - 1].text != ".") && !reexports(&tokens, *index));
+        if let Some((_, export)) = export {
+            return Err(NullScriptError::Transpile(NullScriptTranspileError::with_location(
+                format!(
+                    "Top-level 'hold' cannot be wrapped in an async function because this file shares values, and {} does not support top-level await.\n💡 Move the 'hold' into a function, or build with --module esm --target {}.",
+                    unsupported,
+                    Target::EsNext.as_str()
+                ),
+                location(export),
+            )));
+        }
+
+        Ok(wrap_in_async_function(&output))
+    }
+}
+
+fn top_level_awaits<'a, 'b>(tokens: &'b [Token<'a>]) -> Vec<&'b Token<'a>> {
+    #[derive(PartialEq)]
+    enum Frame {
+        Function,
+        Block,
+        Group { function_head: bool },
+        ArrowBody,
+    }
+
+    let mut stack: Vec<Frame> = Vec::new();
+    let mut closed_function_head = false;
+    let mut awaits = Vec::new();
+
+    for (index, token) in tokens.iter().enumerate() {
+        let previous = index.checked_sub(1).map(|previous| &tokens[previous]);
+        let previous_text = previous.map_or("", |previous| previous.text);
+
+        if matches!(token.text, "," | ";" | ")" | "]" | "}") && stack.last() == Some(&Frame::ArrowBody) {
+            stack.pop();
+        }
+
+        match token.text {
+            "{" => {
+                let function = ends_arrow(tokens, index) || (previous_text == ")" && closed_function_head);
+                stack.push(if function { Frame::Function } else { Frame::Block });
+            }
+            "(" | "[" => {
+                let function_head = token.text == "("
+                    && previous.is_some_and(|previous| {
+                        previous.kind == TokenKind::Identifier && !CONTROL_KEYWORDS.contains(&previous.text)
+                    });
+                stack.push(Frame::Group { function_head });
+            }
+            ")" | "]" | "}" => {
+                closed_function_head = matches!(stack.pop(), Some(Frame::Group { function_head: true }));
+                continue;
+            }
+            ">" if is_arrow(tokens, index) && tokens.get(index + 1).is_some_and(|next| next.text != "{") => {
+                stack.push(Frame::ArrowBody)
+            }
+            "await"
+                if token.kind == TokenKind::Identifier
+                    && previous_text != "."
+                    && !stack.iter().any(|frame| matches!(frame, Frame::Function | Frame::ArrowBody)) =>
+            {
+                awaits.push(token)
+            }
+            _ => {}
+        }
+
+        closed_function_head = false;
+    }
+
+    awaits
+}
+
+fn is_arrow(tokens: &[Token], index: usize) -> bool {
+    index > 0 && tokens[index].text == ">" && tokens[index - 1].text == "=" && tokens[index - 1].start + 1 == tokens[index].start
+}
+
+fn ends_arrow(tokens: &[Token], index: usize) -> bool {
+    index > 0 && is_arrow(tokens, index - 1)
+}
+
+fn wrap_in_async_function(output: &str) -> String {
+    let mut wrapped = String::with_capacity(output.len() + 32);
+    let mut hoisted = String::new();
+    let mut opened_at = None;
+    let mut in_declaration = false;
+
+    for line in output.split_inclusive('\n') {
+        let trimmed = line.trim_start();
+        let declaration = in_declaration || is_module_declaration(trimmed);
+        if declaration {
+            in_declaration = if in_declaration { !line.contains('}') } else { opens_binding_list(line) };
+        }
+
+        match opened_at {
+            None if !declaration && !trimmed.is_empty() && !trimmed.starts_with("//") => {
+                opened_at = Some(wrapped.len());
+                wrapped.push_str("(async () => { ");
+            }
+            Some(_) if declaration => {
+                hoisted.push_str(line);
+                if !line.ends_with('\n') {
+                    hoisted.push('\n');
+                }
+                continue;
+            }
+            _ => {}
+        }
+
+        wrapped.push_str(line);
+    }
+
+    if let Some(opened_at) = opened_at {
+        wrapped.insert_str(opened_at, &hoisted);
+        if !wrapped.ends_with('\n') {
+            wrapped.push('\n');
+        }
+        wrapped.push_str("})();\n");
+    }
+
+    wrapped
+}
+
+fn is_module_declaration(trimmed: &str) -> bool {
+    ["import", "export"].iter().any(|keyword| {
+        trimmed.strip_prefix(keyword).is_some_and(|rest| {
+            rest.starts_with([' ', '\t', '{', '*', '"', '\'']) && !rest.trim_start().starts_with(['(', '.'])
+        })
+    })
+}
+
+fn reexports(tokens: &[Token], index: usize) -> bool {
+    let from = match tokens.get(index + 1).map(|token| token.text) {
+        Some("*") if tokens.get(index + 2).is_some_and(|token| token.text == "as") => index + 4,
+        Some("*") => index + 2,
+        Some("{") => match tokens[index..].iter().position(|token| token.text == "}") {
+            Some(close) => index + close + 1,
+            None => return false,
+        },
+        _ => return false,
+    };
+    tokens.get(from).is_some_and(|token| token.text == "from")
+}
+
+impl TranspileExtension for Minify {
+    fn name(&self) -> &'static str {
+        "minify"
+    }
+
+    fn enabled(&self, options: &TranspileOptions) -> bool {
+        options.minify
+    }
+
+    fn apply(&self, output: String, _options: &TranspileOptions) -> Result<String, NullScriptError> {
+        let tokens = Lexer::new(&output).with_max_depth(usize::MAX).tokenize()?;
+        let mut result = String::with_capacity(output.len());
+
+        for token in &tokens {
+            match token.kind {
+                TokenKind::LineComment => {}
+                TokenKind::BlockComment | TokenKind::Whitespace if token.text.contains('\n') => {
+                    result.extend(token.text.chars().filter(|c| *c == '\n'));
+                }
+                TokenKind::BlockComment | TokenKind::Whitespace => {
+                    if !result.is_empty() && !result.ends_with(['\n', ' ']) {
+                        result.push(' ');
+                    }
+                }
+                _ => result.push_str(token.text),
+            }
+        }
+
+        let lines: Vec<&str> = result.split('\n').map(str::trim_end).collect();
+        Ok(lines.join("\n"))
+    }
+}
+
+impl TranspileExtension for CommonJs {
+    fn name(&self) -> &'static str {
+        "commonjs"
+    }
+
+    fn enabled(&self, options: &TranspileOptions) -> bool {
+        options.module == ModuleFormat::Cjs
+    }
+
+    fn apply(&self, output: String, _options: &TranspileOptions) -> Result<String, NullScriptError> {
+        let mut exported: Vec<(String, String)> = Vec::new();
+        let mut lines = Vec::new();
+
+        let mut pending: Vec<&str> = Vec::new();
+
+        for line in output.split('\n') {
+            if pending.is_empty() && !opens_binding_list(line) {
+                lines.push(lower_line(line, &mut exported)?);
+                continue;
+            }
+
+            pending.push(line);
+            if line.contains('}') {
+                let statement = pending.join("\n");
+                let lowered = lower_line(&statement, &mut exported)?;
+                let padding = pending.len().saturating_sub(lowered.split('\n').count());
+                lines.push(lowered + &"\n".repeat(padding));
+                pending.clear();
+            }
+        }
+        lines.extend(pending.into_iter().map(str::to_string));
+
+        let mut result = lines.join("\n");
+        if !exported.is_empty() {
+            if !result.ends_with('\n') {
+                result.push('\n');
+            }
+            for (exported_name, local) in exported {
+                result.push_str(&format!("module.exports.{} = {};\n", exported_name, local));
+            }
+        }
+
+        Ok(result)
+    }
+}
+
+impl TranspileExtension for RuntimeShim {
+    fn name(&self) -> &'static str {
+        "runtime_shim"
+    }
+
+    fn enabled(&self, options: &TranspileOptions) -> bool {
+        options.runtime_shim
+    }
+
+    fn apply(&self, output: String, options: &TranspileOptions) -> Result<String, NullScriptError> {
+        if output.contains(RUNTIME_SPECIFIER) {
+            return Ok(output);
+        }
+
+        let tokens = Lexer::new(&output).with_max_depth(usize::MAX).tokenize()?;
+        let mut used: Vec<&str> = Vec::new();
+        let mut previous = "";
+        for token in &tokens {
+            if token.kind == TokenKind::Identifier && previous != "." && RUNTIME_SHIM_ALIASES.contains(&token.text) && !used.contains(&token.text) {
+                used.push(token.text);
+            }
+            if !token.is_trivia() {
+                previous = token.text;
+            }
+        }
+        if used.is_empty() {
+            return Ok(output);
+        }
+
+        used.sort_by_key(|alias| RUNTIME_SHIM_ALIASES.iter().position(|shim| shim == alias));
+        let import = match options.module {
+            ModuleFormat::Esm => format!("import {{ {} }} from \"{}\"; ", used.join(", "), RUNTIME_SPECIFIER),
+            ModuleFormat::Cjs => format!("const {{ {} }} = require(\"{}\"); ", used.join(", "), RUNTIME_SPECIFIER),
+        };
+
+        let at = if output.starts_with("#!") { output.find('\n').map_or(output.len(), |i| i + 1) } else { 0 };
+        let mut result = output;
+        result.insert_str(at, &import);
+        Ok(result)
+    }
+}
+
+fn lower_line(line: &str, exported: &mut Vec<(String, String)>) -> Result<String, NullScriptError> {
+    if let Some(caps) = patterns::get("cjs_import_namespace")?.captures(line) {
+        return Ok(format!("{}const {} = require({});", &caps[1], &caps[2], &caps[3]));
+    }
+
+    if let Some(caps) = patterns::get("cjs_import_mixed")?.captures(line) {
+        return Ok(format!(
+            "{}const {} = require({}), {{ {} }} = {};",
+            &caps[1], &caps[2], &caps[4], rename_bindings(&caps[3], ": "), &caps[2]
+        ));
+    }
+
+    if let Some(caps) = patterns::get("cjs_import_named")?.captures(line) {
+        return Ok(format!("{}const {{ {} }} = require({});", &caps[1], rename_bindings(&caps[2], ": "), &caps[3]));
+    }
+
+    if let Some(caps) = patterns::get("cjs_import_default")?.captures(line) {
+        return Ok(format!("{}const {} = require({});", &caps[1], &caps[2], &caps[3]));
+    }
+
+    if let Some(caps) = patterns::get("cjs_import_bare")?.captures(line) {
+        return Ok(format!("{}require({});", &caps[1], &caps[2]));
+    }
+
+    if let Some(caps) = patterns::get("cjs_export_namespace")?.captures(line) {
+        return Ok(format!("{}module.exports.{} = require({});", &caps[1], &caps[2], &caps[3]));
+    }
+
+    if let Some(caps) = patterns::get("cjs_export_all")?.captures(line) {
+        return Ok(format!("{}Object.assign(module.exports, require({}));", &caps[1], &caps[2]));
+    }
+
+    if let Some(caps) = patterns::get("cjs_reexport_named")?.captures(line) {
+        let assignments: Vec<String> = bindings(&caps[2])
+            .into_iter()
+            .map(|(local, name)| match local.as_str() {
+                "default" => format!("module.exports.{} = require({});", name, &caps[3]),
+                _ => format!("module.exports.{} = require({}).{};", name, &caps[3], local),
+            })
+            .collect();
+        return Ok(format!("{}{}", &caps[1], assignments.join(" ")));
+    }
+
+    if let Some(caps) = patterns::get("cjs_export_list")?.captures(line) {
+        exported.extend(bindings(&caps[2]).into_iter().map(|(local, name)| (name, local)));
+        return Ok(caps[1].to_string());
+    }
+
+    if let Some(caps) = patterns::get("cjs_export_default")?.captures(line) {
+        return Ok(format!("{}module.exports = {}", &caps[1], &line[caps[0].len()..]));
+    }
+
+    let declaration = patterns::get("cjs_export_declaration")?;
+    if let Some(caps) = declaration.captures(line) {
+        exported.push((caps[3].to_string(), caps[3].to_string()));
+        return Ok(declaration
+            .replace(line, |caps: &Captures| format!("{}{}{}", &caps[1], &caps[2], &caps[3]))
+            .into_owned());
+    }
+
+    Ok(line.to_string())
+}
+
+fn opens_binding_list(line: &str) -> bool {
+    let trimmed = line.trim_start();
+    (trimmed.starts_with("import") || trimmed.starts_with("export")) && line.contains('{') && !line.contains('}')
+}
+
+fn bindings(list: &str) -> Vec<(String, String)> {
+    list.split(',')
+        .map(str::trim)
+        .filter(|binding| !binding.is_empty())
+        .map(|binding| match binding.split_once(" as ") {
+            Some((local, name)) => (local.trim().to_string(), name.trim().to_string()),
+            None => (binding.to_string(), binding.to_string()),
+        })
+        .collect()
+}
+
+fn rename_bindings(list: &str, separator: &str) -> String {
+    bindings(list)
+        .into_iter()
+        .map(|(imported, local)| {
+            if imported == local {
+                imported
+            } else {
+                format!("{}{}{}", imported, separator, local)
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_commonjs_lowering() {
+        let options = TranspileOptions::new().with_module(ModuleFormat::Cjs);
+        let source = "import fs from \"fs\";\nimport { join as j, dirname } from \"path\";\nexport function greet() {}\nexport const answer = 42;\nexport default greet;\n";
+
+        let output = CommonJs.apply(source.to_string(), &options).unwrap();
+        assert!(output.contains("const fs = require(\"fs\");"));
+        assert!(output.contains("const { join: j, dirname } = require(\"path\");"));
+        assert!(output.contains("\nfunction greet() {}"));
+        assert!(output.contains("module.exports = greet;"));
+        assert!(output.ends_with("module.exports.greet = greet;\nmodule.exports.answer = answer;\n"));
+
+        let multiline = "import {\n    add,\n    subtract\n} from \"./math.js\";\nadd(1, 2);";
+        let output = CommonJs.apply(multiline.to_string(), &options).unwrap();
+        assert_eq!(output, "const { add, subtract } = require(\"./math.js\");\n\n\n\nadd(1, 2);");
+    }
+
+    #[test]
+    fn test_commonjs_reexports() {
+        let options = TranspileOptions::new().with_module(ModuleFormat::Cjs);
+        let source = "export * as tools from './tools.js';\nexport { default as Box, size } from './box.js';\nexport * from './helpers.js';\n";
+
+        assert_eq!(
+            CommonJs.apply(source.to_string(), &options).unwrap(),
+            "module.exports.tools = require('./tools.js');\nmodule.exports.Box = require('./box.js'); module.exports.size = require('./box.js').size;\nObject.assign(module.exports, require('./helpers.js'));\n"
+        );
+    }
+
+    #[test]
+    fn test_top_level_await_policy() {
+        let source = "import fs from \"fs\";\nconst data = await load();\nasync function later() {\n    await data.save();\n}\nconst run = async () => await later();\nif (data) {\n    for await (const chunk of data) {}\n}\n";
+        let awaited: Vec<u32> = top_level_awaits(&Lexer::new(source).tokenize().unwrap().into_iter().filter(|t| !t.is_trivia()).collect::<Vec<_>>())
+            .into_iter()
+            .map(|token| token.line)
+            .collect();
+        assert_eq!(awaited, vec![2, 8]);
+
+        let esnext = TranspileOptions::new();
+        assert!(!TopLevelAwait.enabled(&esnext));
+
+        let cjs = TranspileOptions::new().with_module(ModuleFormat::Cjs);
+        let error = TopLevelAwait.apply(source.to_string(), &cjs).unwrap_err();
+        assert!(matches!(&error, NullScriptError::Transpile(e) if e.location.line == Some(2) && e.message.contains("--module cjs")));
+
+        let wrapped = TopLevelAwait.apply(source.to_string(), &cjs.with_top_level_await(TopLevelAwaitPolicy::Wrap)).unwrap();
+        assert!(wrapped.starts_with("import fs from \"fs\";\n(async () => { const data = await load();\n"));
+        assert!(wrapped.ends_with("}\n})();\n"));
+        assert_eq!(wrapped.lines().count(), source.lines().count() + 1);
+
+        let shared = format!("export const answer = 42;\n{}", source);
+        let old_target = TranspileOptions::new().with_target(Target::Es2020).with_top_level_await(TopLevelAwaitPolicy::Wrap);
+        assert!(TopLevelAwait.apply(shared, &old_target).is_err());
+    }
+
+    #[test]
+    fn test_wrap_hoists_module_declarations() {
+        let source = "// entry\nimport fs from \"fs\";\nconst data = await fs.load();\nimport {\n    parse,\n} from \"./parse.js\"; // helpers\nexport * from \"./shared.js\";\nexport { format as show } from \"./format.js\";\nconst url = import.meta.url;\nconst lazy = await import(\"./lazy.js\");\nconsole.log(parse(data), url, lazy);\n";
+        let options = TranspileOptions::new().with_module(ModuleFormat::Esm).with_target(Target::Es2020).with_top_level_await(TopLevelAwaitPolicy::Wrap);
+        let wrapped = TopLevelAwait.apply(source.to_string(), &options).unwrap();
+
+        assert_eq!(
+            wrapped,
+            "// entry\nimport fs from \"fs\";\nimport {\n    parse,\n} from \"./parse.js\"; // helpers\nexport * from \"./shared.js\";\nexport { format as show } from \"./format.js\";\n(async () => { const data = await fs.load();\nconst url = import.meta.url;\nconst lazy = await import(\"./lazy.js\");\nconsole.log(parse(data), url, lazy);\n})();\n"
+        );
+        assert_eq!(wrapped.lines().count(), source.lines().count() + 1);
+
+        let cjs = options.with_module(ModuleFormat::Cjs);
+        let lowered = CommonJs.apply(TopLevelAwait.apply(source.to_string(), &cjs).unwrap(), &cjs).unwrap();
+        let body = lowered.split_once("(async () => {").unwrap().1;
+        assert!(!body.contains("require("), "{}", lowered);
+    }
+
+    #[test]
+    fn test_target_syntax_rejects_newer_syntax() {
+        let source = "const a = b?.c ?? 1;\nclass Box {\n    size = 2;\n    #id;\n    open() { return this.#id; }\n}\nasync function f() {}\nconst big = 1_000n;\nx ? .5 : 1;\n";
+        let found: Vec<(u32, &str)> = newer_syntax(&Lexer::new(source).tokenize().unwrap().into_iter().filter(|t| !t.is_trivia()).collect::<Vec<_>>())
+            .into_iter()
+            .map(|(token, feature, _)| (token.line, feature))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                (1, "Optional chaining (?.)"),
+                (1, "Nullish coalescing (??)"),
+                (3, "Class fields"),
+                (4, "Private class members (#name)"),
+                (5, "Private class members (#name)"),
+                (7, "Async functions"),
+                (8, "Numeric separators"),
+            ]
+        );
+
+        let es2017 = TranspileOptions::new().with_target(Target::Es2017);
+        let error = TargetSyntax.apply(source.to_string(), &es2017).unwrap_err();
+        assert!(matches!(&error, NullScriptError::Transpile(e) if e.location.line == Some(1) && e.message.contains("--target es2020")));
+        assert!(TargetSyntax.apply("async function f() {}\n".to_string(), &es2017).is_ok());
+        assert_eq!(
+            TargetSyntax.apply("try {\n    a();\n} catch {\n    b();\n}\np.catch(() => {});\n".to_string(), &es2017).unwrap(),
+            "try {\n    a();\n} catch (_ignored) {\n    b();\n}\np.catch(() => {});\n"
+        );
+        assert!(!TargetSyntax.enabled(&TranspileOptions::new()));
+    }
+
+    #[test]
+    fn test_defines_and_minify() {
+        let options = TranspileOptions::new().with_define("DEBUG", "false").with_minify(true);
+        let source = "// header\nif (DEBUG) {\n    config.DEBUG = 1; /* note */\n}\n";
+
+        let defined = Defines.apply(source.to_string(), &options).unwrap();
+        assert!(defined.contains("if (false)"));
+        assert!(defined.contains("config.DEBUG"));
+
+        let minified = Minify.apply(defined, &options).unwrap();
+        assert_eq!(minified, "\nif (false) {\nconfig.DEBUG = 1;\n}\n");
+    }
+}
@@ -0,0 +1,158 @@
+use crate::core::NullScriptError;
+use crate::core::keywords::{FORBIDDEN_KEYWORDS, INVALID_SYNTAX, KEYWORDS};
+use regex::Regex;
+use std::collections::HashMap;
+use std::sync::LazyLock;
+
+static PATTERNS: &[(&str, &str)] = &[
+
+    ("js_function", r"^\s*(function\s+\w+\s*\()"),
+    ("js_const", r"^\s*(const\s+\w+)"),
+    ("js_if", r"^\s*(if\s*\()"),
+    ("js_else", r"^\s*(else\s+)"),
+    ("js_true", r"^\s*(true)\b"),
+    ("js_false", r"^\s*(false)\b"),
+    ("js_class", r"^\s*(class\s+\w+)"),
+    ("js_try", r"^\s*(try\s*\{)"),
+    ("js_catch", r"^\s*(catch\s*\()"),
+    ("js_finally", r"^\s*(finally\s*\{)"),
+    ("variable_identifier", r"^\s*(let|fixed|var)\s+([a-zA-Z_$][\w$]*)\s*="),
+    ("function_identifier", r"^\s*run\s+([a-zA-Z_$][\w$]*)\s*\("),
+    ("class_identifier", r"^\s*model\s+([a-zA-Z_$][\w$]*)\s*(?:inherits\s+[^{;]+?\s*)?(?:\{|$)"),
+    ("method_identifier", r"^\s+run\s+([a-zA-Z_$][\w$]*)\s*\("),
+    ("function_params", r"run\s+[a-zA-Z_$][\w$]*\s*\(([^)]*)\)"),
+
+
+    ("class_decl", r"\bmodel(\s+)([a-zA-Z_$][\w$]*)(\s*)\{"),
+    ("class_extends", r"\bmodel(\s+)([a-zA-Z_$][\w$]*)(\s+)inherits(\s+)([^{;]+?)(\s*)\{"),
+    ("class_field", r"(?m)(^[ \t]+)fixed\s+([a-zA-Z_$][\w$]*)"),
+    ("static", r"\brun\s+forever\s+([a-zA-Z_$][\w$]*)\s*\(([^)]*)\)\s*\{"),
+    ("async_generator_declaration", r"\brun\s+later\s*\*\s*([a-zA-Z_$][\w$]*)\s*\(([^)]*)\)\s*\{"),
+    ("generator_declaration", r"\brun\s*\*\s*([a-zA-Z_$][\w$]*)\s*\(([^)]*)\)\s*\{"),
+    ("async_generator_expression", r"\brun\s+later\s*\*\s*\(([^)]*)\)\s*\{"),
+    ("generator_expression", r"\brun\s*\*\s*\(([^)]*)\)\s*\{"),
+    ("async_top", r"(?m)^([ \t]*)run\s+later\s+([a-zA-Z_$][\w$]*)\s*\(([^)]*)\)\s*\{"),
+    ("function_declaration", r"run\s+([a-zA-Z_$][\w$]*)\s*\(\s*\)\s*\{"),
+    ("function_declaration_params", r"run\s+([a-zA-Z_$][\w$]*)\s*\(([^)]*)\)\s*\{"),
+    ("nested_function", r"(\s*)run\s+([a-zA-Z_$][\w$]*)\s*\(\s*\)\s*\{"),
+    ("nested_function_params", r"(\s*)run\s+([a-zA-Z_$][\w$]*)\s*\(([^)]*)\)\s*\{"),
+    ("class_method_post", r"(?m)((?:^|[{;}])[ \t]*)function\s+([a-zA-Z_$][\w$]*)\s*\(\s*\)\s*\{"),
+    ("class_method_params_post", r"(?m)((?:^|[{;}])[ \t]*)function\s+([a-zA-Z_$][\w$]*)\s*\(([^)]*)\)\s*\{"),
+    ("generator_method", r"(?m)((?:^|[{;}])[ \t]*)(async\s+)?function\*\s*([a-zA-Z_$][\w$]*)\s*\(([^)]*)\)\s*\{"),
+    ("constructor", r"(?m)((?:^|[{;}])[ \t]*)function\s+__init__\s*\(([^)]*)\)\s*\{"),
+    ("constructor_run", r"(?m)((?:^|[{;}])[ \t]*)run\s+__init__\s*\(([^)]*)\)\s*\{"),
+    ("async_method", r"(?m)((?:^|[{;}])[ \t]*)async\s+function\s+([a-zA-Z_$][\w$]*)\s*\(([^)]*)\)\s*\{"),
+    ("async_method_fix", r"(?m)((?:^|[{;}])[ \t]*)function\s+([a-zA-Z_$][\w$]*)\s*\(([^)]*)\)\s*\{(\s*await)"),
+    ("class_async", r"(?m)((?:^|[{;}])[ \t]*)function\s+([a-zA-Z_$][\w$]*)\s*\(([^)]*)\)\s*\{(\s*let\s+response\s*=\s*await)"),
+    ("standalone_async", r"(?m)\brun\s+async\s+([a-zA-Z_$][\w$]*)\s*\(([^)]*)\)\s*\{"),
+    ("class_run_async", r"(?m)((?:^|[{;}])[ \t]*)run\s+async\s+([a-zA-Z_$][\w$]*)\s*\(([^)]*)\)\s*\{"),
+    ("label", r"(?m)((?:^|[;{}])[ \t]*)mark[ \t]+([a-zA-Z_$][\w$]*)[ \t]*:"),
+    ("remove", r"\bremove\s+([a-zA-Z_$][\w$]*(?:\.[a-zA-Z_$][\w$]*)*(?:\[[^\]]+\])?)\b"),
+    ("default_export", r"\bshare\s+default\s+run\s+([a-zA-Z_$][\w$]*)\s*\(([^)]*)\)\s*\{"),
+    ("object_function", r"(\w+)\s*:\s*run\s*\("),
+    ("arrow_function", r"run\s*\(([^)]*)\)\s*\{"),
+    ("non_null", r"([a-zA-Z_$][\w$]*)\!"),
+    ("super_constructor", r"super\.constructor\("),
+    ("json_method", r"\.JSON\("),
+    ("static_method_call", r"([a-zA-Z_$][\w$]*)\.forever\.([a-zA-Z_$][\w$]*)\("),
+    ("static_call", r"([a-zA-Z_$][\w$]*)\.static\.([a-zA-Z_$][\w$]*)\("),
+    ("namespace_import", r"(?m)^([ \t]*use[ \t]+(?:[a-zA-Z_$][\w$]*[ \t]*,[ \t]*)?)everything([ \t]+as\b)"),
+    ("namespace_export", r"(?m)^([ \t]*share[ \t]+)everything([ \t]+(?:as|from)\b)"),
+    ("default_import", r"\bimport\s+default\s+as\s+([a-zA-Z_$][\w$]*)"),
+    ("dynamic_import", r#"(?m)(^|[^.\w$])import[ \t]*\([ \t]*(["'`])(\.\.?/[^"'`\n]*?)\.ns(["'`])"#),
+    ("masked_literal", r"\x{E000}(\d+)\x{E001}"),
+    ("static_specifier", r#"(?m)((?:^[ \t]*(?:import|export)\b[^;"'`\n]*?|^[ \t]*\}[ \t]*)\bfrom[ \t]*|^[ \t]*import[ \t]*)(["'])(\.\.?/[^"'\n]*?)\.ns(["'])"#),
+
+
+    ("cjs_import_namespace", r#"^(\s*)import\s+\*\s+as\s+([A-Za-z_$][\w$]*)\s+from\s+("[^"]*"|'[^']*')\s*;?\s*$"#),
+    ("cjs_import_mixed", r#"^(\s*)import\s+([A-Za-z_$][\w$]*)\s*,\s*\{([^}]*)\}\s*from\s+("[^"]*"|'[^']*')\s*;?\s*$"#),
+    ("cjs_import_named", r#"^(\s*)import\s*\{([^}]*)\}\s*from\s+("[^"]*"|'[^']*')\s*;?\s*$"#),
+    ("cjs_import_default", r#"^(\s*)import\s+([A-Za-z_$][\w$]*)\s+from\s+("[^"]*"|'[^']*')\s*;?\s*$"#),
+    ("cjs_import_bare", r#"^(\s*)import\s+("[^"]*"|'[^']*')\s*;?\s*$"#),
+    ("cjs_export_namespace", r#"^(\s*)export\s*\*\s*as\s+([A-Za-z_$][\w$]*)\s+from\s+("[^"]*"|'[^']*')\s*;?\s*$"#),
+    ("cjs_export_all", r#"^(\s*)export\s*\*\s*from\s+("[^"]*"|'[^']*')\s*;?\s*$"#),
+    ("cjs_reexport_named", r#"^(\s*)export\s*\{([^}]*)\}\s*from\s+("[^"]*"|'[^']*')\s*;?\s*$"#),
+    ("cjs_export_list", r#"^(\s*)export\s*\{([^}]*)\}\s*;?\s*$"#),
+    ("cjs_export_default", r#"^(\s*)export\s+default\s+"#),
+    ("cjs_export_declaration", r#"^(\s*)export\s+((?:async\s+)?(?:function\*?|class|const|let|var)\s+)([A-Za-z_$][\w$]*)"#),
+
+
+    ("file_name_placeholder", r"\[(\w+)(?::(\d+))?\]"),
+    ("output_specifier", r#"(\bfrom[ \t]*|\bimport[ \t]*\(?[ \t]*|\brequire[ \t]*\([ \t]*)(["'])(\.\.?/[^"'\n]*?\.js)["']"#),
+    ("source_map_comment", r"(?m)^(//# sourceMappingURL=).*$"),
+    ("line_token", r#"[A-Za-z_$][\w$]*|\d[\w.]*|"(?:[^"\\]|\\.)*"|'(?:[^'\\]|\\.)*'|`(?:[^`\\]|\\.)*`"#),
+    ("stack_frame_position", r":(\d+)(:\d+)?"),
+];
+
+static COMPILED: LazyLock<HashMap<&'static str, Result<Regex, regex::Error>>> = LazyLock::new(|| {
+    PATTERNS
+        .iter()
+        .map(|(name, pattern)| (*name, Regex::new(pattern)))
+        .collect()
+});
+
+static WORDS: LazyLock<HashMap<&'static str, Result<Regex, regex::Error>>> = LazyLock::new(|| {
+    KEYWORDS
+        .iter()
+        .map(|(keyword, _)| *keyword)
+        .chain(FORBIDDEN_KEYWORDS.iter().copied())
+        .map(|word| (word, Regex::new(&format!(r"\b{}\b", regex::escape(word)))))
+        .collect()
+});
+
+static SYNTAX_RULES: LazyLock<HashMap<&'static str, Result<Regex, regex::Error>>> = LazyLock::new(|| {
+    INVALID_SYNTAX
+        .iter()
+        .map(|rule| (rule.name, Regex::new(rule.pattern)))
+        .collect()
+});
+
+pub fn get(name: &str) -> Result<&'static Regex, NullScriptError> {
+    lookup(&COMPILED, name, "pattern")
+}
+
+pub fn word(word: &str) -> Result<&'static Regex, NullScriptError> {
+    lookup(&WORDS, word, "keyword pattern")
+}
+
+pub fn syntax_rule(name: &str) -> Result<&'static Regex, NullScriptError> {
+    lookup(&SYNTAX_RULES, name, "syntax rule")
+}
+
+fn lookup(
+    registry: &'static LazyLock<HashMap<&'static str, Result<Regex, regex::Error>>>,
+    key: &str,
+    kind: &str,
+) -> Result<&'static Regex, NullScriptError> {
+    match registry.get(key) {
+        Some(Ok(regex)) => Ok(regex),
+        Some(Err(e)) => Err(NullScriptError::Regex(e.clone())),
+        None => Err(NullScriptError::Regex(regex::Error::Syntax(format!(
+            "unknown {} '{}'",
+            kind, key
+        )))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_every_pattern_compiles() {
+        for (name, _) in PATTERNS {
+            assert!(get(name).is_ok(), "pattern '{}' does not compile", name);
+        }
+        for word in WORDS.keys() {
+            assert!(super::word(word).is_ok(), "keyword pattern '{}' does not compile", word);
+        }
+        for rule in INVALID_SYNTAX {
+            assert!(syntax_rule(rule.name).is_ok(), "syntax rule '{}' does not compile", rule.name);
+        }
+    }
+
+    #[test]
+    fn test_unknown_pattern_is_an_error() {
+        assert!(matches!(get("no_such_pattern"), Err(NullScriptError::Regex(_))));
+    }
+}
@@ -0,0 +1,96 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+pub const PLATFORMS: &[&str] = &["node", "browser"];
+
+pub struct PlatformSelection {
+    pub sources: Vec<(PathBuf, PathBuf)>,
+    pub unmatched: Vec<(PathBuf, Vec<String>)>,
+}
+
+pub fn split_variant(path: &Path) -> Option<(PathBuf, &'static str)> {
+    let stem = path.file_stem()?.to_str()?;
+    let (base, suffix) = stem.rsplit_once('.')?;
+    let platform = PLATFORMS.iter().find(|p| **p == suffix)?;
+
+    if base.is_empty() {
+        return None;
+    }
+
+    Some((path.with_file_name(format!("{}.ns", base)), platform))
+}
+
+pub fn select(files: Vec<PathBuf>, platform: Option<&str>) -> PlatformSelection {
+    let mut plain: BTreeMap<PathBuf, PathBuf> = BTreeMap::new();
+    let mut variants: BTreeMap<PathBuf, Vec<(&'static str, PathBuf)>> = BTreeMap::new();
+
+    for file in files {
+        match split_variant(&file) {
+            Some((base, name)) => variants.entry(base).or_default().push((name, file)),
+            None => {
+                plain.insert(file.clone(), file);
+            }
+        }
+    }
+
+    let mut sources: Vec<(PathBuf, PathBuf)> = Vec::new();
+    let mut unmatched = Vec::new();
+
+    for (base, mut group) in variants {
+        group.sort();
+
+        let Some(platform) = platform else {
+            if !plain.contains_key(&base) {
+                unmatched.push((base, group.iter().map(|(name, _)| name.to_string()).collect()));
+            }
+            sources.extend(group.into_iter().map(|(_, file)| (file.clone(), file)));
+            continue;
+        };
+
+        match group.iter().find(|(name, _)| *name == platform) {
+            Some((_, file)) => {
+                plain.remove(&base);
+                sources.push((file.clone(), base));
+            }
+            None if plain.contains_key(&base) => {}
+            None => unmatched.push((base, group.iter().map(|(name, _)| name.to_string()).collect())),
+        }
+    }
+
+    sources.extend(plain.into_values().map(|file| (file.clone(), file)));
+    sources.sort();
+
+    PlatformSelection { sources, unmatched }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_select_prefers_matching_variant() {
+        let files = vec![
+            PathBuf::from("src/utils.node.ns"),
+            PathBuf::from("src/utils.browser.ns"),
+            PathBuf::from("src/main.ns"),
+            PathBuf::from("src/db.browser.ns"),
+            PathBuf::from("src/db.ns"),
+            PathBuf::from("src/net.browser.ns"),
+        ];
+
+        let selection = select(files.clone(), Some("node"));
+        assert_eq!(
+            selection.sources,
+            vec![
+                (PathBuf::from("src/db.ns"), PathBuf::from("src/db.ns")),
+                (PathBuf::from("src/main.ns"), PathBuf::from("src/main.ns")),
+                (PathBuf::from("src/utils.node.ns"), PathBuf::from("src/utils.ns")),
+            ]
+        );
+        assert_eq!(selection.unmatched, vec![(PathBuf::from("src/net.ns"), vec!["browser".to_string()])]);
+
+        let selection = select(files, None);
+        assert_eq!(selection.sources.len(), 6);
+        assert_eq!(selection.unmatched.len(), 2);
+    }
+}
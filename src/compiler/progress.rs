@@ -0,0 +1,85 @@
+use crate::core::ExternalToolError;
+use serde::Serialize;
+use std::fmt;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "event", rename_all = "camelCase")]
+pub enum ProgressEvent {
+    #[serde(rename_all = "camelCase")]
+    TaskStarted { task: String, files: usize },
+    #[serde(rename_all = "camelCase")]
+    FileFinished {
+        task: String,
+        file: PathBuf,
+        output: Option<PathBuf>,
+        index: usize,
+        total: usize,
+        source_bytes: usize,
+        duration_ms: f64,
+        warnings: usize,
+        #[serde(skip_serializing_if = "std::ops::Not::not")]
+        cached: bool,
+    },
+    #[serde(rename_all = "camelCase")]
+    TaskCompleted {
+        task: String,
+        files: usize,
+        warnings: usize,
+        duration_ms: f64,
+        success: bool,
+        error: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        external_tool: Option<ExternalToolError>,
+    },
+}
+
+#[derive(Clone)]
+pub struct Progress(Arc<dyn Fn(&ProgressEvent) + Send + Sync>);
+
+impl Progress {
+    pub fn new(report: impl Fn(&ProgressEvent) + Send + Sync + 'static) -> Self {
+        Self(Arc::new(report))
+    }
+
+    pub fn emit(&self, event: ProgressEvent) {
+        (self.0)(&event);
+    }
+}
+
+impl fmt::Debug for Progress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Progress")
+    }
+}
+
+pub fn millis(duration: Duration) -> f64 {
+    (duration.as_secs_f64() * 1_000_000.0).round() / 1000.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_events_serialize_as_tagged_camel_case() {
+        let event = ProgressEvent::FileFinished {
+            task: "build".to_string(),
+            file: PathBuf::from("src/app.ns"),
+            output: Some(PathBuf::from("dist/app.js")),
+            index: 1,
+            total: 2,
+            source_bytes: 120,
+            duration_ms: millis(Duration::from_micros(1500)),
+            warnings: 0,
+            cached: false,
+        };
+
+        assert_eq!(
+            serde_json::to_string(&event).unwrap(),
+            r#"{"event":"fileFinished","task":"build","file":"src/app.ns","output":"dist/app.js","index":1,"total":2,"sourceBytes":120,"durationMs":1.5,"warnings":0}"#
+        );
+    }
+}
@@ -0,0 +1,571 @@
+use crate::compiler::aliases::PathAliases;
+use crate::compiler::lexer::{Lexer, Token, TokenKind};
+use crate::compiler::platform::split_variant;
+use crate::core::NullScriptError;
+use crate::utils::files::FileUtils;
+use crate::utils::paths::PathUtils;
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ImportTarget {
+    Local(PathBuf),
+    Missing(PathBuf),
+    External(String),
+}
+
+#[derive(Debug, Clone)]
+pub struct ImportEdge {
+    pub specifier: String,
+    pub line: u32,
+    pub column: u32,
+    pub dynamic: bool,
+    pub target: ImportTarget,
+}
+
+#[derive(Debug, Clone)]
+pub struct ModuleNode {
+    pub imports: Vec<ImportEdge>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ModuleGraph {
+    pub root: PathBuf,
+    pub modules: BTreeMap<PathBuf, ModuleNode>,
+    pub platform: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CaseMismatch {
+    pub file: PathBuf,
+    pub line: u32,
+    pub column: u32,
+    pub specifier: String,
+    pub on_disk: PathBuf,
+    pub fixed_specifier: String,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ResolveOptions {
+    pub platform: Option<String>,
+    pub root_dir: Option<PathBuf>,
+    pub aliases: PathAliases,
+}
+
+pub struct ImportSpecifier {
+    pub specifier: String,
+    pub line: u32,
+    pub column: u32,
+    pub dynamic: bool,
+}
+
+pub fn find_imports(source: &str) -> Vec<ImportSpecifier> {
+    let Ok(tokens) = Lexer::new(source).with_max_depth(usize::MAX).tokenize() else {
+        return Vec::new();
+    };
+    let tokens: Vec<Token> = tokens.into_iter().filter(|t| !t.is_trivia()).collect();
+    let mut imports = Vec::new();
+
+    for (i, token) in tokens.iter().enumerate() {
+        if token.kind != TokenKind::Identifier || !matches!(token.text, "use" | "share" | "import" | "export") {
+            continue;
+        }
+
+        let member = i > 0 && tokens[i - 1].text == ".";
+        if let (false, true, Some(spec)) = (member, matches!(token.text, "use" | "import"), dynamic_specifier(&tokens[i + 1..])) {
+            imports.push(ImportSpecifier {
+                specifier: unquote(spec.text),
+                line: spec.line,
+                column: spec.column,
+                dynamic: true,
+            });
+            continue;
+        }
+
+        let statement_start = i == 0 || matches!(tokens[i - 1].text, ";" | "}" | "{")
+            || tokens[i - 1].line < token.line;
+        if !statement_start {
+            continue;
+        }
+
+        let specifier_token = match tokens.get(i + 1) {
+            Some(next) if next.kind == TokenKind::String && matches!(token.text, "use" | "import") => Some(next),
+            _ => tokens[i + 1..]
+                .iter()
+                .take_while(|t| t.text != ";")
+                .position(|t| t.kind == TokenKind::Identifier && t.text == "from")
+                .and_then(|offset| tokens.get(i + 1 + offset + 1))
+                .filter(|t| t.kind == TokenKind::String),
+        };
+
+        if let Some(spec) = specifier_token {
+            imports.push(ImportSpecifier {
+                specifier: unquote(spec.text),
+                line: spec.line,
+                column: spec.column,
+                dynamic: false,
+            });
+        }
+    }
+
+    imports
+}
+
+fn dynamic_specifier<'a, 'b>(rest: &'b [Token<'a>]) -> Option<&'b Token<'a>> {
+    match rest {
+        [open, spec, ..] if open.text == "(" && spec.kind == TokenKind::String => Some(spec),
+        _ => None,
+    }
+}
+
+pub fn resolve_specifier(from_file: &Path, specifier: &str, options: &ResolveOptions) -> ImportTarget {
+    let aliased = options.aliases.targets(specifier);
+    if let Some(first) = aliased.first() {
+        for target in &aliased {
+            if let Some(candidate) = candidates(target, options.platform.as_deref()).into_iter().find(|c| c.is_file()) {
+                return ImportTarget::Local(candidate);
+            }
+        }
+        return ImportTarget::Missing(first.clone());
+    }
+
+    let requested = if specifier.starts_with("./") || specifier.starts_with("../") {
+        let base = from_file.parent().unwrap_or(Path::new("."));
+        PathUtils::normalize(&base.join(specifier))
+    } else if let Some(rooted) = specifier.strip_prefix('/') {
+        match &options.root_dir {
+            Some(root_dir) => PathUtils::normalize(&root_dir.join(rooted)),
+            None => PathUtils::normalize(Path::new(specifier)),
+        }
+    } else {
+        return ImportTarget::External(specifier.to_string());
+    };
+
+    for candidate in candidates(&requested, options.platform.as_deref()) {
+        if candidate.is_file() {
+            return ImportTarget::Local(candidate);
+        }
+    }
+
+    ImportTarget::Missing(requested)
+}
+
+fn candidates(requested: &Path, platform: Option<&str>) -> Vec<PathBuf> {
+    let with_suffix = |path: &Path, suffix: &str| {
+        let mut name = path.as_os_str().to_owned();
+        name.push(suffix);
+        PathBuf::from(name)
+    };
+
+    let (module, mut fallbacks) = match FileUtils::get_extension(requested).as_deref() {
+        Some("ns") => (requested.with_extension(""), vec![requested.to_path_buf()]),
+        Some("js") | Some("mjs") | Some("cjs") => (
+            requested.with_extension(""),
+            vec![requested.with_extension("ns"), requested.to_path_buf()],
+        ),
+        _ => (
+            requested.to_path_buf(),
+            vec![with_suffix(requested, ".ns"), requested.join("index.ns")],
+        ),
+    };
+
+    if let Some(platform) = platform {
+        fallbacks.insert(0, with_suffix(&module, &format!(".{}.ns", platform)));
+    }
+
+    fallbacks
+}
+
+fn on_disk_case(path: &Path, base: &Path) -> Option<PathBuf> {
+    let shared = path.components().zip(base.components()).take_while(|(a, b)| a == b).count();
+    let mut actual: PathBuf = path.components().take(shared).collect();
+
+    for component in path.components().skip(shared) {
+        let wanted = component.as_os_str().to_string_lossy();
+        let entries: Vec<String> = std::fs::read_dir(&actual)
+            .ok()?
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.file_name().to_string_lossy().into_owned())
+            .collect();
+
+        let name = entries
+            .iter()
+            .find(|name| **name == wanted)
+            .or_else(|| entries.iter().find(|name| name.eq_ignore_ascii_case(&wanted)))?;
+        actual.push(name);
+    }
+
+    Some(actual)
+}
+
+fn fix_specifier_case(specifier: &str, on_disk: &Path) -> String {
+    let stem = |name: &str| -> (usize, bool) {
+        match name.rsplit_once('.') {
+            Some((stem, "ns" | "js" | "mjs" | "cjs")) => (stem.len(), true),
+            _ => (name.len(), false),
+        }
+    };
+
+    let mut names: Vec<String> = on_disk
+        .components()
+        .map(|component| component.as_os_str().to_string_lossy().into_owned())
+        .collect();
+    let mut parts: Vec<String> = specifier.split('/').map(str::to_string).collect();
+
+    if names.last().is_some_and(|name| name == "index.ns")
+        && !parts.last().is_some_and(|part| part.eq_ignore_ascii_case("index") || part.eq_ignore_ascii_case("index.ns"))
+    {
+        names.pop();
+    }
+
+    for part in parts.iter_mut().rev() {
+        let Some(name) = names.pop() else {
+            break;
+        };
+        let (part_stem, _) = stem(part);
+        let (name_stem, _) = stem(&name);
+
+        if part[..part_stem].eq_ignore_ascii_case(&name[..name_stem]) {
+            part.replace_range(..part_stem, &name[..name_stem]);
+        } else {
+            break;
+        }
+    }
+
+    parts.join("/")
+}
+
+fn unquote(text: &str) -> String {
+    let trimmed = text.trim();
+    if trimmed.len() >= 2 {
+        trimmed[1..trimmed.len() - 1].to_string()
+    } else {
+        trimmed.to_string()
+    }
+}
+
+impl ModuleGraph {
+    pub fn build(root: &Path, options: &ResolveOptions) -> Result<Self, NullScriptError> {
+        let root = PathUtils::absolute(root);
+        let mut modules = BTreeMap::new();
+
+        let files: Vec<PathBuf> = if root.is_file() {
+            vec![root.clone()]
+        } else {
+            WalkDir::new(&root)
+                .into_iter()
+                .filter_map(|e| e.ok())
+                .filter(|e| e.file_type().is_file() && FileUtils::is_nullscript_file(e.path()))
+                .map(|e| PathUtils::normalize(e.path()))
+                .collect()
+        };
+
+        for file in files {
+            let node = Self::parse_module(&file, options)?;
+            modules.insert(file, node);
+        }
+
+        let root = if root.is_file() {
+            root.parent().map(Path::to_path_buf).unwrap_or_default()
+        } else {
+            root
+        };
+
+        Ok(Self {
+            root,
+            modules,
+            platform: options.platform.clone(),
+        })
+    }
+
+    fn parse_module(file: &Path, options: &ResolveOptions) -> Result<ModuleNode, NullScriptError> {
+        let bytes = std::fs::read(file)?;
+        let imports = if FileUtils::binary_reason(&bytes).is_some() {
+            Vec::new()
+        } else {
+            find_imports(&String::from_utf8_lossy(&bytes))
+                .into_iter()
+                .map(|import| ImportEdge {
+                    target: resolve_specifier(file, &import.specifier, options),
+                    specifier: import.specifier,
+                    line: import.line,
+                    column: import.column,
+                    dynamic: import.dynamic,
+                })
+                .collect()
+        };
+
+        Ok(ModuleNode { imports })
+    }
+
+    pub fn display_path(&self, path: &Path) -> String {
+        PathUtils::relative_to(path, &self.root)
+            .map(|p| PathUtils::to_slash(&p))
+            .unwrap_or_else(|| PathUtils::to_slash(path))
+    }
+
+    pub fn dependencies(&self, path: &Path) -> Vec<&Path> {
+        self.modules
+            .get(path)
+            .map(|node| {
+                node.imports
+                    .iter()
+                    .filter_map(|edge| match &edge.target {
+                        ImportTarget::Local(target) if self.modules.contains_key(target) => Some(target.as_path()),
+                        _ => None,
+                    })
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    pub fn case_mismatches(&self) -> Vec<CaseMismatch> {
+        let mut mismatches = Vec::new();
+
+        for (file, node) in &self.modules {
+            let base = file.parent().unwrap_or(Path::new(""));
+
+            for edge in &node.imports {
+                let requested = match &edge.target {
+                    ImportTarget::Local(path) => vec![path.clone()],
+                    ImportTarget::Missing(path) => candidates(path, self.platform.as_deref()),
+                    ImportTarget::External(_) => continue,
+                };
+
+                let found = requested
+                    .iter()
+                    .find_map(|path| on_disk_case(path, base).filter(|actual| actual != path));
+                if let Some(on_disk) = found {
+                    mismatches.push(CaseMismatch {
+                        file: file.clone(),
+                        line: edge.line,
+                        column: edge.column,
+                        fixed_specifier: fix_specifier_case(&edge.specifier, &on_disk),
+                        specifier: edge.specifier.clone(),
+                        on_disk,
+                    });
+                }
+            }
+        }
+
+        mismatches
+    }
+
+    pub fn reachable_from(&self, entries: &[PathBuf], max_depth: Option<usize>) -> BTreeMap<PathBuf, usize> {
+        let mut depths = BTreeMap::new();
+        let mut queue = VecDeque::new();
+
+        for entry in entries {
+            let entry = PathUtils::absolute(entry);
+            if self.modules.contains_key(&entry) && !depths.contains_key(&entry) {
+                depths.insert(entry.clone(), 0);
+                queue.push_back(entry);
+            }
+        }
+
+        while let Some(current) = queue.pop_front() {
+            let depth = depths[&current];
+            if max_depth.is_some_and(|max| depth >= max) {
+                continue;
+            }
+
+            for dependency in self.dependencies(&current) {
+                if !depths.contains_key(dependency) {
+                    depths.insert(dependency.to_path_buf(), depth + 1);
+                    queue.push_back(dependency.to_path_buf());
+                }
+            }
+        }
+
+        depths
+    }
+
+    pub fn unreachable_from(&self, entries: &[PathBuf]) -> Vec<&Path> {
+        let reachable = self.reachable_from(entries, None);
+        self.modules
+            .keys()
+            .filter(|path| !reachable.contains_key(*path))
+            .filter(|path| match (split_variant(path), self.platform.as_deref()) {
+                (Some((_, variant)), Some(platform)) => variant == platform,
+                _ => true,
+            })
+            .map(PathBuf::as_path)
+            .collect()
+    }
+
+    pub fn cycles(&self) -> Vec<Vec<PathBuf>> {
+        let nodes: Vec<&PathBuf> = self.modules.keys().collect();
+        let index_of: BTreeMap<&Path, usize> = nodes.iter().enumerate().map(|(i, p)| (p.as_path(), i)).collect();
+        let adjacency: Vec<Vec<usize>> = nodes
+            .iter()
+            .map(|path| {
+                self.dependencies(path)
+                    .into_iter()
+                    .filter_map(|dep| index_of.get(dep).copied())
+                    .collect()
+            })
+            .collect();
+
+        let count = nodes.len();
+        let mut index = vec![usize::MAX; count];
+        let mut lowlink = vec![0; count];
+        let mut on_stack = vec![false; count];
+        let mut stack = Vec::new();
+        let mut next_index = 0;
+        let mut components = Vec::new();
+
+        for start in 0..count {
+            if index[start] != usize::MAX {
+                continue;
+            }
+
+            let mut work: Vec<(usize, usize)> = vec![(start, 0)];
+            index[start] = next_index;
+            lowlink[start] = next_index;
+            next_index += 1;
+            stack.push(start);
+            on_stack[start] = true;
+
+            while let Some((node, edge)) = work.pop() {
+                if edge < adjacency[node].len() {
+                    work.push((node, edge + 1));
+                    let next = adjacency[node][edge];
+
+                    if index[next] == usize::MAX {
+                        index[next] = next_index;
+                        lowlink[next] = next_index;
+                        next_index += 1;
+                        stack.push(next);
+                        on_stack[next] = true;
+                        work.push((next, 0));
+                    } else if on_stack[next] {
+                        lowlink[node] = lowlink[node].min(index[next]);
+                    }
+                    continue;
+                }
+
+                if let Some(&(parent, _)) = work.last() {
+                    lowlink[parent] = lowlink[parent].min(lowlink[node]);
+                }
+
+                if lowlink[node] == index[node] {
+                    let mut component = Vec::new();
+                    while let Some(member) = stack.pop() {
+                        on_stack[member] = false;
+                        component.push(member);
+                        if member == node {
+                            break;
+                        }
+                    }
+
+                    let self_loop = adjacency[node].contains(&node);
+                    if component.len() > 1 || self_loop {
+                        component.sort();
+                        components.push(component.into_iter().map(|i| nodes[i].clone()).collect());
+                    }
+                }
+            }
+        }
+
+        components
+    }
+
+    pub fn cycle_members(&self) -> BTreeSet<PathBuf> {
+        self.cycles().into_iter().flatten().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_imports_ignores_strings_and_comments() {
+        let source = "use { a } from \"./a.ns\";\n// use b from \"./b.ns\";\nfixed s = \"use c from './c.ns'\";\nuse \"./d\";\nshare { e } from \"./e.js\";\nfixed f = hold use(\"./f.ns\");\nloader.use(\"./g.ns\");\n";
+        let specifiers: Vec<(String, bool)> = find_imports(source).into_iter().map(|i| (i.specifier, i.dynamic)).collect();
+        assert_eq!(
+            specifiers,
+            vec![
+                ("./a.ns".to_string(), false),
+                ("./d".to_string(), false),
+                ("./e.js".to_string(), false),
+                ("./f.ns".to_string(), true),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_find_imports_follows_reexports() {
+        let source = "share * from \"./a.ns\";\nshare * as b from './b';\nshare { c as d, default as e } from \"./c.js\";\nshare {\n    f,\n} from './f.ns';\nshare { g };\n";
+        let specifiers: Vec<String> = find_imports(source).into_iter().map(|i| i.specifier).collect();
+        assert_eq!(specifiers, vec!["./a.ns", "./b", "./c.js", "./f.ns"]);
+    }
+
+    #[test]
+    fn test_module_graph_detects_cycles() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("a.ns"), "use { b } from \"./b\";\n").unwrap();
+        std::fs::write(dir.path().join("b.ns"), "use { a } from \"./a.js\";\nuse fs from \"fs\";\n").unwrap();
+        std::fs::write(dir.path().join("c.ns"), "use { a } from \"./a.ns\";\n").unwrap();
+
+        let graph = ModuleGraph::build(dir.path(), &ResolveOptions::default()).unwrap();
+        let cycles = graph.cycles();
+        assert_eq!(cycles.len(), 1);
+        let names: Vec<String> = cycles[0].iter().map(|p| graph.display_path(p)).collect();
+        assert_eq!(names, vec!["a.ns", "b.ns"]);
+
+        let reachable = graph.reachable_from(&[dir.path().join("c.ns")], Some(1));
+        assert_eq!(reachable.len(), 2);
+
+        let unused = graph.unreachable_from(&[dir.path().join("a.ns")]);
+        assert_eq!(unused, vec![PathUtils::absolute(&dir.path().join("c.ns"))]);
+    }
+
+    #[test]
+    fn test_case_mismatches_suggest_on_disk_casing() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("Utils/Strings")).unwrap();
+        std::fs::write(dir.path().join("Utils/Strings/index.ns"), "share fixed pad = 1;\n").unwrap();
+        std::fs::write(dir.path().join("Utils/Helper.ns"), "share fixed help = 1;\n").unwrap();
+        std::fs::write(
+            dir.path().join("main.ns"),
+            "use { help } from \"./utils/helper.js\";\nuse { pad } from \"./Utils/strings\";\nuse { help } from \"./Utils/Helper.ns\";\n",
+        )
+        .unwrap();
+
+        let graph = ModuleGraph::build(dir.path(), &ResolveOptions::default()).unwrap();
+        let fixes: Vec<(u32, String, String)> = graph
+            .case_mismatches()
+            .into_iter()
+            .map(|m| (m.line, m.specifier, m.fixed_specifier))
+            .collect();
+        assert_eq!(
+            fixes,
+            vec![
+                (1, "./utils/helper.js".to_string(), "./Utils/Helper.js".to_string()),
+                (2, "./Utils/strings".to_string(), "./Utils/Strings".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_rooted_specifiers_use_root_dir() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("src/lib")).unwrap();
+        std::fs::write(dir.path().join("src/lib/util.ns"), "share fixed a = 1;\n").unwrap();
+
+        let options = ResolveOptions {
+            platform: None,
+            root_dir: Some(dir.path().join("src")),
+            aliases: PathAliases::new(dir.path(), &BTreeMap::from([("@lib/*".to_string(), vec!["src/lib/*".to_string()])])),
+        };
+        let target = resolve_specifier(&dir.path().join("src/app/main.ns"), "/lib/util", &options);
+        assert_eq!(target, ImportTarget::Local(PathUtils::normalize(&dir.path().join("src/lib/util.ns"))));
+
+        let aliased = resolve_specifier(&dir.path().join("src/app/main.ns"), "@lib/util", &options);
+        assert_eq!(aliased, target);
+        let missing = resolve_specifier(&dir.path().join("src/app/main.ns"), "@lib/gone", &options);
+        assert_eq!(missing, ImportTarget::Missing(dir.path().join("src/lib/gone")));
+    }
+}
@@ -0,0 +1,102 @@
+use crate::core::keywords::{console_methods, KEYWORDS, RUNTIME_SHIM_ALIASES};
+use std::path::PathBuf;
+
+pub const RUNTIME_SPECIFIER: &str = "nullscript/runtime";
+
+pub fn shims(keyword: &str) -> bool {
+    RUNTIME_SHIM_ALIASES.contains(&keyword) || console_methods().iter().any(|(alias, _)| *alias == keyword)
+}
+
+pub fn package_files() -> Vec<(PathBuf, String)> {
+    let package = PathBuf::from("node_modules").join("nullscript");
+    let manifest = r#"{
+  "name": "nullscript",
+  "private": true,
+  "description": "NullScript runtime aliases, generated by nsc --runtime-shim",
+  "exports": {
+    "./runtime": {
+      "import": "./runtime.mjs",
+      "require": "./runtime.cjs"
+    }
+  }
+}
+"#;
+
+    vec![
+        (package.join("package.json"), manifest.to_string()),
+        (package.join("runtime.mjs"), module_source("export const ", "")),
+        (
+            package.join("runtime.cjs"),
+            module_source("const ", &format!("\nmodule.exports = {{ {} }};\n", RUNTIME_SHIM_ALIASES.join(", "))),
+        ),
+    ]
+}
+
+fn module_source(declare: &str, footer: &str) -> String {
+    let mut source = String::from("// Generated by nsc. Aliases used by builds with --runtime-shim.\n\n");
+
+    source.push_str(&format!("{}speak = Object.assign(Object.create(console), {{\n", declare));
+    for (alias, method) in console_methods().iter().filter(|(alias, method)| alias != method) {
+        source.push_str(&format!("    {}: (...args) => console.{}(...args),\n", alias, method));
+    }
+    source.push_str("});\n");
+
+    for alias in RUNTIME_SHIM_ALIASES.iter().filter(|alias| **alias != "speak") {
+        if let Some((_, global)) = KEYWORDS.iter().find(|(keyword, _)| keyword == alias) {
+            source.push_str(&format!("{}{} = {};\n", declare, alias, global));
+        }
+    }
+
+    source.push_str(footer);
+    source
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compiler::options::{ModuleFormat, TranspileOptions};
+    use crate::compiler::NullScriptTranspiler;
+
+    #[test]
+    fn test_runtime_shim_imports_aliases_instead_of_rewriting() {
+        let source = "fixed greeter = { say: (x) => x };\nspeak.say(greeter.say(\"hi\"), maths.max(1, 2));\n";
+        let options = TranspileOptions::new().with_runtime_shim(true);
+        let transpiler = NullScriptTranspiler::new().with_options(options.clone());
+        assert_eq!(
+            transpiler.transpile(source).unwrap(),
+            "import { speak, maths } from \"nullscript/runtime\"; const greeter = { say: (x) => x };\nspeak.say(greeter.say(\"hi\"), maths.max(1, 2));\n"
+        );
+
+        let cjs = NullScriptTranspiler::new().with_options(options.with_module(ModuleFormat::Cjs));
+        assert_eq!(
+            cjs.transpile("speak.yell(1);\n").unwrap(),
+            "const { speak } = require(\"nullscript/runtime\"); speak.yell(1);\n"
+        );
+
+        let files = package_files();
+        let esm = &files.iter().find(|(path, _)| path.ends_with("runtime.mjs")).unwrap().1;
+        assert!(esm.contains("    say: (...args) => console.log(...args),\n"));
+        assert!(esm.contains("export const maths = Math;\n"));
+        assert_eq!(console_methods().len(), crate::core::keywords::CONSOLE_METHODS.len());
+    }
+
+    #[cfg(feature = "build")]
+    #[tokio::test]
+    async fn test_single_file_build_writes_runtime_package() {
+        let dir = tempfile::tempdir().unwrap();
+        let ns_path = dir.path().join("a.ns");
+        let js_path = dir.path().join("out").join("a.mjs");
+        std::fs::write(&ns_path, "speak.say(maths.max(1, 2));\n").unwrap();
+
+        let transpiler = NullScriptTranspiler::new().with_options(TranspileOptions::new().with_runtime_shim(true));
+        transpiler.transpile_to_js(&ns_path, &js_path).await.unwrap();
+        transpiler.write_runtime(&mut crate::compiler::sink::FsSink, &dir.path().join("out")).await.unwrap();
+        assert!(dir.path().join("out/node_modules/nullscript/runtime.mjs").is_file());
+
+        let Ok(run) = std::process::Command::new("node").arg(&js_path).output() else {
+            return;
+        };
+        assert!(run.status.success(), "{}", String::from_utf8_lossy(&run.stderr));
+        assert_eq!(String::from_utf8_lossy(&run.stdout), "2\n");
+    }
+}
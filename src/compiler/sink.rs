@@ -0,0 +1,116 @@
+use crate::compiler::manifest::{BuildManifest, ManifestEntry, MANIFEST_FILE};
+use crate::core::NullScriptError;
+use crate::utils::paths::PathUtils;
+use std::collections::BTreeMap;
+use std::future::Future;
+use std::path::{Path, PathBuf};
+use tokio::fs;
+
+pub trait OutputSink: Send {
+    fn write(&mut self, path: &Path, contents: String) -> impl Future<Output = Result<(), NullScriptError>> + Send;
+}
+
+pub struct FsSink;
+
+impl OutputSink for FsSink {
+    async fn write(&mut self, path: &Path, contents: String) -> Result<(), NullScriptError> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).await?;
+        }
+
+        fs::write(path, contents).await?;
+        Ok(())
+    }
+}
+
+#[derive(Default)]
+pub struct MemorySink {
+    files: BTreeMap<PathBuf, String>,
+}
+
+impl MemorySink {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn files(&self) -> &BTreeMap<PathBuf, String> {
+        &self.files
+    }
+
+    pub fn into_files(self) -> BTreeMap<PathBuf, String> {
+        self.files
+    }
+}
+
+impl OutputSink for MemorySink {
+    async fn write(&mut self, path: &Path, contents: String) -> Result<(), NullScriptError> {
+        self.files.insert(path.to_path_buf(), contents);
+        Ok(())
+    }
+}
+
+pub struct ManifestSink<S> {
+    inner: S,
+    out_dir: PathBuf,
+    manifest: BuildManifest,
+}
+
+impl<S: OutputSink> ManifestSink<S> {
+    pub fn new(inner: S, out_dir: &Path, manifest: BuildManifest) -> Self {
+        Self {
+            inner,
+            out_dir: out_dir.to_path_buf(),
+            manifest,
+        }
+    }
+
+    pub fn record(&mut self, entry: ManifestEntry) {
+        self.manifest.files.push(entry);
+    }
+
+    pub fn rename_outputs(&mut self, names: &BTreeMap<PathBuf, PathBuf>) {
+        let relative = |path: &Path| PathUtils::relative_to(path, &self.out_dir).map(|p| PathUtils::to_slash(&p)).unwrap_or_default();
+        let names: BTreeMap<String, String> = names.iter().map(|(original, renamed)| (relative(original), relative(renamed))).collect();
+
+        for entry in &mut self.manifest.files {
+            if let Some(renamed) = names.get(&entry.output) {
+                entry.output = renamed.clone();
+            }
+        }
+        self.manifest.names = names;
+    }
+
+    pub async fn finish(mut self) -> Result<S, NullScriptError> {
+        let content = serde_json::to_string_pretty(&self.manifest)?;
+        self.inner.write(&self.out_dir.join(MANIFEST_FILE), content + "\n").await?;
+        Ok(self.inner)
+    }
+}
+
+impl<S: OutputSink> OutputSink for ManifestSink<S> {
+    async fn write(&mut self, path: &Path, contents: String) -> Result<(), NullScriptError> {
+        self.inner.write(path, contents).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_manifest_sink_writes_through() {
+        let mut sink = ManifestSink::new(MemorySink::new(), Path::new("dist"), BuildManifest::new());
+        sink.write(Path::new("dist/app.js"), "const a = 1;\n".to_string()).await.unwrap();
+        sink.record(ManifestEntry {
+            source: "app.ns".to_string(),
+            output: "app.js".to_string(),
+        });
+
+        let memory = sink.finish().await.unwrap();
+        assert_eq!(memory.files()[Path::new("dist/app.js")], "const a = 1;\n");
+
+        let manifest: BuildManifest = serde_json::from_str(&memory.files()[&Path::new("dist").join(MANIFEST_FILE)]).unwrap();
+        assert_eq!(manifest.files.len(), 1);
+        assert!(manifest.is_current());
+    }
+}
@@ -0,0 +1,327 @@
+use crate::compiler::manifest::KeywordTableInfo;
+use crate::compiler::patterns;
+use crate::core::keywords::{JS_RESERVED_WORDS, KEYWORDS};
+use crate::utils::paths::PathUtils;
+use regex::{Captures, Regex};
+use serde_json::json;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
+
+const BASE64: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+const ANCHOR_WINDOW: usize = 64;
+
+static KEYWORD_WORDS: LazyLock<HashSet<&'static str>> = LazyLock::new(|| {
+    KEYWORDS
+        .iter()
+        .flat_map(|(keyword, js)| [*keyword, *js])
+        .flat_map(|text| text.split(|c: char| !(c.is_alphanumeric() || c == '_' || c == '$')))
+        .chain(JS_RESERVED_WORDS.iter().copied())
+        .filter(|word| !word.is_empty())
+        .collect()
+});
+
+pub struct SourceMap {
+    pub source: PathBuf,
+    pub generated: PathBuf,
+    lines: Vec<Option<u32>>,
+    columns: Vec<Vec<TokenColumn>>,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct TokenColumn {
+    generated: u32,
+    source: u32,
+    width: u32,
+}
+
+impl SourceMap {
+    pub fn line_preserving(source: &Path, generated: &Path, source_text: &str, generated_text: &str) -> Self {
+        let source_tokens = line_tokens(source_text);
+        let generated_tokens = line_tokens(generated_text);
+        let source_keys: Vec<String> = source_tokens.iter().map(|tokens| line_key(tokens)).collect();
+        let generated_keys: Vec<String> = generated_tokens.iter().map(|tokens| line_key(tokens)).collect();
+        let source_lines = source_keys.len().max(1) as u32;
+
+        let mut anchors: Vec<Option<u32>> = vec![None; generated_keys.len().max(1)];
+        let mut columns = vec![Vec::new(); anchors.len()];
+        let mut cursor = 0;
+        for (generated_line, key) in generated_keys.iter().enumerate() {
+            if key.is_empty() {
+                continue;
+            }
+            let mut window = source_keys.iter().enumerate().skip(cursor).take(ANCHOR_WINDOW);
+            if let Some((source_line, _)) = window.find(|(_, candidate)| *candidate == key) {
+                anchors[generated_line] = Some(source_line as u32 + 1);
+                columns[generated_line] = generated_tokens[generated_line]
+                    .iter()
+                    .zip(&source_tokens[source_line])
+                    .map(|(generated, source)| TokenColumn {
+                        generated: generated.column,
+                        source: source.column,
+                        width: generated.text.chars().count() as u32,
+                    })
+                    .collect();
+                cursor = source_line + 1;
+            }
+        }
+
+        let mut next_anchor = vec![source_lines + 1; anchors.len()];
+        let mut next = source_lines + 1;
+        for (index, anchor) in anchors.iter().enumerate().rev() {
+            next = anchor.unwrap_or(next);
+            next_anchor[index] = next;
+        }
+
+        let mut lines = Vec::with_capacity(anchors.len());
+        let mut previous = (0u32, 0u32);
+        for (index, anchor) in anchors.iter().enumerate() {
+            let line = match anchor {
+                Some(line) => {
+                    previous = (index as u32, *line);
+                    *line
+                }
+                None => {
+                    let next = next_anchor[index];
+                    let guess = previous.1 + (index as u32 - previous.0).max(1);
+                    guess.min(next.saturating_sub(1).max(previous.1)).clamp(1, source_lines)
+                }
+            };
+            lines.push(Some(line));
+        }
+
+        Self {
+            source: source.to_path_buf(),
+            generated: generated.to_path_buf(),
+            lines,
+            columns,
+        }
+    }
+
+    pub fn original_line(&self, generated_line: u32) -> Option<u32> {
+        self.lines.get(generated_line.checked_sub(1)? as usize).copied().flatten()
+    }
+
+    pub fn original_column(&self, generated_line: u32, generated_column: u32) -> Option<u32> {
+        let columns = self.columns.get(generated_line.checked_sub(1)? as usize)?;
+        if let Some(token) = columns
+            .iter()
+            .rev()
+            .find(|token| token.generated <= generated_column && generated_column < token.generated + token.width)
+        {
+            return Some(token.source + generated_column - token.generated);
+        }
+
+        let token = columns
+            .iter()
+            .find(|token| token.generated > generated_column)
+            .or_else(|| columns.last())?;
+        Some((token.source as i64 + generated_column as i64 - token.generated as i64).max(1) as u32)
+    }
+
+    pub fn insert_unmapped(&mut self, at: u32, count: u32) {
+        let at = (at as usize).min(self.lines.len());
+        self.lines.splice(at..at, std::iter::repeat_n(None, count as usize));
+        self.columns.splice(at..at, std::iter::repeat_n(Vec::new(), count as usize));
+    }
+
+    pub fn mappings(&self) -> String {
+        let mut previous = 1i64;
+        self.lines
+            .iter()
+            .map(|line| {
+                let Some(line) = line else {
+                    return String::new();
+                };
+                let delta = *line as i64 - previous;
+                previous = *line as i64;
+                format!("AA{}A", encode_vlq(delta))
+            })
+            .collect::<Vec<_>>()
+            .join(";")
+    }
+
+    pub fn to_json(&self, map_path: &Path) -> String {
+        let map_dir = map_path.parent().unwrap_or(Path::new(""));
+        let source = PathUtils::relative_to(&self.source, map_dir)
+            .or_else(|| relative_with_parents(&self.source, map_dir))
+            .map(|p| PathUtils::to_slash(&p))
+            .unwrap_or_else(|| PathUtils::to_slash(&self.source));
+        let file = self
+            .generated
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default();
+
+        let document = json!({
+            "version": 3,
+            "file": file,
+            "sources": [source],
+            "names": [],
+            "mappings": self.mappings(),
+            "x_nullscript": {
+                "keywordTable": KeywordTableInfo::current(),
+            },
+        });
+
+        serde_json::to_string(&document).unwrap_or_default()
+    }
+
+    pub fn rewrite_stack(&self, text: &str) -> String {
+        let Ok(position) = patterns::get("stack_frame_position") else {
+            return text.to_string();
+        };
+        let generated = self.generated.to_string_lossy();
+        let Ok(frame) = Regex::new(&format!("{}{}", regex::escape(&generated), position.as_str())) else {
+            return text.to_string();
+        };
+
+        let source = self.source.to_string_lossy();
+        frame
+            .replace_all(text, |caps: &Captures| {
+                let column = caps.get(2).map_or("", |column| column.as_str());
+                match caps[1].parse().ok().and_then(|line| self.original_line(line)) {
+                    Some(line) => format!("{}:{}{}", source, line, column),
+                    None => source.to_string(),
+                }
+            })
+            .into_owned()
+    }
+}
+
+struct LineToken<'a> {
+    column: u32,
+    text: &'a str,
+}
+
+fn line_tokens(text: &str) -> Vec<Vec<LineToken<'_>>> {
+    let Ok(token) = patterns::get("line_token") else {
+        return text.lines().map(|_| Vec::new()).collect();
+    };
+
+    text.lines()
+        .map(|line| {
+            token
+                .find_iter(line)
+                .filter(|found| !KEYWORD_WORDS.contains(found.as_str()))
+                .map(|found| LineToken {
+                    column: line[..found.start()].chars().count() as u32 + 1,
+                    text: found.as_str(),
+                })
+                .collect()
+        })
+        .collect()
+}
+
+fn line_key(tokens: &[LineToken]) -> String {
+    tokens.iter().map(|token| token.text).collect::<Vec<_>>().join(" ")
+}
+
+fn encode_vlq(value: i64) -> String {
+    let mut vlq = if value < 0 { ((-value) << 1) | 1 } else { value << 1 };
+    let mut encoded = String::new();
+
+    loop {
+        let mut digit = (vlq & 0b11111) as usize;
+        vlq >>= 5;
+        if vlq > 0 {
+            digit |= 0b100000;
+        }
+        encoded.push(BASE64[digit] as char);
+        if vlq == 0 {
+            return encoded;
+        }
+    }
+}
+
+fn relative_with_parents(path: &Path, base: &Path) -> Option<PathBuf> {
+    let path = PathUtils::absolute(path);
+    let base = PathUtils::absolute(base);
+    let common = path
+        .ancestors()
+        .find(|ancestor| base.starts_with(ancestor))?;
+
+    let mut relative = PathBuf::new();
+    for _ in base.strip_prefix(common).ok()?.components() {
+        relative.push("..");
+    }
+    relative.push(path.strip_prefix(common).ok()?);
+    Some(relative)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rewrite_stack_points_at_source() {
+        let map = SourceMap::line_preserving(
+            Path::new("/app/hello.ns"),
+            Path::new("/app/hello.temp.js"),
+            "fixed a = 1;\ntrigger fresh fail(\"boom\");\n",
+            "const a = 1;\nthrow new Error(\"boom\");\n",
+        );
+
+        let stderr = "/app/hello.temp.js:2\nthrow new Error(\"boom\");\n    at Object.<anonymous> (/app/hello.temp.js:2:7)\n    at node:internal/main:1:1\n";
+        let rewritten = map.rewrite_stack(stderr);
+
+        assert!(rewritten.starts_with("/app/hello.ns:2\n"));
+        assert!(rewritten.contains("(/app/hello.ns:2:7)"));
+        assert!(rewritten.contains("node:internal/main:1:1"));
+    }
+
+    #[test]
+    fn test_line_preserving_follows_moved_lines() {
+        let map = SourceMap::line_preserving(
+            Path::new("/app/hello.ns"),
+            Path::new("/app/hello.temp.js"),
+            "fixed a = 1;\n\n// greet\nspeak.say(a);\nwhatever (a more 0) {\n    trigger fresh fail(\"boom\");\n}\n",
+            "const a = 1;\nconsole.log(a);\nconst __helper = 0;\nif (a > 0) {\n    throw new Error(\"boom\");\n}\n",
+        );
+
+        let lines: Vec<Option<u32>> = (1..=6).map(|line| map.original_line(line)).collect();
+        assert_eq!(lines, vec![Some(1), Some(4), Some(4), Some(5), Some(6), Some(7)]);
+
+        let stderr = "    at Object.<anonymous> (/app/hello.temp.js:5:11)\n";
+        assert_eq!(map.rewrite_stack(stderr), "    at Object.<anonymous> (/app/hello.ns:6:11)\n");
+    }
+
+    #[test]
+    fn test_original_column_follows_shared_tokens() {
+        let mut map = SourceMap::line_preserving(
+            Path::new("/app/hello.ns"),
+            Path::new("/app/hello.temp.js"),
+            "fixed total = add(1, \"two\");\nwhatever (total more 0) {\n}\n",
+            "const total = add(1, \"two\");\nif (total > 0) {\n}\n",
+        );
+
+        assert_eq!(map.original_column(1, 22), Some(22));
+        assert_eq!(map.original_column(1, 7), Some(7));
+        assert_eq!(map.original_column(1, 1), Some(1));
+        assert_eq!(map.original_column(2, 5), Some(11));
+        assert_eq!(map.original_column(2, 13), Some(22));
+        assert_eq!(map.original_column(3, 1), None);
+
+        map.insert_unmapped(0, 1);
+        assert_eq!(map.original_column(1, 5), None);
+        assert_eq!(map.original_column(3, 5), Some(11));
+    }
+
+    #[test]
+    fn test_mappings_and_json() {
+        let map = SourceMap::line_preserving(
+            Path::new("/app/src/main.ns"),
+            Path::new("/app/dist/main.js"),
+            "a\nb\n",
+            "a\nb\nc\n",
+        );
+
+        assert_eq!(map.mappings(), "AAAA;AACA;AAAA");
+        assert_eq!(encode_vlq(-17), "jB");
+
+        let json: serde_json::Value = serde_json::from_str(&map.to_json(Path::new("/app/dist/main.js.map"))).unwrap();
+        assert_eq!(json["sources"][0], "../src/main.ns");
+        assert_eq!(json["file"], "main.js");
+    }
+}
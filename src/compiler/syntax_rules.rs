@@ -0,0 +1,752 @@
+use crate::compiler::lexer::{Lexer, Token, TokenKind};
+use crate::compiler::patterns;
+use crate::core::keywords::{SyntaxContext, SyntaxRule, INVALID_SYNTAX, JS_RESERVED_WORDS, KEYWORDS};
+use crate::core::config::DiagnosticLevel;
+use crate::core::NullScriptError;
+static MODULE_KEYWORDS: &[&str] = &["use", "share", "import", "export"];
+
+pub(crate) static CONTROL_KEYWORDS: &[&str] = &["whatever", "if", "when", "while", "since", "for", "switch", "grab", "catch", "with", "using"];
+
+#[derive(Debug)]
+pub struct Violation {
+    pub rule: &'static SyntaxRule,
+    pub text: String,
+    pub line: u32,
+    pub column: u32,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Group {
+    Parameters,
+    Class,
+    Other,
+}
+
+pub fn check(source: &str) -> Result<Vec<Violation>, NullScriptError> {
+    let tokens: Vec<Token> = Lexer::new(source).with_max_depth(usize::MAX).tokenize()?;
+    let (view, offsets) = code_view(&tokens);
+    let parameters = parameter_lists(&tokens);
+
+    let mut violations = Vec::new();
+    let mut groups: Vec<Group> = Vec::new();
+    let mut previous: Option<&Token> = None;
+    let mut newline = true;
+    let mut module_statement: Option<usize> = None;
+    let mut class_header: Option<usize> = None;
+    let mut closed_parameters = false;
+
+    for (index, token) in tokens.iter().enumerate() {
+        if token.is_trivia() {
+            newline |= token.text.contains('\n');
+            continue;
+        }
+
+        let statement_start = newline || previous.is_none_or(|previous| matches!(previous.text, ";" | "{" | "}"));
+        if statement_start && module_statement.is_none_or(|depth| groups.len() <= depth) {
+            module_statement = MODULE_KEYWORDS.contains(&token.text).then_some(groups.len());
+        }
+        let parameter_start = groups.last() == Some(&Group::Parameters)
+            && previous.is_some_and(|previous| matches!(previous.text, "(" | ","));
+        let return_type_start = std::mem::take(&mut closed_parameters);
+
+        if matches!(token.kind, TokenKind::Identifier | TokenKind::Punct) {
+            for rule in INVALID_SYNTAX.iter() {
+                let applies = match rule.context {
+                    SyntaxContext::Statement => statement_start,
+                    SyntaxContext::ClassBody => statement_start && groups.last() == Some(&Group::Class),
+                    SyntaxContext::Parameters => parameter_start,
+                    SyntaxContext::ReturnType => return_type_start,
+                    SyntaxContext::Expression => module_statement.is_none(),
+                };
+                if !applies {
+                    continue;
+                }
+
+                let regex = patterns::syntax_rule(rule.name)?;
+                let start = offsets[index];
+                if let Some(captures) = regex.captures(&view[start..]) {
+                    let Some(found) = captures.get(1).or_else(|| captures.get(0)) else {
+                        continue;
+                    };
+                    let at = start + found.start();
+                    let owner = offsets.partition_point(|&offset| offset <= at) - 1;
+                    violations.push(Violation {
+                        rule,
+                        text: found.as_str().trim_end_matches(['(', '{', '[']).trim().to_string(),
+                        line: tokens[owner].line,
+                        column: tokens[owner].column + (at - offsets[owner]) as u32,
+                    });
+                }
+            }
+        }
+
+        if matches!(token.text, "model" | "class") && previous.is_none_or(|previous| previous.text != ".") {
+            class_header = Some(groups.len());
+        }
+
+        match token.text {
+            "(" if parameters.contains(&index) => groups.push(Group::Parameters),
+            "{" if class_header == Some(groups.len()) => {
+                class_header = None;
+                groups.push(Group::Class);
+            }
+            "(" | "[" | "{" => groups.push(Group::Other),
+            ")" | "]" | "}" => {
+                closed_parameters = groups.pop() == Some(Group::Parameters);
+            }
+            _ => {}
+        }
+        previous = Some(token);
+        newline = false;
+    }
+
+    Ok(violations)
+}
+
+pub fn yields_outside_generators(source: &str) -> Result<Vec<(u32, u32)>, NullScriptError> {
+    let tokens: Vec<Token> = Lexer::new(source).with_max_depth(usize::MAX).tokenize()?;
+    let code: Vec<&Token> = tokens.iter().filter(|token| !token.is_trivia()).collect();
+    let text = |position: usize| code.get(position).map_or("", |token| token.text);
+
+    let mut found = Vec::new();
+    let mut scopes: Vec<bool> = Vec::new();
+    let mut header: Option<(bool, usize)> = None;
+    let mut depth = 0usize;
+
+    for (position, token) in code.iter().enumerate() {
+        let previous = position.checked_sub(1).map_or("", text);
+        if token.kind == TokenKind::Identifier && previous == "." {
+            continue;
+        }
+
+        match token.text {
+            "run" | "function" if token.kind == TokenKind::Identifier => {
+                let star = if text(position + 1) == "later" { position + 2 } else { position + 1 };
+                header = Some((text(star) == "*", depth));
+            }
+            "(" | "[" => depth += 1,
+            ")" | "]" => depth = depth.saturating_sub(1),
+            "{" => {
+                let generator = match header {
+                    Some((generator, at)) if at == depth => {
+                        header = None;
+                        generator
+                    }
+                    _ if previous == ">" && position >= 2 && text(position - 2) == "=" => false,
+                    _ => scopes.last().copied().unwrap_or(false),
+                };
+                scopes.push(generator);
+            }
+            "}" => {
+                scopes.pop();
+            }
+            "pause" if token.kind == TokenKind::Identifier
+                && previous != "run"
+                && text(position + 1) != ":"
+                && !scopes.last().copied().unwrap_or(false) =>
+            {
+                found.push((token.line, token.column));
+            }
+            _ => {}
+        }
+    }
+
+    Ok(found)
+}
+
+pub fn label_errors(source: &str) -> Result<Vec<(u32, u32, String)>, NullScriptError> {
+    let tokens: Vec<Token> = Lexer::new(source).with_max_depth(usize::MAX).tokenize()?;
+    let code: Vec<&Token> = tokens.iter().filter(|token| !token.is_trivia()).collect();
+    let text = |position: usize| code.get(position).map_or("", |token| token.text);
+
+    let mut errors = Vec::new();
+    let mut labels: Vec<(&str, usize)> = Vec::new();
+    let mut pending: Vec<&str> = Vec::new();
+    let mut braces = 0usize;
+    let mut parens = 0usize;
+
+    for (position, token) in code.iter().enumerate() {
+        let previous = position.checked_sub(1).map_or("", text);
+        let statement_start = matches!(previous, "" | ";" | "{" | "}");
+
+        match token.text {
+            "mark" if statement_start && code.get(position + 1).is_some_and(|t| t.kind == TokenKind::Identifier) && text(position + 2) == ":" => {
+                let name = code[position + 1];
+                if KEYWORDS.iter().any(|(keyword, _)| *keyword == name.text) || JS_RESERVED_WORDS.contains(&name.text) {
+                    errors.push((
+                        name.line,
+                        name.column,
+                        format!("Label '{}' is a reserved word and would be rewritten; pick another name", name.text),
+                    ));
+                }
+                pending.push(name.text);
+            }
+            _ if statement_start
+                && token.kind == TokenKind::Identifier
+                && text(position + 1) == ":"
+                && matches!(text(position + 2), "since" | "when" | "do" | "switch" | "{") =>
+            {
+                pending.push(token.text);
+            }
+            "stop" | "keepgoing" if token.kind == TokenKind::Identifier && previous != "." => {
+                let Some(target) = code.get(position + 1).filter(|next| next.kind == TokenKind::Identifier && next.line == token.line) else {
+                    continue;
+                };
+                let declared = labels.iter().map(|(name, _)| *name).chain(pending.iter().copied()).any(|name| name == target.text);
+                if !declared {
+                    errors.push((
+                        target.line,
+                        target.column,
+                        format!("'{} {}' refers to a label that does not enclose it; declare it with 'mark {}:'", token.text, target.text, target.text),
+                    ));
+                }
+            }
+            "(" | "[" => parens += 1,
+            ")" | "]" => parens = parens.saturating_sub(1),
+            "{" => {
+                labels.extend(pending.drain(..).map(|name| (name, braces)));
+                braces += 1;
+            }
+            "}" => {
+                braces = braces.saturating_sub(1);
+                labels.retain(|(_, depth)| *depth < braces);
+            }
+            ";" if parens == 0 => pending.clear(),
+            _ => {}
+        }
+    }
+
+    Ok(errors)
+}
+
+pub fn invalid_catch_bindings(source: &str) -> Result<Vec<(u32, u32)>, NullScriptError> {
+    let tokens: Vec<Token> = Lexer::new(source).with_max_depth(usize::MAX).tokenize()?;
+    let code: Vec<&Token> = tokens.iter().filter(|token| !token.is_trivia()).collect();
+    let text = |position: usize| code.get(position).map_or("", |token| token.text);
+
+    Ok(code
+        .iter()
+        .enumerate()
+        .filter(|(position, token)| {
+            token.text == "grab"
+                && token.kind == TokenKind::Identifier
+                && text(position + 1) == "("
+                && (text(position + 2) == ")" || code.get(position + 2).is_some_and(|t| t.kind == TokenKind::Identifier) && text(position + 3) == ",")
+        })
+        .map(|(position, token)| (token.line, token.column + (code[position + 1].start - token.start) as u32))
+        .collect())
+}
+
+pub fn destructured_names(source: &str) -> Result<Vec<(usize, &str)>, NullScriptError> {
+    let tokens: Vec<Token> = Lexer::new(source).with_max_depth(usize::MAX).tokenize()?;
+    let code: Vec<&Token> = tokens.iter().filter(|token| !token.is_trivia()).collect();
+
+    let mut names = Vec::new();
+    for (position, token) in code.iter().enumerate() {
+        let member = position > 0 && code[position - 1].text == ".";
+        if token.kind == TokenKind::Identifier
+            && !member
+            && matches!(token.text, "let" | "fixed" | "var")
+            && code.get(position + 1).is_some_and(|next| matches!(next.text, "{" | "["))
+        {
+            binding_pattern(&code, position + 1, &mut names);
+        }
+    }
+
+    Ok(names)
+}
+
+pub fn duplicate_module_names(source: &str) -> Result<Vec<(u32, u32, String, DiagnosticLevel)>, NullScriptError> {
+    let tokens: Vec<Token> = Lexer::new(source).with_max_depth(usize::MAX).tokenize()?;
+    let code: Vec<&Token> = tokens.iter().filter(|token| !token.is_trivia()).collect();
+    let text = |position: usize| code.get(position).map_or("", |token| token.text);
+
+    let mut found = Vec::new();
+    let mut exported: Vec<(&str, u32)> = Vec::new();
+    let mut locals: Vec<(&str, u32)> = Vec::new();
+    let mut modules: Vec<(String, u32)> = Vec::new();
+    let mut symbols: Vec<(String, &str, &str)> = Vec::new();
+
+    for (position, token) in code.iter().enumerate() {
+        let statement_start = position == 0 || matches!(text(position - 1), ";" | "{" | "}") || code[position - 1].line < token.line;
+        if token.kind != TokenKind::Identifier || !statement_start {
+            continue;
+        }
+
+        match token.text {
+            "use" | "import" if !matches!(text(position + 1), "(" | ".") && code.get(position + 1).is_some_and(|next| next.kind != TokenKind::String) => {
+                let Some(from) = (position + 1..code.len()).take_while(|&p| text(p) != ";").find(|&p| text(p) == "from") else {
+                    continue;
+                };
+                let Some(specifier) = code.get(from + 1).filter(|t| t.kind == TokenKind::String) else {
+                    continue;
+                };
+                let module = specifier.text[1..specifier.text.len().saturating_sub(1)].to_string();
+
+                if let Some((_, line)) = modules.iter().find(|(seen, _)| *seen == module) {
+                    found.push((
+                        specifier.line,
+                        specifier.column,
+                        format!("'{}' is already imported on line {}; merge the two imports", module, line),
+                        DiagnosticLevel::Warn,
+                    ));
+                } else {
+                    modules.push((module.clone(), specifier.line));
+                }
+
+                for (imported, local) in import_bindings(&code[position + 1..from]) {
+                    if let Some((_, line)) = locals.iter().find(|(name, _)| *name == local.text) {
+                        found.push((
+                            local.line,
+                            local.column,
+                            format!("'{}' is already imported on line {}", local.text, line),
+                            DiagnosticLevel::Error,
+                        ));
+                        continue;
+                    }
+                    locals.push((local.text, local.line));
+
+                    if let Some((.., first)) = symbols.iter().find(|(seen, name, _)| *seen == module && *name == imported) {
+                        found.push((
+                            local.line,
+                            local.column,
+                            format!("'{}' from '{}' is imported as both '{}' and '{}'", imported, module, first, local.text),
+                            DiagnosticLevel::Warn,
+                        ));
+                    }
+                    symbols.push((module.clone(), imported, local.text));
+                }
+            }
+            "share" | "export" => {
+                for name in export_names(&code, position + 1) {
+                    let exported_name = if name.text == "done" { "default" } else { name.text };
+                    match exported.iter().find(|(seen, _)| *seen == exported_name) {
+                        Some((_, line)) => found.push((
+                            name.line,
+                            name.column,
+                            format!("'{}' is already exported on line {}", exported_name, line),
+                            DiagnosticLevel::Error,
+                        )),
+                        None => exported.push((exported_name, name.line)),
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    Ok(found)
+}
+
+fn import_bindings<'a, 'b>(clause: &'b [&'b Token<'a>]) -> Vec<(&'a str, &'b Token<'a>)> {
+    let mut bindings = Vec::new();
+    let mut braces = false;
+    let mut position = 0;
+
+    while let Some(token) = clause.get(position) {
+        let aliased = clause.get(position + 1).is_some_and(|t| t.text == "as");
+        match token.text {
+            "{" => braces = true,
+            "}" => braces = false,
+            "*" | "everything" if aliased => {
+                if let Some(local) = clause.get(position + 2) {
+                    bindings.push(("*", *local));
+                }
+                position += 2;
+            }
+            _ if token.kind == TokenKind::Identifier || token.kind == TokenKind::String => {
+                let imported = if braces { token.text } else { "default" };
+                match (aliased, clause.get(position + 2)) {
+                    (true, Some(local)) => {
+                        bindings.push((imported, *local));
+                        position += 2;
+                    }
+                    _ if token.kind == TokenKind::Identifier => bindings.push((imported, *token)),
+                    _ => {}
+                }
+            }
+            _ => {}
+        }
+        position += 1;
+    }
+
+    bindings
+}
+
+fn export_names<'a, 'b>(code: &'b [&'b Token<'a>], start: usize) -> Vec<&'b Token<'a>> {
+    let text = |position: usize| code.get(position).map_or("", |token| token.text);
+    let named = |position: usize| code.get(position).filter(|token| token.kind == TokenKind::Identifier).into_iter().copied().collect();
+
+    match text(start) {
+        "default" | "done" => named(start),
+        "*" | "everything" if text(start + 1) == "as" => named(start + 2),
+        "{" => {
+            let mut names = Vec::new();
+            let mut position = start + 1;
+            while position < code.len() && text(position) != "}" {
+                if text(position + 1) == "as" {
+                    position += 2;
+                }
+                if matches!(code[position].kind, TokenKind::Identifier | TokenKind::String) {
+                    names.push(code[position]);
+                }
+                position += 1;
+            }
+            names
+        }
+        "fixed" | "let" | "var" | "const" if matches!(text(start + 1), "{" | "[") => {
+            let mut bound = Vec::new();
+            binding_pattern(code, start + 1, &mut bound);
+            code.iter().filter(|token| bound.iter().any(|(offset, _)| *offset == token.start)).copied().collect()
+        }
+        "fixed" | "let" | "var" | "const" | "model" | "class" => named(start + 1),
+        "run" | "function" | "later" | "async" => {
+            let name = (start..start + 4).find(|&p| !matches!(text(p), "run" | "function" | "later" | "async" | "*"));
+            name.map_or_else(Vec::new, named)
+        }
+        _ => Vec::new(),
+    }
+}
+
+fn binding_pattern<'a>(code: &[&Token<'a>], open: usize, names: &mut Vec<(usize, &'a str)>) -> usize {
+    let text = |position: usize| code.get(position).map_or("", |token| token.text);
+    let object = text(open) == "{";
+    let close = if object { "}" } else { "]" };
+    let mut position = open + 1;
+
+    while position < code.len() && text(position) != close {
+        let started = position;
+        while text(position) == "." {
+            position += 1;
+        }
+
+        if object && (text(position + 1) == ":" || text(position) == "[") {
+            position = if text(position) == "[" { skip_balanced(code, position) } else { position };
+            position += 2;
+        }
+
+        match code.get(position) {
+            Some(token) if matches!(token.text, "{" | "[") => position = binding_pattern(code, position, names),
+            Some(token) if token.kind == TokenKind::Identifier => {
+                names.push((token.start, token.text));
+                position += 1;
+            }
+            _ => {}
+        }
+
+        if text(position) == "=" {
+            while position < code.len() && !matches!(text(position), "," | "}" | "]") {
+                position = match text(position) {
+                    "(" | "[" | "{" => skip_balanced(code, position) + 1,
+                    _ => position + 1,
+                };
+            }
+        }
+        if text(position) == "," || position == started {
+            position += 1;
+        }
+    }
+
+    position + 1
+}
+
+fn skip_balanced(code: &[&Token], open: usize) -> usize {
+    let mut depth = 0usize;
+    for (position, token) in code.iter().enumerate().skip(open) {
+        match token.text {
+            "(" | "[" | "{" => depth += 1,
+            ")" | "]" | "}" => {
+                depth -= 1;
+                if depth == 0 {
+                    return position;
+                }
+            }
+            _ => {}
+        }
+    }
+    code.len()
+}
+
+pub fn invalid_numbers(source: &str) -> Result<Vec<(u32, u32, String, &'static str)>, NullScriptError> {
+    Ok(Lexer::new(source)
+        .with_max_depth(usize::MAX)
+        .tokenize()?
+        .into_iter()
+        .filter(|token| token.kind == TokenKind::Number)
+        .filter_map(|token| number_error(token.text).map(|reason| (token.line, token.column, token.text.to_string(), reason)))
+        .collect())
+}
+
+fn number_error(text: &str) -> Option<&'static str> {
+    let lower = text.to_ascii_lowercase();
+    let (digits, radix) = match lower.get(..2) {
+        Some("0x") => (&lower[2..], 16),
+        Some("0o") => (&lower[2..], 8),
+        Some("0b") => (&lower[2..], 2),
+        _ => (lower.as_str(), 10),
+    };
+    // The lexer keeps member access such as `0xff.toString` or `1.5.toFixed` in the number token.
+    let literal = match radix {
+        10 => digits.match_indices('.').nth(1).map_or(digits, |(index, _)| &digits[..index]),
+        _ => digits.split('.').next().unwrap_or_default(),
+    };
+    let (body, bigint) = literal.strip_suffix('n').map_or((literal, false), |body| (body, true));
+
+    if body.is_empty() {
+        return Some("a digit is required after the base prefix");
+    }
+
+    let chars: Vec<char> = body.chars().collect();
+    let separated = chars.iter().enumerate().all(|(index, &c)| {
+        c != '_' || index > 0 && chars[index - 1].is_digit(radix) && chars.get(index + 1).is_some_and(|next| next.is_digit(radix))
+    });
+    if !separated {
+        return Some("'_' separators must sit between two digits");
+    }
+    if radix == 10 && body.starts_with('0') && (body[1..].starts_with('_') || bigint && body[1..].starts_with(|c: char| c.is_ascii_digit())) {
+        return Some("a leading 0 cannot be followed by '_' or used in a BigInt literal");
+    }
+
+    if radix != 10 {
+        return (!body.chars().all(|c| c == '_' || c.is_digit(radix))).then_some("the literal contains a digit that is not valid for its base");
+    }
+
+    let (mantissa, exponent) = body.split_once('e').unwrap_or((body, "0"));
+    let exponent = exponent.strip_prefix(['+', '-']).unwrap_or(exponent);
+    if exponent.is_empty() || !exponent.chars().all(|c| c == '_' || c.is_ascii_digit()) {
+        return Some("the exponent needs at least one digit");
+    }
+    if mantissa.matches('.').count() > 1 || !mantissa.chars().all(|c| c == '_' || c == '.' || c.is_ascii_digit()) {
+        return Some("the literal contains a digit that is not valid for its base");
+    }
+    if bigint && body.contains(['.', 'e']) {
+        return Some("BigInt literals must be whole numbers without a decimal point or exponent");
+    }
+
+    None
+}
+
+fn code_view(tokens: &[Token]) -> (String, Vec<usize>) {
+    let mut view = String::new();
+    let mut offsets = Vec::with_capacity(tokens.len());
+
+    for token in tokens {
+        offsets.push(view.len());
+        match token.kind {
+            TokenKind::Whitespace | TokenKind::LineComment | TokenKind::BlockComment => {
+                if !view.ends_with(' ') {
+                    view.push(' ');
+                }
+            }
+            TokenKind::String | TokenKind::Template | TokenKind::Regex => view.push_str("\"\""),
+            _ => view.push_str(token.text),
+        }
+    }
+
+    (view, offsets)
+}
+
+fn parameter_lists(tokens: &[Token]) -> Vec<usize> {
+    let code: Vec<usize> = (0..tokens.len()).filter(|&i| !tokens[i].is_trivia()).collect();
+    let text = |position: Option<usize>| position.and_then(|p| code.get(p)).map_or("", |&i| tokens[i].text);
+
+    let mut lists = Vec::new();
+    let mut open: Vec<usize> = Vec::new();
+    for (position, &index) in code.iter().enumerate() {
+        match tokens[index].text {
+            "(" => {
+                open.push(position);
+                let before = text(position.checked_sub(1));
+                let star = if before == "*" { position.checked_sub(1) } else { position.checked_sub(2).filter(|&p| text(Some(p)) == "*") };
+                let keyword = match star {
+                    Some(star) => text(star.checked_sub(1)),
+                    None => text(position.checked_sub(2)),
+                };
+                let named = tokens[code[position.saturating_sub(1)]].kind == TokenKind::Identifier && keyword == "run";
+                if before == "run" || named || star.is_some() && matches!(keyword, "run" | "later") {
+                    lists.push(index);
+                }
+            }
+            ")" => {
+                if let Some(start) = open.pop() {
+                    let arrow = text(Some(position + 1)) == "=" && text(Some(position + 2)) == ">";
+                    let method = text(Some(past_return_type(tokens, &code, position + 1))) == "{"
+                        && start.checked_sub(1).is_some_and(|name| {
+                            tokens[code[name]].kind == TokenKind::Identifier && !CONTROL_KEYWORDS.contains(&tokens[code[name]].text)
+                        });
+                    if arrow || method {
+                        lists.push(code[start]);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    lists
+}
+
+fn past_return_type(tokens: &[Token], code: &[usize], position: usize) -> usize {
+    let text = |position: usize| code.get(position).map_or("", |&i| tokens[i].text);
+    if text(position) != ":" {
+        return position;
+    }
+
+    let mut position = position + 1;
+    let mut angles = 0usize;
+    while let Some(&index) = code.get(position) {
+        match tokens[index].text {
+            "<" => angles += 1,
+            ">" if angles > 0 => angles -= 1,
+            "," if angles > 0 => {}
+            "." | "[" | "]" | "|" => {}
+            _ if tokens[index].kind == TokenKind::Identifier => {}
+            _ => break,
+        }
+        position += 1;
+    }
+    position
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::config::DiagnosticLevel;
+
+    fn rules(source: &str) -> Vec<&'static str> {
+        check(source).unwrap().iter().map(|violation| violation.rule.name).collect()
+    }
+
+    #[test]
+    fn test_declaration_and_parameter_types() {
+        let violations = check("fixed name: string = \"a\";\n").unwrap();
+        assert_eq!((violations[0].rule.name, violations[0].text.as_str(), violations[0].column), ("declaration_type", ": string", 11));
+        assert_eq!(rules("let count : Counter;\n"), vec!["declaration_type"]);
+        assert_eq!(rules("run greet(name: string, times?: number) {}\n"), vec!["parameter_type", "parameter_type"]);
+        assert_eq!(rules("fixed add = (a: number, b) => a + b;\n"), vec!["parameter_type"]);
+
+        assert!(rules("fixed point = { x: number, y: string };\n").is_empty());
+        assert!(rules("fixed pick = ready ? first : number;\n").is_empty());
+        assert!(rules("run show({ name: label }) {}\nfixed url = \"http://host: number\";\n").is_empty());
+        assert!(rules("greet(name ? a : b);\n").is_empty());
+    }
+
+    #[test]
+    fn test_method_parameter_and_field_types() {
+        let source = "model Greeter {\n    name: string;\n    forever count?: number = 0;\n    greet(name: string) {}\n    later load(url: URL) {}\n}\n";
+        assert_eq!(rules(source), vec!["field_type", "field_type", "parameter_type", "parameter_type"]);
+        assert_eq!(rules("fixed api = { send(body: Body) {} };\n"), vec!["parameter_type"]);
+
+        let source = "model Greeter {\n    name = \"a\";\n    greet(name) {\n        whatever (ready ? a : b) {}\n        fixed point = { x: name };\n    }\n}\n";
+        assert!(rules(source).is_empty());
+    }
+
+    #[test]
+    fn test_return_types_and_annotated_defaults() {
+        let violations = check("run total(items): number {}\n").unwrap();
+        assert_eq!((violations[0].rule.name, violations[0].text.as_str(), violations[0].column), ("return_type", ": number", 17));
+        assert_eq!(rules("run later load(url) : Promise<Response> {}\n"), vec!["return_type"]);
+        assert_eq!(rules("fixed parse = run(text): Item[] | null {};\n"), vec!["return_type"]);
+        assert_eq!(rules("model Greeter {\n    greet(name): Map<string, number> {}\n}\n"), vec!["return_type"]);
+        assert_eq!(rules("fixed add = (a: number = 1, b: Item) => a;\n"), vec!["parameter_type", "parameter_type"]);
+
+        assert!(rules("switch (kind) {\n    case (a): {\n        stop;\n    }\n}\n").is_empty());
+        assert!(rules("fixed pick = ready ? (first) : second;\nfixed handlers = { load: run(a) {} };\n").is_empty());
+    }
+
+    #[test]
+    fn test_type_assertions_and_type_parameters() {
+        assert_eq!(rules("fixed id = input as string;\n"), vec!["type_assertion"]);
+        assert_eq!(rules("fixed n = <number>value;\n"), vec!["angle_assertion"]);
+        assert_eq!(rules("run identity<T>(value) {}\n"), vec!["type_parameters"]);
+        assert_eq!(rules("fixed pair = make<K extends Key, V>(a);\n"), vec!["type_parameters"]);
+
+        assert!(rules("use { parse as number } from \"./parse.ns\";\n").is_empty());
+        assert!(rules("speak.say(\"<T>\", `as string`, /<number>/);\n").is_empty());
+        assert!(rules("fixed less = a < b && c > (d);\n").is_empty());
+    }
+
+    #[test]
+    fn test_pause_only_inside_generators() {
+        let source = "run* ids(start) {\n    pause start;\n    whatever (start) { pause 1; }\n}\nrun later* pages() {\n    pause hold fetch(\"/next\");\n}\nmodel Tree {\n    run* walk({ depth }) {\n        pause depth;\n    }\n}\n";
+        assert!(yields_outside_generators(source).unwrap().is_empty());
+
+        let source = "run ids() {\n    pause 1;\n}\nrun* outer() {\n    fixed f = () => { pause 2; };\n}\nfixed options = { pause: 3 };\nplayer.pause();\n";
+        assert_eq!(yields_outside_generators(source).unwrap(), vec![(2, 5), (5, 23)]);
+        assert_eq!(rules("run* pairs(key: string) {}\n"), vec!["parameter_type"]);
+    }
+
+    #[test]
+    fn test_labels_must_enclose_stop_and_keepgoing() {
+        let source = "mark outer: since (let i = 0; i < 3; i++) {\n    mark inner: since (fixed j of list) {\n        whatever (j) { keepgoing outer; }\n        stop inner;\n    }\n}\nplain: when (yes) { stop plain; }\n";
+        assert!(label_errors(source).unwrap().is_empty());
+
+        let errors = label_errors("mark outer: since (;;) {\n    stop;\n}\nstop outer;\nmark done: when (yes) {}\n").unwrap();
+        let locations: Vec<(u32, u32)> = errors.iter().map(|(line, column, _)| (*line, *column)).collect();
+        assert_eq!(locations, vec![(4, 6), (5, 6)]);
+        assert!(errors[0].2.starts_with("'stop outer' refers to a label"));
+    }
+
+    #[test]
+    fn test_catch_takes_one_binding_or_none() {
+        let source = "test { a(); } grab { b(); }\ntest { a(); } grab (error) { b(); }\ntest { a(); } grab ({ message }) { b(message); }\n";
+        assert!(invalid_catch_bindings(source).unwrap().is_empty());
+        assert_eq!(invalid_catch_bindings("test {} grab () {}\ntest {} grab (a, b) {}\n").unwrap(), vec![(1, 14), (2, 14)]);
+    }
+
+    #[test]
+    fn test_duplicate_module_names() {
+        let source = "use fs, { readFile as read } from 'fs';\nuse { readFile } from \"fs\";\nuse everything as path from 'path';\nuse { join as path } from 'node:path';\nfixed a = 1;\nshare { a, a as b };\nshare fixed { c, d: [b] } = obj;\nshare run later load() {}\nshare run* load() {}\nshare done a;\nshare { c as default };\nuse './polyfill.js';\nuse './polyfill.js';\n";
+        let found: Vec<(u32, String, DiagnosticLevel)> = duplicate_module_names(source)
+            .unwrap()
+            .into_iter()
+            .map(|(line, _, message, level)| (line, message, level))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                (2, "'fs' is already imported on line 1; merge the two imports".to_string(), DiagnosticLevel::Warn),
+                (2, "'readFile' from 'fs' is imported as both 'read' and 'readFile'".to_string(), DiagnosticLevel::Warn),
+                (4, "'path' is already imported on line 3".to_string(), DiagnosticLevel::Error),
+                (7, "'b' is already exported on line 6".to_string(), DiagnosticLevel::Error),
+                (9, "'load' is already exported on line 8".to_string(), DiagnosticLevel::Error),
+                (11, "'default' is already exported on line 10".to_string(), DiagnosticLevel::Error),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_destructured_names() {
+        let source = "fixed {a, b: {c}, 'd': [e, , f = g(1, 2)], [key]: h, ...rest} = obj;\nfor (let [k, v] of pairs) {}\nthing.let [0];\n";
+        let names: Vec<&str> = destructured_names(source).unwrap().into_iter().map(|(_, name)| name).collect();
+        assert_eq!(names, vec!["a", "c", "e", "f", "h", "rest", "k", "v"]);
+    }
+
+    #[test]
+    fn test_invalid_number_literals() {
+        let valid = "1_000_000 123n 0n 0b1010 0o777 0xFF 0XdeadBEEFn 1.5e-3 .5 1e1_0 0xff.toString 1.5.toFixed 1..toString 0.25";
+        assert_eq!(invalid_numbers(valid).unwrap(), vec![]);
+
+        let reasons: Vec<(String, &str)> = invalid_numbers("1__0 1_ 0_1 0x_1 1._5 0b102 0x 1e 1.5n 1e3n 012n\n")
+            .unwrap()
+            .into_iter()
+            .map(|(_, _, text, reason)| (text, reason))
+            .collect();
+        assert_eq!(
+            reasons.iter().map(|(text, _)| text.as_str()).collect::<Vec<_>>(),
+            vec!["1__0", "1_", "0_1", "0x_1", "1._5", "0b102", "0x", "1e", "1.5n", "1e3n", "012n"]
+        );
+        assert_eq!(reasons[8].1, "BigInt literals must be whole numbers without a decimal point or exponent");
+        assert_eq!(reasons[10].1, "a leading 0 cannot be followed by '_' or used in a BigInt literal");
+    }
+
+    #[test]
+    fn test_decorators_only_at_statement_start() {
+        let violations = check("@Component({})\nmodel Widget {}\n").unwrap();
+        assert_eq!((violations[0].rule.name, violations[0].line, violations[0].column), ("decorator", 1, 1));
+        assert_eq!(violations[0].text, "@Component");
+        assert_eq!(violations[0].rule.level, DiagnosticLevel::Error);
+
+        assert!(rules("fixed email = \"me@Component.dev\";\n// @Injectable\n").is_empty());
+    }
+}
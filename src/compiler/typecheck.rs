@@ -0,0 +1,128 @@
+use crate::core::config::DiagnosticLevel;
+use std::path::PathBuf;
+
+pub const DEFAULT_CHECKER: &str = "tsc --noEmit --allowJs --checkJs --skipLibCheck --pretty false";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TypeFinding {
+    pub file: PathBuf,
+    pub line: u32,
+    pub column: u32,
+    pub level: DiagnosticLevel,
+    pub message: String,
+}
+
+pub fn checker_command(checker: &str) -> (String, Vec<String>) {
+    let checker = if checker.trim().is_empty() || checker.trim() == "tsc" { DEFAULT_CHECKER } else { checker };
+    let mut parts = split_command(checker).into_iter();
+    (parts.next().unwrap_or_default(), parts.collect())
+}
+
+fn split_command(command: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    let mut word: Option<String> = None;
+    let mut quote = None;
+    let mut chars = command.chars();
+
+    while let Some(c) = chars.next() {
+        match (quote, c) {
+            (Some(open), c) if c == open => quote = None,
+            (Some('"') | None, '\\') => {
+                if let Some(escaped) = chars.next() {
+                    word.get_or_insert_with(String::new).push(escaped);
+                }
+            }
+            (Some(_), c) => word.get_or_insert_with(String::new).push(c),
+            (None, '"' | '\'') => {
+                quote = Some(c);
+                word.get_or_insert_with(String::new);
+            }
+            (None, c) if c.is_whitespace() => words.extend(word.take()),
+            (None, c) => word.get_or_insert_with(String::new).push(c),
+        }
+    }
+
+    words.extend(word);
+    words
+}
+
+pub fn parse_output(output: &str) -> Vec<TypeFinding> {
+    let mut findings: Vec<TypeFinding> = Vec::new();
+
+    for line in output.lines() {
+        if line.starts_with(char::is_whitespace) {
+            if let Some(last) = findings.last_mut().filter(|_| !line.trim().is_empty()) {
+                last.message.push('\n');
+                last.message.push_str(line.trim_end());
+            }
+            continue;
+        }
+
+        let Some(finding) = parse_line(line) else {
+            continue;
+        };
+        findings.push(finding);
+    }
+
+    findings
+}
+
+fn parse_line(line: &str) -> Option<TypeFinding> {
+    let (location, rest) = line.split_once("): ")?;
+    let (file, position) = location.rsplit_once('(')?;
+    let (line_number, column) = position.split_once(',')?;
+    let (level, message) = rest.split_once(' ')?;
+    let level = match level {
+        "error" => DiagnosticLevel::Error,
+        "warning" | "message" | "suggestion" => DiagnosticLevel::Warn,
+        _ => return None,
+    };
+
+    Some(TypeFinding {
+        file: PathBuf::from(file),
+        line: line_number.trim().parse().ok()?,
+        column: column.trim().parse().ok()?,
+        level,
+        message: message.to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_tsc_output() {
+        let output = "src/app.js(3,7): error TS2322: Type 'string' is not assignable to type 'number'.\n\
+                      lib/util (copy).js(12,1): error TS2554: Expected 2 arguments, but got 1.\n\
+                      \x20 An argument for 'b' was not provided.\n\
+                      error TS5023: Unknown compiler option 'strictest'.\n\
+                      \n\
+                      Found 2 errors in 2 files.\n";
+        let findings = parse_output(output);
+
+        assert_eq!(findings.len(), 2);
+        assert_eq!(findings[0].file, PathBuf::from("src/app.js"));
+        assert_eq!((findings[0].line, findings[0].column), (3, 7));
+        assert_eq!(findings[0].message, "TS2322: Type 'string' is not assignable to type 'number'.");
+        assert_eq!(findings[1].file, PathBuf::from("lib/util (copy).js"));
+        assert_eq!(findings[1].message, "TS2554: Expected 2 arguments, but got 1.\n  An argument for 'b' was not provided.");
+
+        assert_eq!(checker_command("tsc").0, "tsc");
+        assert!(checker_command("tsc").1.contains(&"--checkJs".to_string()));
+        assert_eq!(checker_command("npx tsc --noEmit"), ("npx".to_string(), vec!["tsc".to_string(), "--noEmit".to_string()]));
+        assert_eq!(
+            checker_command(r#""/opt/my tools/tsc" --project 'configs/strict tsconfig.json' --outDir build\ dir """#),
+            (
+                "/opt/my tools/tsc".to_string(),
+                vec![
+                    "--project".to_string(),
+                    "configs/strict tsconfig.json".to_string(),
+                    "--outDir".to_string(),
+                    "build dir".to_string(),
+                    String::new(),
+                ]
+            )
+        );
+    }
+}
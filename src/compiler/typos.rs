@@ -0,0 +1,194 @@
+use crate::compiler::lexer::{Lexer, Token, TokenKind};
+use crate::core::NullScriptError;
+use crate::core::keywords::{JS_GLOBALS, JS_RESERVED_WORDS, KEYWORDS};
+use std::collections::HashSet;
+use std::ops::Range;
+
+const MIN_FUZZY_LENGTH: usize = 5;
+
+static DECLARATION_WORDS: &[&str] = &["let", "fixed", "var", "const", "run", "function", "model", "class", "use", "import"];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeywordTypo {
+    pub found: String,
+    pub suggestion: &'static str,
+    pub line: u32,
+    pub column: u32,
+    pub span: Range<usize>,
+}
+
+impl KeywordTypo {
+    pub fn message(&self) -> String {
+        format!(
+            "'{}' is not a NullScript keyword and is left as a plain identifier; did you mean '{}'?",
+            self.found, self.suggestion
+        )
+    }
+
+    pub fn apply(source: &str, typos: &[KeywordTypo]) -> String {
+        let mut fixed = String::with_capacity(source.len());
+        let mut copied = 0;
+
+        for typo in typos {
+            fixed.push_str(&source[copied..typo.span.start]);
+            fixed.push_str(typo.suggestion);
+            copied = typo.span.end;
+        }
+
+        fixed.push_str(&source[copied..]);
+        fixed
+    }
+}
+
+pub fn find(source: &str, protected: &[String]) -> Result<Vec<KeywordTypo>, NullScriptError> {
+    let tokens: Vec<Token> = Lexer::new(source)
+        .with_max_depth(usize::MAX)
+        .tokenize()?
+        .into_iter()
+        .filter(|token| !token.is_trivia())
+        .collect();
+    let declared = declared_names(&tokens);
+    let mut typos = Vec::new();
+
+    for (index, token) in tokens.iter().enumerate() {
+        if token.kind != TokenKind::Identifier || !starts_statement(&tokens, index) || declared.contains(token.text) {
+            continue;
+        }
+
+        let next = tokens.get(index + 1).map_or("", |next| next.text);
+        let followed_like_keyword = tokens.get(index + 1).is_some_and(|next| next.kind == TokenKind::Identifier)
+            || matches!(next, "(" | "{" | ".");
+        if !followed_like_keyword || is_known(token.text, protected) {
+            continue;
+        }
+
+        if let Some(suggestion) = closest_keyword(token.text) {
+            typos.push(KeywordTypo {
+                found: token.text.to_string(),
+                suggestion,
+                line: token.line,
+                column: token.column,
+                span: token.start..token.start + token.text.len(),
+            });
+        }
+    }
+
+    Ok(typos)
+}
+
+fn starts_statement(tokens: &[Token], index: usize) -> bool {
+    match index.checked_sub(1).map(|previous| &tokens[previous]) {
+        None => true,
+        Some(previous) => matches!(previous.text, ";" | "{" | "}") || previous.line < tokens[index].line && previous.text != ".",
+    }
+}
+
+fn declared_names<'a>(tokens: &[Token<'a>]) -> HashSet<&'a str> {
+    let mut declared = HashSet::new();
+    let mut in_params = false;
+    let mut in_binding_list = false;
+
+    for (index, token) in tokens.iter().enumerate() {
+        let previous = index.checked_sub(1).map_or("", |previous| tokens[previous].text);
+        let before_previous = index.checked_sub(2).map_or("", |before| tokens[before].text);
+
+        match token.text {
+            "(" if declares(previous) || declares(before_previous) => in_params = true,
+            ")" => in_params = false,
+            "{" if declares(previous) => in_binding_list = true,
+            "}" => in_binding_list = false,
+            _ if token.kind != TokenKind::Identifier => {}
+            _ if in_params || in_binding_list || declares(previous) || previous == "as" => {
+                declared.insert(token.text);
+            }
+            _ if tokens.get(index + 1).is_some_and(|next| matches!(next.text, "=" | "=>" | ":")) => {
+                declared.insert(token.text);
+            }
+            _ => {}
+        }
+    }
+
+    declared
+}
+
+fn declares(word: &str) -> bool {
+    DECLARATION_WORDS.iter().any(|declaration| declaration.eq_ignore_ascii_case(word))
+}
+
+fn is_known(word: &str, protected: &[String]) -> bool {
+    KEYWORDS.iter().any(|(keyword, js_keyword)| *keyword == word || *js_keyword == word)
+        || JS_RESERVED_WORDS.contains(&word)
+        || JS_GLOBALS.contains(&word)
+        || protected.iter().any(|name| name == word)
+}
+
+fn closest_keyword(word: &str) -> Option<&'static str> {
+    let lower = word.to_lowercase();
+
+    KEYWORDS
+        .iter()
+        .map(|(keyword, _)| *keyword)
+        .filter(|keyword| keyword.chars().all(|c| c.is_ascii_alphabetic()))
+        .find_map(|keyword| {
+            if keyword.to_lowercase() == lower {
+                return Some((keyword, 0));
+            }
+
+            let fuzzy = keyword.len() >= MIN_FUZZY_LENGTH && edit_distance(keyword, word) == 1;
+            fuzzy.then_some((keyword, 1))
+        })
+        .map(|(keyword, _)| keyword)
+}
+
+fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let mut rows = vec![vec![0usize; b.len() + 1]; a.len() + 1];
+
+    rows[0] = (0..=b.len()).collect();
+    for (i, row) in rows.iter_mut().enumerate() {
+        row[0] = i;
+    }
+
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            rows[i][j] = (rows[i - 1][j] + 1).min(rows[i][j - 1] + 1).min(rows[i - 1][j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                rows[i][j] = rows[i][j].min(rows[i - 2][j - 2] + 1);
+            }
+        }
+    }
+
+    rows[a.len()][b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_finds_misspelled_keywords() {
+        let source = "modle Shape {\n}\nRun area(shape) {\n    whateverr (shape.size more 1) {\n        shape.grow();\n    }\n    fixed share = 1;\n    share.push(2);\n}\nspeek.say(\"hi\");\n";
+
+        let typos = find(source, &[]).unwrap();
+        let found: Vec<(&str, &str, u32)> = typos.iter().map(|t| (t.found.as_str(), t.suggestion, t.line)).collect();
+        assert_eq!(
+            found,
+            vec![("modle", "model", 1), ("Run", "run", 3), ("whateverr", "whatever", 4), ("speek", "speak", 10)]
+        );
+
+        let fixed = KeywordTypo::apply(source, &typos);
+        assert!(fixed.starts_with("model Shape {\n}\nrun area(shape) {\n    whatever (shape"));
+        assert!(find(&fixed, &[]).unwrap().is_empty());
+        assert!(find(source, &["modle".to_string()]).unwrap().iter().all(|t| t.found != "modle"));
+    }
+
+    #[test]
+    fn test_javascript_globals_are_known() {
+        assert!(is_known("structuredClone", &[]));
+        assert!(is_known("globalThis", &[]));
+        assert!(!is_known("modle", &[]));
+        assert!(find("structuredClone(value);\nglobalThis.ready = yes;\nprocess.exit(0);\n", &[]).unwrap().is_empty());
+    }
+}
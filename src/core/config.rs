@@ -0,0 +1,362 @@
+use crate::compiler::options::Target;
+use crate::core::NullScriptError;
+use crate::core::version::{Version, VersionReq};
+use crate::utils::paths::PathUtils;
+use globset::{Glob, GlobSet, GlobSetBuilder};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+pub const CONFIG_FILE: &str = "nsconfig.json";
+
+pub static NESTED_KEYS: &[&str] = &["root", "protected"];
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct NsConfig {
+    pub root: bool,
+    pub entry: Vec<PathBuf>,
+    pub warn_unused: bool,
+    pub platform: Option<String>,
+    pub protected: Vec<String>,
+    pub root_dir: Option<PathBuf>,
+    pub include: Vec<String>,
+    pub exclude: Vec<String>,
+    pub nsc_version: Option<String>,
+    pub strict_version: bool,
+    pub generate: Vec<GenerateRule>,
+    pub keyword_typos: DiagnosticLevel,
+    pub top_level_await: TopLevelAwaitPolicy,
+    pub paths: BTreeMap<String, Vec<String>>,
+    pub import_case: DiagnosticLevel,
+    pub banner: Option<String>,
+    pub file_names: Option<String>,
+    pub external_diagnostics: Vec<PathBuf>,
+    pub target: Option<Target>,
+    pub runtime_shim: bool,
+    pub operator_aliases: OperatorAliases,
+    pub migration: bool,
+    pub lint: BTreeMap<String, DiagnosticLevel>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DiagnosticLevel {
+    Off,
+    #[default]
+    #[serde(alias = "warning")]
+    Warn,
+    Error,
+}
+
+impl DiagnosticLevel {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DiagnosticLevel::Off => "off",
+            DiagnosticLevel::Warn => "warn",
+            DiagnosticLevel::Error => "error",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TopLevelAwaitPolicy {
+    #[default]
+    Error,
+    Wrap,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum OperatorAliases {
+    On,
+    Off,
+    #[default]
+    AcceptBoth,
+}
+
+impl OperatorAliases {
+    pub fn parse(value: &str) -> Result<Self, String> {
+        match value.to_ascii_lowercase().as_str() {
+            "on" => Ok(OperatorAliases::On),
+            "off" => Ok(OperatorAliases::Off),
+            "accept-both" => Ok(OperatorAliases::AcceptBoth),
+            other => Err(format!("unknown operator alias mode '{}', expected on, off or accept-both", other)),
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            OperatorAliases::On => "on",
+            OperatorAliases::Off => "off",
+            OperatorAliases::AcceptBoth => "accept-both",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct GenerateRule {
+    pub inputs: Option<String>,
+    pub output: String,
+    pub command: Option<String>,
+    pub builtin: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Exclusion {
+    OutsideRoot(PathBuf),
+    NotIncluded,
+    Excluded,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct LoadedConfig {
+    pub path: Option<PathBuf>,
+    pub config: NsConfig,
+    pub chain: Vec<PathBuf>,
+    pub origins: BTreeMap<String, PathBuf>,
+}
+
+impl NsConfig {
+    pub fn load(path: &Path) -> Result<Self, NullScriptError> {
+        let content = std::fs::read_to_string(path)?;
+        Ok(serde_json::from_str(&content)?)
+    }
+
+    pub fn discover(start: &Path) -> Result<LoadedConfig, NullScriptError> {
+        let start = PathUtils::absolute(start);
+        let start = if start.is_file() {
+            start.parent().map(Path::to_path_buf).unwrap_or_default()
+        } else {
+            start
+        };
+
+        let mut layers = Vec::new();
+        for directory in start.ancestors() {
+            let candidate = directory.join(CONFIG_FILE);
+            if !candidate.is_file() {
+                continue;
+            }
+
+            let content = std::fs::read_to_string(&candidate)?;
+            let layer: Map<String, Value> = serde_json::from_str(&content)?;
+            let is_root = layer.get("root").and_then(Value::as_bool).unwrap_or(false);
+            layers.push((candidate, layer));
+
+            if is_root {
+                break;
+            }
+        }
+
+        let Some((nearest, _)) = layers.first() else {
+            return Ok(LoadedConfig::default());
+        };
+        let path = Some(nearest.clone());
+
+        let mut merged = Map::new();
+        let mut origins = BTreeMap::new();
+        let mut chain = Vec::new();
+        for (candidate, layer) in layers.into_iter().rev() {
+            for (key, value) in layer {
+                origins.insert(key.clone(), candidate.clone());
+                merged.insert(key, value);
+            }
+            chain.push(candidate);
+        }
+
+        Ok(LoadedConfig {
+            config: serde_json::from_value(Value::Object(merged))?,
+            path,
+            chain,
+            origins,
+        })
+    }
+}
+
+impl LoadedConfig {
+    pub fn base_dir(&self) -> PathBuf {
+        self.path
+            .as_deref()
+            .and_then(Path::parent)
+            .map(Path::to_path_buf)
+            .unwrap_or_default()
+    }
+
+    pub fn origin_dir(&self, key: &str) -> PathBuf {
+        self.origins
+            .get(key)
+            .and_then(|origin| origin.parent())
+            .map(Path::to_path_buf)
+            .unwrap_or_else(|| self.base_dir())
+    }
+
+    pub fn root_dir(&self) -> PathBuf {
+        let base = self.origin_dir("rootDir");
+        match &self.config.root_dir {
+            Some(root_dir) => PathUtils::normalize(&base.join(root_dir)),
+            None => base,
+        }
+    }
+
+    pub fn exclusion(&self, file: &Path) -> Result<Option<Exclusion>, NullScriptError> {
+        if self.path.is_none() {
+            return Ok(None);
+        }
+
+        let file = PathUtils::absolute(file);
+        let root_dir = self.root_dir();
+        if !file.starts_with(&root_dir) {
+            return Ok(Some(Exclusion::OutsideRoot(root_dir)));
+        }
+
+        let relative_to = |key: &str| {
+            PathUtils::relative_to(&file, &self.origin_dir(key))
+                .map(|p| PathUtils::to_slash(&p))
+                .unwrap_or_else(|| PathUtils::to_slash(&file))
+        };
+
+        if !self.config.include.is_empty() && !glob_set(&self.config.include)?.is_match(relative_to("include")) {
+            return Ok(Some(Exclusion::NotIncluded));
+        }
+
+        if glob_set(&self.config.exclude)?.is_match(relative_to("exclude")) {
+            return Ok(Some(Exclusion::Excluded));
+        }
+
+        Ok(None)
+    }
+
+    pub fn version_mismatch(&self, version: &Version) -> Result<Option<&str>, NullScriptError> {
+        let Some(range) = &self.config.nsc_version else {
+            return Ok(None);
+        };
+
+        let requirement = VersionReq::parse(range)
+            .map_err(|e| NullScriptError::Io(std::io::Error::new(std::io::ErrorKind::InvalidData, e)))?;
+
+        Ok((!requirement.matches(version)).then_some(range.as_str()))
+    }
+
+    pub fn check_nested(&self, parent_chain: &[PathBuf]) -> Result<(), NullScriptError> {
+        let unsupported = self
+            .origins
+            .iter()
+            .find(|(key, origin)| !parent_chain.contains(origin) && !NESTED_KEYS.contains(&key.as_str()));
+
+        match unsupported {
+            Some((key, origin)) => Err(NullScriptError::Config(format!(
+                "{}: \"{}\" cannot be set in a nested {}; only \"protected\" applies to a subdirectory. Move it to the project config or build that directory on its own.",
+                origin.display(),
+                key,
+                CONFIG_FILE
+            ))),
+            None => Ok(()),
+        }
+    }
+
+    pub fn external_diagnostics(&self) -> Vec<PathBuf> {
+        let base = self.origin_dir("externalDiagnostics");
+        self.config
+            .external_diagnostics
+            .iter()
+            .map(|report| PathUtils::normalize(&base.join(report)))
+            .collect()
+    }
+
+    pub fn entries(&self) -> Vec<PathBuf> {
+        let base = self.origin_dir("entry");
+        self.config
+            .entry
+            .iter()
+            .map(|entry| PathUtils::normalize(&base.join(entry)))
+            .collect()
+    }
+}
+
+fn glob_set(patterns: &[String]) -> Result<GlobSet, NullScriptError> {
+    let mut builder = GlobSetBuilder::new();
+    for pattern in patterns {
+        let glob = Glob::new(pattern).map_err(|e| {
+            NullScriptError::Io(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!("invalid pattern in {}: {}", CONFIG_FILE, e),
+            ))
+        })?;
+        builder.add(glob);
+    }
+
+    builder.build().map_err(|e| {
+        NullScriptError::Io(std::io::Error::new(std::io::ErrorKind::InvalidData, e.to_string()))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_discover_walks_up_and_resolves_entries() {
+        let dir = tempfile::tempdir().unwrap();
+        let nested = dir.path().join("src").join("lib");
+        std::fs::create_dir_all(&nested).unwrap();
+        std::fs::write(
+            dir.path().join(CONFIG_FILE),
+            r#"{ "entry": ["src/main.ns"], "warnUnused": true }"#,
+        ).unwrap();
+
+        let loaded = NsConfig::discover(&nested).unwrap();
+        assert!(loaded.config.warn_unused);
+        assert_eq!(
+            loaded.entries(),
+            vec![PathUtils::normalize(&dir.path().join("src").join("main.ns"))]
+        );
+    }
+
+    #[test]
+    fn test_nested_configs_override_parent_settings() {
+        let dir = tempfile::tempdir().unwrap();
+        let package = dir.path().join("packages").join("web");
+        std::fs::create_dir_all(&package).unwrap();
+        std::fs::write(
+            dir.path().join(CONFIG_FILE),
+            r#"{ "entry": ["main.ns"], "platform": "node", "protected": ["list"] }"#,
+        ).unwrap();
+        std::fs::write(package.join(CONFIG_FILE), r#"{ "platform": "browser" }"#).unwrap();
+
+        let loaded = NsConfig::discover(&package).unwrap();
+        assert_eq!(loaded.config.platform.as_deref(), Some("browser"));
+        assert_eq!(loaded.config.protected, vec!["list".to_string()]);
+        assert_eq!(loaded.chain, vec![dir.path().join(CONFIG_FILE), package.join(CONFIG_FILE)]);
+        assert_eq!(loaded.entries(), vec![PathUtils::normalize(&dir.path().join("main.ns"))]);
+
+        let parent = NsConfig::discover(dir.path()).unwrap();
+        assert!(loaded.check_nested(&parent.chain).is_err());
+        std::fs::write(package.join(CONFIG_FILE), r#"{ "protected": ["tally"] }"#).unwrap();
+        assert!(NsConfig::discover(&package).unwrap().check_nested(&parent.chain).is_ok());
+
+        std::fs::write(package.join(CONFIG_FILE), r#"{ "root": true }"#).unwrap();
+        let isolated = NsConfig::discover(&package).unwrap();
+        assert_eq!(isolated.config.platform, None);
+        assert_eq!(isolated.chain.len(), 1);
+    }
+
+    #[test]
+    fn test_exclusion_follows_root_dir_and_patterns() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join(CONFIG_FILE),
+            r#"{ "rootDir": "src", "include": ["src/**/*.ns"], "exclude": ["**/*.draft.ns"] }"#,
+        ).unwrap();
+
+        let loaded = NsConfig::discover(dir.path()).unwrap();
+        assert_eq!(loaded.exclusion(&dir.path().join("src/app/main.ns")).unwrap(), None);
+        assert_eq!(loaded.exclusion(&dir.path().join("src/app/main.draft.ns")).unwrap(), Some(Exclusion::Excluded));
+        assert!(matches!(
+            loaded.exclusion(&dir.path().join("scripts/tool.ns")).unwrap(),
+            Some(Exclusion::OutsideRoot(_))
+        ));
+    }
+}
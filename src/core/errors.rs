@@ -1,5 +1,9 @@
 use thiserror::Error;
+use crate::core::i18n::{trf, trn};
 use crate::core::types::{Location, WithLocation};
+use crate::utils::commands::CommandUtils;
+use serde::Serialize;
+use std::process::{ExitStatus, Output};
 
 #[derive(Error, Debug)]
 pub enum NullScriptError {
@@ -9,9 +13,15 @@ pub enum NullScriptError {
     #[error("NullScriptSyntaxError")]
     Syntax(#[from] NullScriptSyntaxError),
 
+    #[error("{} NullScriptSyntaxErrors", errors.len() + omitted)]
+    SyntaxErrors { errors: Vec<NullScriptSyntaxError>, omitted: usize },
+
     #[error("NullScriptTypeError")]
     Type(#[from] NullScriptTypeError),
 
+    #[error("{0}")]
+    ExternalTool(#[from] ExternalToolError),
+
     #[error("IO error: {0}")]
     Io(#[from] std::io::Error),
 
@@ -20,6 +30,12 @@ pub enum NullScriptError {
 
     #[error("JSON error: {0}")]
     Json(#[from] serde_json::Error),
+
+    #[error("Config error: {0}")]
+    Config(String),
+
+    #[error("Usage error: {0}")]
+    Usage(String),
 }
 
 #[derive(Error, Debug)]
@@ -43,6 +59,61 @@ pub struct NullScriptTypeError {
     pub location: Location,
 }
 
+#[derive(Error, Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[error("'{}' exited with code {exit_code}", self.command_line())]
+pub struct ExternalToolError {
+    pub tool: String,
+    pub args: Vec<String>,
+    pub exit_code: i32,
+    pub stdout: String,
+    pub stderr: String,
+}
+
+impl ExternalToolError {
+    pub fn from_output(tool: &str, args: &[&str], output: &Output) -> Self {
+        Self {
+            tool: tool.to_string(),
+            args: args.iter().map(|arg| arg.to_string()).collect(),
+            exit_code: exit_code(&output.status),
+            stdout: String::from_utf8_lossy(&output.stdout).into_owned(),
+            stderr: String::from_utf8_lossy(&output.stderr).into_owned(),
+        }
+    }
+
+    pub fn with_stderr(mut self, stderr: String) -> Self {
+        self.stderr = stderr;
+        self
+    }
+
+    pub fn command_line(&self) -> String {
+        let args: Vec<&str> = self.args.iter().map(String::as_str).collect();
+        CommandUtils::command_line(&self.tool, &args)
+    }
+
+    pub fn format_error(&self) -> String {
+        let mut output = trf("error.external_tool", &[("tool", &self.tool), ("code", &self.exit_code)]);
+        output.push_str(&format!("\n{}", trf("error.external_tool_command", &[("command", &self.command_line())])));
+        let stderr = self.stderr.trim_end();
+        if !stderr.is_empty() {
+            output.push_str(&format!("\n\n{}", stderr));
+        }
+        output
+    }
+}
+
+pub fn exit_code(status: &ExitStatus) -> i32 {
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::ExitStatusExt;
+        if let Some(signal) = status.signal() {
+            return 128 + signal;
+        }
+    }
+
+    status.code().unwrap_or(1)
+}
+
 impl WithLocation for NullScriptTranspileError {
     fn with_location(message: String, location: Location) -> Self {
         Self { message, location }
@@ -92,13 +163,65 @@ impl NullScriptTypeError {
 
 
 
+impl NullScriptError {
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            NullScriptError::ExternalTool(e) if e.exit_code != 0 => e.exit_code,
+            NullScriptError::Usage(_) => 2,
+            _ => 1,
+        }
+    }
+}
+
 pub fn format_error(error: &NullScriptError) -> String {
     match error {
         NullScriptError::Transpile(e) => e.format_error(),
         NullScriptError::Syntax(e) => e.format_error(),
+        NullScriptError::SyntaxErrors { errors, omitted } => {
+            let mut output = errors.iter().map(NullScriptSyntaxError::format_error).collect::<Vec<_>>().join("\n\n");
+            if *omitted > 0 {
+                output.push_str(&format!("\n\n{}", trn("error.more_syntax", *omitted as u64, &[])));
+            }
+            output
+        }
         NullScriptError::Type(e) => e.format_error(),
-        NullScriptError::Io(e) => format!("❌ IO Error: {}", e),
-        NullScriptError::Regex(e) => format!("❌ Regex Error: {}", e),
-        NullScriptError::Json(e) => format!("❌ JSON Error: {}", e),
+        NullScriptError::ExternalTool(e) => e.format_error(),
+        NullScriptError::Io(e) => trf("error.io", &[("error", e)]),
+        NullScriptError::Regex(e) => trf("error.regex", &[("error", e)]),
+        NullScriptError::Json(e) => trf("error.json", &[("error", e)]),
+        NullScriptError::Config(e) => trf("error.config", &[("error", e)]),
+        NullScriptError::Usage(e) => trf("error.usage", &[("error", e)]),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(unix)]
+    #[test]
+    fn test_external_tool_error_keeps_exit_code_and_output() {
+        let args = ["-c", "echo partial; echo 'boom' >&2; exit 3"];
+        let output = std::process::Command::new("sh").args(args).output().unwrap();
+        let error = NullScriptError::from(ExternalToolError::from_output("sh", &args, &output));
+
+        assert_eq!(error.exit_code(), 3);
+        assert_eq!(error.to_string(), "'sh -c \"echo partial; echo 'boom' >&2; exit 3\"' exited with code 3");
+        assert!(format_error(&error).ends_with("\n\nboom"));
+
+        let NullScriptError::ExternalTool(tool) = &error else {
+            unreachable!();
+        };
+        assert_eq!(
+            serde_json::to_value(tool).unwrap(),
+            serde_json::json!({ "tool": "sh", "args": args, "exitCode": 3, "stdout": "partial\n", "stderr": "boom\n" })
+        );
+    }
+
+    #[test]
+    fn test_usage_errors_exit_with_code_2() {
+        let error = NullScriptError::Usage("Unknown keyword category 'loops'".to_string());
+        assert_eq!(error.exit_code(), 2);
+        assert_eq!(error.to_string(), "Usage error: Unknown keyword category 'loops'");
     }
 }
@@ -0,0 +1,638 @@
+use std::sync::RwLock;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Lang {
+    En,
+    Es,
+}
+
+impl Lang {
+    pub fn from_tag(tag: &str) -> Option<Self> {
+        let primary = tag
+            .split(['_', '-', '.', '@'])
+            .next()
+            .unwrap_or("")
+            .to_ascii_lowercase();
+
+        match primary.as_str() {
+            "en" | "c" | "posix" => Some(Lang::En),
+            "es" => Some(Lang::Es),
+            _ => None,
+        }
+    }
+
+    pub fn detect(requested: Option<&str>) -> Self {
+        Self::from_env(requested, |var| std::env::var(var).ok())
+    }
+
+    fn from_env(requested: Option<&str>, var: impl Fn(&str) -> Option<String>) -> Self {
+        if let Some(lang) = requested.and_then(Self::from_tag) {
+            return lang;
+        }
+
+        ["NSC_LANG", "LC_ALL", "LC_MESSAGES", "LANG"]
+            .iter()
+            .filter_map(|name| var(name))
+            .find(|value| !value.is_empty())
+            .and_then(|value| Self::from_tag(&value))
+            .unwrap_or(Lang::En)
+    }
+
+    fn catalog(self) -> &'static [(&'static str, &'static str)] {
+        match self {
+            Lang::En => EN,
+            Lang::Es => ES,
+        }
+    }
+}
+
+static LANG: RwLock<Lang> = RwLock::new(Lang::En);
+
+pub fn set_lang(lang: Lang) {
+    if let Ok(mut current) = LANG.write() {
+        *current = lang;
+    }
+}
+
+pub fn lang() -> Lang {
+    LANG.read().map(|l| *l).unwrap_or(Lang::En)
+}
+
+pub fn tr(key: &str) -> &'static str {
+    translate(lang(), key)
+}
+
+pub fn trf(key: &str, args: &[(&str, &dyn std::fmt::Display)]) -> String {
+    format_in(lang(), key, args)
+}
+
+pub fn trn(key: &str, count: u64, args: &[(&str, &dyn std::fmt::Display)]) -> String {
+    plural_in(lang(), key, count, args)
+}
+
+fn translate(lang: Lang, key: &str) -> &'static str {
+    resolve(lang.catalog(), key)
+}
+
+fn resolve(catalog: &'static [(&'static str, &'static str)], key: &str) -> &'static str {
+    lookup(catalog, key)
+        .or_else(|| lookup(EN, key))
+        .unwrap_or("")
+}
+
+fn format_in(lang: Lang, key: &str, args: &[(&str, &dyn std::fmt::Display)]) -> String {
+    let mut message = translate(lang, key).to_string();
+    for (name, value) in args {
+        message = message.replace(&format!("{{{}}}", name), &value.to_string());
+    }
+    message
+}
+
+fn plural_in(lang: Lang, key: &str, count: u64, args: &[(&str, &dyn std::fmt::Display)]) -> String {
+    let form = if count == 1 { "one" } else { "other" };
+    let mut all_args: Vec<(&str, &dyn std::fmt::Display)> = vec![("count", &count)];
+    all_args.extend_from_slice(args);
+    format_in(lang, &format!("{}.{}", key, form), &all_args)
+}
+
+fn lookup(catalog: &'static [(&'static str, &'static str)], key: &str) -> Option<&'static str> {
+    catalog.iter().find(|(k, _)| *k == key).map(|(_, v)| *v)
+}
+
+static EN: &[(&str, &str)] = &[
+
+    ("build.title", "📊 Build Information"),
+    ("build.input_path", "Input path: {path}"),
+    ("build.output_dir", "Output directory: {path}"),
+    ("build.estimated.one", "Estimated output: {count} JavaScript file"),
+    ("build.estimated.other", "Estimated output: {count} JavaScript files"),
+    ("build.transpiled_dir.one", "✅ Transpiled {count} file to {dir}"),
+    ("build.transpiled_dir.other", "✅ Transpiled {count} files to {dir}"),
+    ("build.cached.one", "   ♻️  {count} unchanged file reused from .ns-cache"),
+    ("build.cached.other", "   ♻️  {count} unchanged files reused from .ns-cache"),
+    ("build.transpiled_file", "✅ Transpiled {input} → {output}"),
+    ("build.bundled.one", "✅ Bundled {count} file into {file}"),
+    ("build.bundled.other", "✅ Bundled {count} files into {file}"),
+    ("build.bundle_needs_dir", "--bundle needs a project directory, but {path} is a file"),
+    ("build.warnings.one", "⚠️  {count} warning:"),
+    ("build.warnings.other", "⚠️  {count} warnings:"),
+    ("build.unused", "never imported from any entry point"),
+    ("build.import_case", "line {line}: '{specifier}' does not match the casing on disk ({actual}); use '{fixed}'"),
+    ("build.external_finding", "line {location}: {message}"),
+    ("build.import_case_failed.one", "{count} import does not match the casing on disk:"),
+    ("build.import_case_failed.other", "{count} imports do not match the casing on disk:"),
+    ("build.dry_run.one", "🔍 Dry run: {count} file would be written to {dir}"),
+    ("build.dry_run.other", "🔍 Dry run: {count} files would be written to {dir}"),
+    ("build.dry_run_size", "({bytes} bytes)"),
+
+
+    ("graph.written.one", "✅ Wrote graph of {count} module to {path}"),
+    ("graph.written.other", "✅ Wrote graph of {count} modules to {path}"),
+    ("hermetic.forbidden", "{feature} needs {tool}, but --hermetic forbids running external tools"),
+    ("hermetic.features", "Features that need external tools:"),
+    ("build.depfile_written.one", "✅ Wrote dependency rules for {count} file to {path}"),
+    ("build.depfile_written.other", "✅ Wrote dependency rules for {count} files to {path}"),
+    ("compdb.written.one", "✅ Wrote compilation database with {count} file to {path}"),
+    ("compdb.written.other", "✅ Wrote compilation database with {count} files to {path}"),
+
+
+    ("analyze.title", "📈 Analysis Report"),
+    ("analyze.modules", "Modules: {count}"),
+    ("analyze.config", "Config: {path}"),
+    ("analyze.revision", "Commit: {commit} ({branch})"),
+    ("analyze.detached", "detached HEAD"),
+    ("analyze.scope", "Per-module reports cover {count} of {total} modules"),
+    ("analyze.blame_title", "📦 Output size from {from} to {to}"),
+    ("analyze.blame_failed.one", "⚠️  {count} file did not transpile at some commit and was left out"),
+    ("analyze.blame_failed.other", "⚠️  {count} files did not transpile at some commit and were left out"),
+    ("analyze.blame_unchanged", "✅ No commit in this range changed the output size"),
+    ("analyze.blame_commits", "By commit (first parent), largest growth first:"),
+    ("analyze.blame_more.one", "… and {count} more file"),
+    ("analyze.blame_more.other", "… and {count} more files"),
+    ("analyze.blame_files", "By file:"),
+    ("analyze.heatmap_written", "✅ Wrote keyword heatmap to {path}"),
+    ("analyze.unused_title", "🗑️ Unused files"),
+    ("analyze.no_entries", "⚠️  No entry points configured. Add an \"entry\" list to nsconfig.json or pass --entry."),
+    ("analyze.entry", "Entry: {path}"),
+    ("analyze.unused_none", "✅ Every module is reachable from an entry point"),
+    ("analyze.unused_count.one", "{count} file is never imported from any entry point:"),
+    ("analyze.unused_count.other", "{count} files are never imported from any entry point:"),
+    ("analyze.profile_title", "⏱️ Transpile time by pass (ms)"),
+    ("analyze.profile_file", "File"),
+    ("analyze.profile_total", "Total"),
+    ("analyze.minify_title", "🗜️ Output size with --minify"),
+    ("analyze.minify_before", "Before"),
+    ("analyze.minify_after", "After"),
+    ("analyze.minify_saved", "Saved"),
+    ("check.no_changes", "✅ No NullScript files changed since {reference}"),
+    ("check.import_case", "'{specifier}' does not match the casing on disk ({actual}); use '{fixed}'"),
+    ("check.types_missing", "Type checker '{command}' was not found; install TypeScript (npm install -g typescript) or pass --types=COMMAND"),
+    ("check.ignored.one", "{count} problem outside the changed lines was ignored"),
+    ("check.ignored.other", "{count} problems outside the changed lines were ignored"),
+    ("check.clean.one", "✅ Checked {count} file, no problems found"),
+    ("check.clean.other", "✅ Checked {count} files, no problems found"),
+    ("check.problems.one", "⚠️  {count} problem found"),
+    ("check.problems.other", "⚠️  {count} problems found"),
+    ("check.failed.one", "{count} error found"),
+    ("check.failed.other", "{count} errors found"),
+    ("check.docs_clean.one", "✅ Checked {count} code block in {files} Markdown file(s)"),
+    ("check.docs_clean.other", "✅ Checked {count} code blocks in {files} Markdown file(s)"),
+    ("check.docs_failed.one", "{count} code block failed"),
+    ("check.docs_failed.other", "{count} code blocks failed"),
+    ("editor.not_configured", "No editor configured; set $VISUAL or $EDITOR to use --open-editor"),
+    ("editor.failed", "{editor} exited with {status}"),
+    ("editor.pick_title", "Open an error location in your editor:"),
+    ("prompt.confirm_default_yes", "[Y/n]"),
+    ("prompt.confirm_default_no", "[y/N]"),
+    ("prompt.confirm_invalid", "Answer y or n"),
+    ("prompt.select", "Number to choose, Enter to skip: "),
+    ("prompt.select_invalid", "Pick a number from 1 to {count}"),
+
+
+    ("run.running", "🚀 Running NullScript..."),
+    ("run.failed", "❌ Failed to run:"),
+    ("run.engine_unavailable", "This nsc was built without the builtin engine; reinstall it with --features engine or use --engine node"),
+    ("run.engine_inspect", "--inspect-memory needs node; drop --engine builtin to use it"),
+    ("run.outside_project", "⚠️  {path} is not part of the project in {config}: {reason}"),
+    ("run.outside_project_tip", "💡 Pass --allow-outside to run it anyway without this warning"),
+    ("repl.welcome", "NullScript REPL v{version} ({engine})"),
+    ("repl.hint", "Type .help for commands, .exit or Ctrl+D to leave"),
+    ("repl.help", "  .keywords [TEXT]  List keywords, optionally only those containing TEXT\n  .help             Show this help\n  .exit             Leave the REPL\n  An empty line ends an unfinished block early"),
+    ("repl.unknown_command", "Unknown command {command}; type .help for the list"),
+    ("repl.node_exited", "The node session ended unexpectedly"),
+
+
+    ("memory.title", "🧠 Memory Report"),
+    ("memory.read_failed", "❌ Could not read heap snapshot:"),
+    ("memory.write_failed", "❌ Could not write memory report:"),
+    ("memory.report", "Report: {path}"),
+    ("memory.snapshot", "Snapshot: {path}"),
+    ("memory.signal_tip", "💡 Send SIGUSR2 while running to capture extra snapshots in {dir}"),
+
+
+    ("keywords.title", "🎭 NullScript Keywords"),
+    ("keywords.mapping", "📋 NullScript → JavaScript Keywords:"),
+    ("keywords.category.basics.one", "Basics ({count} keyword)"),
+    ("keywords.category.basics.other", "Basics ({count} keywords)"),
+    ("keywords.category.operators.one", "Operators ({count} keyword)"),
+    ("keywords.category.operators.other", "Operators ({count} keywords)"),
+    ("keywords.category.advanced.one", "Advanced ({count} keyword)"),
+    ("keywords.category.advanced.other", "Advanced ({count} keywords)"),
+    ("keywords.category.console.one", "Console ({count} keyword)"),
+    ("keywords.category.console.other", "Console ({count} keywords)"),
+    ("keywords.category.globals.one", "Globals ({count} keyword)"),
+    ("keywords.category.globals.other", "Globals ({count} keywords)"),
+    ("keywords.category.functions.one", "Global functions ({count} keyword)"),
+    ("keywords.category.functions.other", "Global functions ({count} keywords)"),
+    ("keywords.unknown_category", "Unknown keyword category '{category}', expected one of: {expected}"),
+    ("keywords.no_matches", "No keywords match the filter"),
+    ("keywords.tip", "💡 Tip: Use NullScript keywords in your .ns files, they will be transpiled to JavaScript"),
+
+
+    ("update.checking", "🔎 Checking for a newer nsc release..."),
+    ("update.current", "Installed version: {version}"),
+    ("update.latest", "Latest release: {version}"),
+    ("update.up_to_date", "✅ nsc is up to date"),
+    ("update.available", "⬆️  nsc {version} is available"),
+    ("update.npm_managed", "💡 This nsc was installed with npm; run 'npm install -g nullscript@latest' to update it"),
+    ("update.downloading", "📥 Downloading {asset}..."),
+    ("update.verified", "✅ Checksum verified"),
+    ("update.installed", "✅ Updated to nsc {version} ({path})"),
+    ("generate.regenerated.one", "⚙️  Regenerated {count} file:"),
+    ("generate.regenerated.other", "⚙️  Regenerated {count} files:"),
+    ("generate.up_to_date.one", "⚙️  {count} generated file is up to date"),
+    ("generate.up_to_date.other", "⚙️  {count} generated files are up to date"),
+    ("explain.title", "🔍 Applied transforms"),
+    ("explain.none", "no transforms matched"),
+    ("explain.removed", "(removed)"),
+    ("explain.written", "🔍 Wrote the applied transforms to {path}"),
+    ("convert.done", "✅ Converted {input} → {output}"),
+    ("convert.exists", "{path} already exists; pass --force to overwrite it"),
+    ("convert.overwrite", "{path} already exists. Overwrite it?"),
+    ("convert.collision.identifier", "line {line}: '{word}' is a NullScript keyword and will be rewritten when transpiled"),
+    ("convert.collision_tip", "💡 Rename these, or list identifiers under \"protected\" in nsconfig.json, to keep the output identical"),
+    ("convert.roundtrip_exact", "🔁 Transpiling the result gives back the original JavaScript"),
+    ("convert.roundtrip_differs", "🔁 Transpiling the result differs from the original starting at line {line}"),
+    ("convert.roundtrip_failed", "🔁 The converted file does not transpile:"),
+    ("convert.fixture", "🧪 Recorded round-trip fixture in {dir}"),
+    ("convert.fixture_failed", "No fixture was recorded because the converted file does not transpile"),
+    ("fmt.changed", "✨ Formatted {path}"),
+    ("fmt.would_change", "✗ {path} is not formatted"),
+    ("fmt.done.one", "✅ Formatted {count} file"),
+    ("fmt.done.other", "✅ Formatted {count} files"),
+    ("fmt.unchanged.one", "✅ {count} file already formatted"),
+    ("fmt.unchanged.other", "✅ {count} files already formatted"),
+    ("fmt.check_failed.one", "{count} file is not formatted; run nsc fmt to fix it"),
+    ("fmt.check_failed.other", "{count} files are not formatted; run nsc fmt to fix them"),
+    ("fmt.missing", "{path} is not a file, directory or glob pattern"),
+    ("lint.title", "Lint rules"),
+    ("lint.clean.one", "✅ Linted {count} file, no problems found"),
+    ("lint.clean.other", "✅ Linted {count} files, no problems found"),
+    ("codemod.title", "Built-in codemods"),
+    ("codemod.unknown", "Unknown codemod '{name}'; pass a .ns-codemod file or one of: {builtins}"),
+    ("codemod.rewrote.one", "✏️ {path}: {count} replacement"),
+    ("codemod.rewrote.other", "✏️ {path}: {count} replacements"),
+    ("codemod.dry_run.one", "{count} file would change; run again with --write to apply"),
+    ("codemod.dry_run.other", "{count} files would change; run again with --write to apply"),
+    ("codemod.done.one", "✅ Rewrote {count} file"),
+    ("codemod.done.other", "✅ Rewrote {count} files"),
+    ("codemod.unchanged", "✅ Nothing to change"),
+    ("examples.title", "🎓 NullScript Examples"),
+    ("examples.hello", "Hello world with a function and template strings"),
+    ("examples.classes", "Classes, inheritance and static fields"),
+    ("examples.async", "Async functions, promises and fetch"),
+    ("examples.files", "Writing and reading files with fs/promises"),
+    ("examples.tip", "💡 Show one with 'nsc examples <name>', run it with --run"),
+    ("examples.source", "📄 {name}.ns"),
+    ("examples.run_tip", "💡 Run it with 'nsc examples {name} --run'"),
+    ("examples.unknown", "Unknown example '{name}'. Available examples: {names}"),
+    ("system.title", "🔧 System Information"),
+    ("system.available", "✅ Available"),
+    ("system.not_found", "❌ Not found"),
+    ("system.target", "Target: {target} (needs Node.js {version}+)"),
+    ("target.node_too_old", "⚠️  Node.js {node} cannot run {target} output, which needs Node.js {required}+. Upgrade Node.js or lower \"target\" in nsconfig.json."),
+
+
+    ("info.title", "📁 File Information"),
+    ("info.missing_path", "❌ Path does not exist: {path}"),
+    ("info.path", "Path: {path}"),
+    ("info.extension", "Extension: {ext}"),
+    ("info.name", "Name: {name}"),
+    ("info.size", "Size: {size}"),
+    ("info.lines", "Lines: {count}"),
+    ("info.modified", "Modified: {duration} ago"),
+    ("info.details", "📋 File Details:"),
+    ("info.total_size", "Total size: {size}"),
+    ("info.total_files", "Total files: {count}"),
+    ("info.nullscript_files", "NullScript files: {count}"),
+    ("info.config_none", "No nsconfig.json found; using defaults"),
+
+
+    ("duration.seconds.one", "{count} second"),
+    ("duration.seconds.other", "{count} seconds"),
+    ("duration.minutes.one", "{count} minute"),
+    ("duration.minutes.other", "{count} minutes"),
+    ("duration.hours.one", "{count} hour"),
+    ("duration.hours.other", "{count} hours"),
+    ("duration.days.one", "{count} day"),
+    ("duration.days.other", "{count} days"),
+    ("duration.unknown", "unknown"),
+
+
+    ("config.outside_root", "it is outside rootDir {dir}"),
+    ("config.not_included", "it does not match any include pattern"),
+    ("config.excluded", "it matches an exclude pattern"),
+    ("config.title", "⚙️  Effective Configuration"),
+    ("config.status_included", "✅ Part of the project"),
+    ("config.status_excluded", "⚠️  Not part of the project: {reason}"),
+    ("config.chain", "📚 Config files (later files override earlier ones)"),
+    ("config.values", "📋 Values"),
+    ("config.default", "default"),
+    ("config.version_mismatch", "⚠️  {config} requires nsc {range}, but this is nsc {version}"),
+    ("config.version_required", "{config} requires nsc {range}, but this is nsc {version}"),
+    ("config.version_mismatch_tip", "💡 Install a matching nsc or update \"nscVersion\"; set \"strictVersion\": true to make this an error"),
+
+
+    ("error.location_in", "in"),
+    ("error.external_tool", "❌ {tool} exited with code {code}"),
+    ("error.external_tool_command", "   Command: {command}"),
+    ("error.io", "❌ IO Error: {error}"),
+    ("error.more_syntax.one", "... and 1 more syntax error (raise --max-errors to see it)"),
+    ("error.more_syntax.other", "... and {count} more syntax errors (raise --max-errors to see them)"),
+    ("error.regex", "❌ Regex Error: {error}"),
+    ("error.json", "❌ JSON Error: {error}"),
+    ("error.config", "❌ Config Error: {error}"),
+    ("error.usage", "❌ Usage Error: {error}"),
+    ("error.generic", "Error: {error}"),
+];
+
+static ES: &[(&str, &str)] = &[
+
+    ("build.title", "📊 Información de compilación"),
+    ("build.input_path", "Ruta de entrada: {path}"),
+    ("build.output_dir", "Directorio de salida: {path}"),
+    ("build.estimated.one", "Salida estimada: {count} archivo JavaScript"),
+    ("build.estimated.other", "Salida estimada: {count} archivos JavaScript"),
+    ("build.transpiled_dir.one", "✅ Se transpiló {count} archivo a {dir}"),
+    ("build.transpiled_dir.other", "✅ Se transpilaron {count} archivos a {dir}"),
+    ("build.cached.one", "   ♻️  {count} archivo sin cambios reutilizado desde .ns-cache"),
+    ("build.cached.other", "   ♻️  {count} archivos sin cambios reutilizados desde .ns-cache"),
+    ("build.transpiled_file", "✅ Transpilado {input} → {output}"),
+    ("build.bundled.one", "✅ Se empaquetó {count} archivo en {file}"),
+    ("build.bundled.other", "✅ Se empaquetaron {count} archivos en {file}"),
+    ("build.bundle_needs_dir", "--bundle necesita un directorio de proyecto, pero {path} es un archivo"),
+    ("build.warnings.one", "⚠️  {count} advertencia:"),
+    ("build.warnings.other", "⚠️  {count} advertencias:"),
+    ("build.unused", "nunca se importa desde ningún punto de entrada"),
+    ("build.import_case", "línea {line}: '{specifier}' no coincide con las mayúsculas en disco ({actual}); usa '{fixed}'"),
+    ("build.external_finding", "línea {location}: {message}"),
+    ("build.import_case_failed.one", "{count} importación no coincide con las mayúsculas en disco:"),
+    ("build.import_case_failed.other", "{count} importaciones no coinciden con las mayúsculas en disco:"),
+    ("build.dry_run.one", "🔍 Simulación: se escribiría {count} archivo en {dir}"),
+    ("build.dry_run.other", "🔍 Simulación: se escribirían {count} archivos en {dir}"),
+    ("build.dry_run_size", "({bytes} bytes)"),
+
+
+    ("graph.written.one", "✅ Grafo de {count} módulo escrito en {path}"),
+    ("graph.written.other", "✅ Grafo de {count} módulos escrito en {path}"),
+    ("hermetic.forbidden", "{feature} necesita {tool}, pero --hermetic prohíbe ejecutar herramientas externas"),
+    ("hermetic.features", "Funciones que necesitan herramientas externas:"),
+    ("build.depfile_written.one", "✅ Reglas de dependencias de {count} archivo escritas en {path}"),
+    ("build.depfile_written.other", "✅ Reglas de dependencias de {count} archivos escritas en {path}"),
+    ("compdb.written.one", "✅ Base de datos de compilación con {count} archivo escrita en {path}"),
+    ("compdb.written.other", "✅ Base de datos de compilación con {count} archivos escrita en {path}"),
+
+
+    ("analyze.title", "📈 Informe de análisis"),
+    ("analyze.modules", "Módulos: {count}"),
+    ("analyze.config", "Configuración: {path}"),
+    ("analyze.revision", "Commit: {commit} ({branch})"),
+    ("analyze.detached", "HEAD separado"),
+    ("analyze.scope", "Los informes por módulo cubren {count} de {total} módulos"),
+    ("analyze.blame_title", "📦 Tamaño de salida de {from} a {to}"),
+    ("analyze.blame_failed.one", "⚠️  {count} archivo no se pudo transpilar en algún commit y se omitió"),
+    ("analyze.blame_failed.other", "⚠️  {count} archivos no se pudieron transpilar en algún commit y se omitieron"),
+    ("analyze.blame_unchanged", "✅ Ningún commit de este rango cambió el tamaño de salida"),
+    ("analyze.blame_commits", "Por commit (primer padre), mayor crecimiento primero:"),
+    ("analyze.blame_more.one", "… y {count} archivo más"),
+    ("analyze.blame_more.other", "… y {count} archivos más"),
+    ("analyze.blame_files", "Por archivo:"),
+    ("analyze.heatmap_written", "✅ Mapa de calor de palabras clave escrito en {path}"),
+    ("analyze.unused_title", "🗑️ Archivos sin usar"),
+    ("analyze.no_entries", "⚠️  No hay puntos de entrada configurados. Añade una lista \"entry\" a nsconfig.json o usa --entry."),
+    ("analyze.entry", "Entrada: {path}"),
+    ("analyze.unused_none", "✅ Todos los módulos son alcanzables desde un punto de entrada"),
+    ("analyze.unused_count.one", "{count} archivo nunca se importa desde ningún punto de entrada:"),
+    ("analyze.unused_count.other", "{count} archivos nunca se importan desde ningún punto de entrada:"),
+    ("analyze.profile_title", "⏱️ Tiempo de transpilación por paso (ms)"),
+    ("analyze.profile_file", "Archivo"),
+    ("analyze.profile_total", "Total"),
+    ("analyze.minify_title", "🗜️ Tamaño de salida con --minify"),
+    ("analyze.minify_before", "Antes"),
+    ("analyze.minify_after", "Después"),
+    ("analyze.minify_saved", "Ahorro"),
+    ("check.no_changes", "✅ Ningún archivo NullScript cambió desde {reference}"),
+    ("check.import_case", "'{specifier}' no coincide con las mayúsculas en disco ({actual}); usa '{fixed}'"),
+    ("check.types_missing", "No se encontró el verificador de tipos '{command}'; instala TypeScript (npm install -g typescript) o usa --types=COMANDO"),
+    ("check.ignored.one", "Se ignoró {count} problema fuera de las líneas cambiadas"),
+    ("check.ignored.other", "Se ignoraron {count} problemas fuera de las líneas cambiadas"),
+    ("check.clean.one", "✅ Se revisó {count} archivo, sin problemas"),
+    ("check.clean.other", "✅ Se revisaron {count} archivos, sin problemas"),
+    ("check.problems.one", "⚠️  Se encontró {count} problema"),
+    ("check.problems.other", "⚠️  Se encontraron {count} problemas"),
+    ("check.failed.one", "Se encontró {count} error"),
+    ("check.failed.other", "Se encontraron {count} errores"),
+    ("check.docs_clean.one", "✅ Se revisó {count} bloque de código en {files} archivo(s) Markdown"),
+    ("check.docs_clean.other", "✅ Se revisaron {count} bloques de código en {files} archivo(s) Markdown"),
+    ("check.docs_failed.one", "Falló {count} bloque de código"),
+    ("check.docs_failed.other", "Fallaron {count} bloques de código"),
+    ("editor.not_configured", "No hay editor configurado; define $VISUAL o $EDITOR para usar --open-editor"),
+    ("editor.failed", "{editor} terminó con {status}"),
+    ("editor.pick_title", "Abre la ubicación de un error en tu editor:"),
+    ("prompt.confirm_default_yes", "[S/n]"),
+    ("prompt.confirm_default_no", "[s/N]"),
+    ("prompt.confirm_invalid", "Responde s o n"),
+    ("prompt.select", "Número para elegir, Enter para omitir: "),
+    ("prompt.select_invalid", "Elige un número del 1 al {count}"),
+
+
+    ("run.running", "🚀 Ejecutando NullScript..."),
+    ("run.failed", "❌ No se pudo ejecutar:"),
+    ("run.engine_unavailable", "Este nsc se compiló sin el motor integrado; reinstálalo con --features engine o usa --engine node"),
+    ("run.engine_inspect", "--inspect-memory necesita node; quita --engine builtin para usarlo"),
+    ("run.outside_project", "⚠️  {path} no forma parte del proyecto de {config}: {reason}"),
+    ("run.outside_project_tip", "💡 Usa --allow-outside para ejecutarlo sin esta advertencia"),
+    ("repl.welcome", "REPL de NullScript v{version} ({engine})"),
+    ("repl.hint", "Escribe .help para ver los comandos, .exit o Ctrl+D para salir"),
+    ("repl.help", "  .keywords [TEXTO]  Lista las palabras clave, opcionalmente solo las que contienen TEXTO\n  .help              Muestra esta ayuda\n  .exit              Sale del REPL\n  Una línea vacía termina antes un bloque sin cerrar"),
+    ("repl.unknown_command", "Comando desconocido {command}; escribe .help para ver la lista"),
+    ("repl.node_exited", "La sesión de node terminó inesperadamente"),
+
+
+    ("memory.title", "🧠 Informe de memoria"),
+    ("memory.read_failed", "❌ No se pudo leer la instantánea del heap:"),
+    ("memory.write_failed", "❌ No se pudo escribir el informe de memoria:"),
+    ("memory.report", "Informe: {path}"),
+    ("memory.snapshot", "Instantánea: {path}"),
+    ("memory.signal_tip", "💡 Envía SIGUSR2 durante la ejecución para capturar más instantáneas en {dir}"),
+
+
+    ("keywords.title", "🎭 Palabras clave de NullScript"),
+    ("keywords.mapping", "📋 Palabras clave NullScript → JavaScript:"),
+    ("keywords.category.basics.one", "Básicas ({count} palabra clave)"),
+    ("keywords.category.basics.other", "Básicas ({count} palabras clave)"),
+    ("keywords.category.operators.one", "Operadores ({count} palabra clave)"),
+    ("keywords.category.operators.other", "Operadores ({count} palabras clave)"),
+    ("keywords.category.advanced.one", "Avanzadas ({count} palabra clave)"),
+    ("keywords.category.advanced.other", "Avanzadas ({count} palabras clave)"),
+    ("keywords.category.console.one", "Consola ({count} palabra clave)"),
+    ("keywords.category.console.other", "Consola ({count} palabras clave)"),
+    ("keywords.category.globals.one", "Globales ({count} palabra clave)"),
+    ("keywords.category.globals.other", "Globales ({count} palabras clave)"),
+    ("keywords.category.functions.one", "Funciones globales ({count} palabra clave)"),
+    ("keywords.category.functions.other", "Funciones globales ({count} palabras clave)"),
+    ("keywords.unknown_category", "Categoría de palabras clave desconocida '{category}'; se esperaba una de: {expected}"),
+    ("keywords.no_matches", "Ninguna palabra clave coincide con el filtro"),
+    ("keywords.tip", "💡 Consejo: usa las palabras clave de NullScript en tus archivos .ns; se transpilarán a JavaScript"),
+
+
+    ("update.checking", "🔎 Buscando una versión más reciente de nsc..."),
+    ("update.current", "Versión instalada: {version}"),
+    ("update.latest", "Última versión: {version}"),
+    ("update.up_to_date", "✅ nsc está actualizado"),
+    ("update.available", "⬆️  nsc {version} está disponible"),
+    ("update.npm_managed", "💡 Este nsc se instaló con npm; ejecuta 'npm install -g nullscript@latest' para actualizarlo"),
+    ("update.downloading", "📥 Descargando {asset}..."),
+    ("update.verified", "✅ Suma de verificación correcta"),
+    ("update.installed", "✅ Actualizado a nsc {version} ({path})"),
+    ("generate.regenerated.one", "⚙️  {count} archivo regenerado:"),
+    ("generate.regenerated.other", "⚙️  {count} archivos regenerados:"),
+    ("generate.up_to_date.one", "⚙️  {count} archivo generado está al día"),
+    ("generate.up_to_date.other", "⚙️  {count} archivos generados están al día"),
+    ("explain.title", "🔍 Transformaciones aplicadas"),
+    ("explain.none", "ninguna transformación coincidió"),
+    ("explain.removed", "(eliminado)"),
+    ("explain.written", "🔍 Transformaciones aplicadas escritas en {path}"),
+    ("convert.done", "✅ Convertido {input} → {output}"),
+    ("convert.exists", "{path} ya existe; usa --force para sobrescribirlo"),
+    ("convert.overwrite", "{path} ya existe. ¿Sobrescribirlo?"),
+    ("convert.collision.identifier", "línea {line}: '{word}' es una palabra clave de NullScript y se reescribirá al transpilar"),
+    ("convert.collision_tip", "💡 Renómbralos, o añade los identificadores a \"protected\" en nsconfig.json, para que la salida sea idéntica"),
+    ("convert.roundtrip_exact", "🔁 Al transpilar el resultado se obtiene el JavaScript original"),
+    ("convert.roundtrip_differs", "🔁 Al transpilar el resultado difiere del original a partir de la línea {line}"),
+    ("convert.roundtrip_failed", "🔁 El archivo convertido no se puede transpilar:"),
+    ("convert.fixture", "🧪 Fixture de ida y vuelta guardado en {dir}"),
+    ("convert.fixture_failed", "No se guardó ningún fixture porque el archivo convertido no se puede transpilar"),
+    ("fmt.changed", "✨ Formateado {path}"),
+    ("fmt.would_change", "✗ {path} no está formateado"),
+    ("fmt.done.one", "✅ {count} archivo formateado"),
+    ("fmt.done.other", "✅ {count} archivos formateados"),
+    ("fmt.unchanged.one", "✅ {count} archivo ya estaba formateado"),
+    ("fmt.unchanged.other", "✅ {count} archivos ya estaban formateados"),
+    ("fmt.check_failed.one", "{count} archivo no está formateado; ejecuta nsc fmt para corregirlo"),
+    ("fmt.check_failed.other", "{count} archivos no están formateados; ejecuta nsc fmt para corregirlos"),
+    ("fmt.missing", "{path} no es un archivo, un directorio ni un patrón glob"),
+    ("lint.title", "Reglas de lint"),
+    ("lint.clean.one", "✅ Se analizó {count} archivo, sin problemas"),
+    ("lint.clean.other", "✅ Se analizaron {count} archivos, sin problemas"),
+    ("codemod.title", "Codemods incluidos"),
+    ("codemod.unknown", "Codemod desconocido '{name}'; indica un archivo .ns-codemod o uno de: {builtins}"),
+    ("codemod.rewrote.one", "✏️ {path}: {count} reemplazo"),
+    ("codemod.rewrote.other", "✏️ {path}: {count} reemplazos"),
+    ("codemod.dry_run.one", "{count} archivo cambiaría; vuelve a ejecutarlo con --write para aplicarlo"),
+    ("codemod.dry_run.other", "{count} archivos cambiarían; vuelve a ejecutarlo con --write para aplicarlo"),
+    ("codemod.done.one", "✅ {count} archivo reescrito"),
+    ("codemod.done.other", "✅ {count} archivos reescritos"),
+    ("codemod.unchanged", "✅ No hay nada que cambiar"),
+    ("examples.title", "🎓 Ejemplos de NullScript"),
+    ("examples.hello", "Hola mundo con una función y plantillas de texto"),
+    ("examples.classes", "Clases, herencia y campos estáticos"),
+    ("examples.async", "Funciones asíncronas, promesas y fetch"),
+    ("examples.files", "Escribir y leer archivos con fs/promises"),
+    ("examples.tip", "💡 Muestra uno con 'nsc examples <nombre>' y ejecútalo con --run"),
+    ("examples.source", "📄 {name}.ns"),
+    ("examples.run_tip", "💡 Ejecútalo con 'nsc examples {name} --run'"),
+    ("examples.unknown", "Ejemplo desconocido '{name}'. Ejemplos disponibles: {names}"),
+    ("system.title", "🔧 Información del sistema"),
+    ("system.available", "✅ Disponible"),
+    ("system.not_found", "❌ No encontrado"),
+    ("system.target", "Target: {target} (requiere Node.js {version}+)"),
+    ("target.node_too_old", "⚠️  Node.js {node} no puede ejecutar código {target}, que requiere Node.js {required}+. Actualiza Node.js o baja \"target\" en nsconfig.json."),
+
+
+    ("info.title", "📁 Información del archivo"),
+    ("info.missing_path", "❌ La ruta no existe: {path}"),
+    ("info.path", "Ruta: {path}"),
+    ("info.extension", "Extensión: {ext}"),
+    ("info.name", "Nombre: {name}"),
+    ("info.size", "Tamaño: {size}"),
+    ("info.lines", "Líneas: {count}"),
+    ("info.modified", "Modificado hace {duration}"),
+    ("info.details", "📋 Detalles de archivos:"),
+    ("info.total_size", "Tamaño total: {size}"),
+    ("info.total_files", "Archivos totales: {count}"),
+    ("info.nullscript_files", "Archivos NullScript: {count}"),
+    ("info.config_none", "No se encontró nsconfig.json; se usan los valores predeterminados"),
+
+
+    ("duration.seconds.one", "{count} segundo"),
+    ("duration.seconds.other", "{count} segundos"),
+    ("duration.minutes.one", "{count} minuto"),
+    ("duration.minutes.other", "{count} minutos"),
+    ("duration.hours.one", "{count} hora"),
+    ("duration.hours.other", "{count} horas"),
+    ("duration.days.one", "{count} día"),
+    ("duration.days.other", "{count} días"),
+    ("duration.unknown", "desconocido"),
+
+
+    ("config.outside_root", "está fuera de rootDir {dir}"),
+    ("config.not_included", "no coincide con ningún patrón de include"),
+    ("config.excluded", "coincide con un patrón de exclude"),
+    ("config.title", "⚙️  Configuración efectiva"),
+    ("config.status_included", "✅ Forma parte del proyecto"),
+    ("config.status_excluded", "⚠️  No forma parte del proyecto: {reason}"),
+    ("config.chain", "📚 Archivos de configuración (los posteriores sobrescriben a los anteriores)"),
+    ("config.values", "📋 Valores"),
+    ("config.default", "predeterminado"),
+    ("config.version_mismatch", "⚠️  {config} requiere nsc {range}, pero este es nsc {version}"),
+    ("config.version_required", "{config} requiere nsc {range}, pero este es nsc {version}"),
+    ("config.version_mismatch_tip", "💡 Instala un nsc compatible o actualiza \"nscVersion\"; usa \"strictVersion\": true para que sea un error"),
+
+
+    ("error.location_in", "en"),
+    ("error.external_tool", "❌ {tool} terminó con el código {code}"),
+    ("error.external_tool_command", "   Comando: {command}"),
+    ("error.io", "❌ Error de E/S: {error}"),
+    ("error.more_syntax.one", "... y 1 error de sintaxis más (sube --max-errors para verlo)"),
+    ("error.more_syntax.other", "... y {count} errores de sintaxis más (sube --max-errors para verlos)"),
+    ("error.regex", "❌ Error de expresión regular: {error}"),
+    ("error.json", "❌ Error de JSON: {error}"),
+    ("error.config", "❌ Error de configuración: {error}"),
+    ("error.usage", "❌ Error de uso: {error}"),
+    ("error.generic", "Error: {error}"),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_locale_selection() {
+        assert_eq!(Lang::from_tag("es_ES.UTF-8"), Some(Lang::Es));
+        assert_eq!(Lang::from_tag("en-GB"), Some(Lang::En));
+        assert_eq!(Lang::from_tag("C"), Some(Lang::En));
+        assert_eq!(Lang::from_tag("fr_FR"), None);
+
+        let env = |vars: &'static [(&'static str, &'static str)]| {
+            move |name: &str| vars.iter().find(|(k, _)| *k == name).map(|(_, v)| v.to_string())
+        };
+
+        assert_eq!(Lang::from_env(Some("es"), env(&[("NSC_LANG", "en")])), Lang::Es);
+        assert_eq!(Lang::from_env(Some("fr"), env(&[("LANG", "es_MX.UTF-8")])), Lang::Es);
+        assert_eq!(Lang::from_env(None, env(&[("NSC_LANG", ""), ("LC_ALL", "es_ES"), ("LANG", "en_US")])), Lang::Es);
+        assert_eq!(Lang::from_env(None, env(&[("LANG", "de_DE.UTF-8")])), Lang::En);
+        assert_eq!(Lang::from_env(None, env(&[])), Lang::En);
+    }
+
+    #[test]
+    fn test_missing_keys_fall_back_to_english() {
+        assert_eq!(translate(Lang::Es, "build.unused"), "nunca se importa desde ningún punto de entrada");
+        assert_eq!(translate(Lang::Es, "no.such.key"), "");
+
+        static PARTIAL: &[(&str, &str)] = &[("build.unused", "nunca importado")];
+        assert_eq!(resolve(PARTIAL, "build.unused"), "nunca importado");
+        assert_eq!(resolve(PARTIAL, "build.title"), "📊 Build Information");
+
+        let keys = |catalog: &'static [(&'static str, &'static str)]| catalog.iter().map(|(k, _)| *k).collect::<std::collections::BTreeSet<_>>();
+        assert_eq!(keys(EN), keys(ES));
+    }
+
+    #[test]
+    fn test_plural_forms_and_placeholders() {
+        assert_eq!(plural_in(Lang::En, "build.estimated", 1, &[]), "Estimated output: 1 JavaScript file");
+        assert_eq!(plural_in(Lang::En, "build.estimated", 3, &[]), "Estimated output: 3 JavaScript files");
+        assert_eq!(plural_in(Lang::En, "build.transpiled_dir", 0, &[("dir", &"dist")]), "✅ Transpiled 0 files to dist");
+        assert_eq!(format_in(Lang::En, "build.input_path", &[("path", &"src/")]), "Input path: src/");
+    }
+}
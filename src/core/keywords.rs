@@ -1,125 +1,163 @@
-pub static KEYWORDS: &[(&str, &str)] = &[
-
-    ("run", "function"),
-    ("return", "return"),
-    ("let", "let"),
-    ("fixed", "const"),
-    ("var", "var"),
-    ("share", "export"),
-    ("use", "import"),
-    ("whatever", "if"),
-    ("otherwise", "else"),
-    ("since", "for"),
-    ("when", "while"),
-    ("switch", "switch"),
-    ("case", "case"),
-    ("done", "default"),
-    ("stop", "break"),
-    ("keepgoing", "continue"),
-    ("test", "try"),
-    ("grab", "catch"),
-    ("atLast", "finally"),
-    ("fresh", "new"),
-    ("self", "this"),
-    ("parent", "super"),
-    ("model", "class"),
-    ("remove", "delete"),
-    ("null", "null"),
-    ("yes", "true"),
-    ("no", "false"),
-    ("undefined", "undefined"),
-    ("is", "==="),
-    ("isnt", "!=="),
-    ("more", ">"),
-    ("less", "<"),
-    ("moreeq", ">="),
-    ("lesseq", "<="),
-    ("and", "&&"),
-    ("or", "||"),
-    ("not", "!"),
-
-
-    ("trigger", "throw"),
-    ("inherits", "extends"),
-    ("__init__", "constructor"),
-    ("forever", "static"),
-    ("later", "async"),
-    ("hold", "await"),
-    ("what", "typeof"),
-    ("kind", "instanceof"),
-    ("inside", "in"),
-    ("part", "of"),
-    ("nothing", "void"),
-    ("using", "with"),
-    ("freeze", "debugger"),
-    ("pause", "yield"),
-    ("getter", "get"),
-    ("setter", "set"),
-
-
-    ("speak", "console"),
-    ("say", "log"),
-    ("yell", "warn"),
-    ("scream", "error"),
-    ("whisper", "info"),
-    ("peek", "debug"),
-    ("check", "assert"),
-    ("wipe", "clear"),
-    ("tally", "count"),
-    ("resetcount", "countReset"),
-    ("dir", "dir"),
-    ("deepdir", "dirxml"),
-    ("group", "group"),
-    ("fold", "groupCollapsed"),
-    ("ungroup", "groupEnd"),
-    ("show", "table"),
-    ("time", "time"),
-    ("stoptimer", "timeEnd"),
-    ("logtimer", "timeLog"),
-    ("backtrace", "trace"),
-
-
-    ("thing", "Object"),
-    ("list", "Array"),
-    ("text", "String"),
-    ("num", "Number"),
-    ("bool", "Boolean"),
-    ("clock", "Date"),
-    ("maths", "Math"),
-    ("json", "JSON"),
-    ("pattern", "RegExp"),
-    ("fail", "Error"),
-    ("promise", "Promise"),
-    ("dict", "Map"),
-    ("unique", "Set"),
-    ("weakdict", "WeakMap"),
-    ("weakunique", "WeakSet"),
-    ("symbol", "Symbol"),
-    ("proxy", "Proxy"),
-    ("reflect", "Reflect"),
-    ("intl", "Intl"),
-    ("wasm", "WebAssembly"),
-
-
-    ("toint", "parseInt"),
-    ("tofloat", "parseFloat"),
-    ("isnan", "isNaN"),
-    ("isfinite", "isFinite"),
-    ("encodeurl", "encodeURI"),
-    ("encodeurlpart", "encodeURIComponent"),
-    ("decodeurl", "decodeURI"),
-    ("decodeurlpart", "decodeURIComponent"),
-    ("esc", "escape"),
-    ("unesc", "unescape"),
-    ("runcode", "eval"),
-    ("delay", "setTimeout"),
-    ("repeat", "setInterval"),
-    ("stopdelay", "clearTimeout"),
-    ("stoprepeat", "clearInterval"),
-    ("pull", "fetch"),
-    ("need", "require"),
+use crate::core::config::DiagnosticLevel;
+use std::sync::LazyLock;
+use KeywordCategory::*;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeywordCategory {
+    Basics,
+    Operators,
+    Advanced,
+    Console,
+    Globals,
+    Functions,
+}
+
+impl KeywordCategory {
+    pub const ALL: [KeywordCategory; 6] = [Basics, Operators, Advanced, Console, Globals, Functions];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Basics => "basics",
+            Operators => "operators",
+            Advanced => "advanced",
+            Console => "console",
+            Globals => "globals",
+            Functions => "functions",
+        }
+    }
+
+    pub fn parse(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|category| category.as_str().eq_ignore_ascii_case(name.trim()))
+    }
+}
+
+pub static CATEGORIZED_KEYWORDS: &[(&str, &str, KeywordCategory)] = &[
+
+    ("run", "function", Basics),
+    ("return", "return", Basics),
+    ("let", "let", Basics),
+    ("fixed", "const", Basics),
+    ("var", "var", Basics),
+    ("share", "export", Basics),
+    ("use", "import", Basics),
+    ("whatever", "if", Basics),
+    ("otherwise", "else", Basics),
+    ("since", "for", Basics),
+    ("when", "while", Basics),
+    ("switch", "switch", Basics),
+    ("case", "case", Basics),
+    ("done", "default", Basics),
+    ("stop", "break", Basics),
+    ("keepgoing", "continue", Basics),
+    ("test", "try", Basics),
+    ("grab", "catch", Basics),
+    ("atLast", "finally", Basics),
+    ("fresh", "new", Basics),
+    ("self", "this", Basics),
+    ("parent", "super", Basics),
+    ("model", "class", Basics),
+    ("remove", "delete", Basics),
+    ("null", "null", Basics),
+    ("yes", "true", Basics),
+    ("no", "false", Basics),
+    ("undefined", "undefined", Basics),
+    ("is", "===", Operators),
+    ("isnt", "!==", Operators),
+    ("more", ">", Operators),
+    ("less", "<", Operators),
+    ("moreeq", ">=", Operators),
+    ("lesseq", "<=", Operators),
+    ("and", "&&", Operators),
+    ("or", "||", Operators),
+    ("not", "!", Operators),
+    ("power", "**", Operators),
+    ("leftover", "%", Operators),
+
+
+    ("trigger", "throw", Advanced),
+    ("inherits", "extends", Advanced),
+    ("__init__", "constructor", Advanced),
+    ("forever", "static", Advanced),
+    ("later", "async", Advanced),
+    ("hold", "await", Advanced),
+    ("what", "typeof", Advanced),
+    ("kind", "instanceof", Advanced),
+    ("inside", "in", Advanced),
+    ("part", "of", Advanced),
+    ("nothing", "void", Advanced),
+    ("using", "with", Advanced),
+    ("freeze", "debugger", Advanced),
+    ("pause", "yield", Advanced),
+    ("getter", "get", Advanced),
+    ("setter", "set", Advanced),
+
+
+    ("speak", "console", Console),
+    ("say", "log", Console),
+    ("yell", "warn", Console),
+    ("scream", "error", Console),
+    ("whisper", "info", Console),
+    ("peek", "debug", Console),
+    ("check", "assert", Console),
+    ("wipe", "clear", Console),
+    ("tally", "count", Console),
+    ("resetcount", "countReset", Console),
+    ("dir", "dir", Console),
+    ("deepdir", "dirxml", Console),
+    ("group", "group", Console),
+    ("fold", "groupCollapsed", Console),
+    ("ungroup", "groupEnd", Console),
+    ("show", "table", Console),
+    ("time", "time", Console),
+    ("stoptimer", "timeEnd", Console),
+    ("logtimer", "timeLog", Console),
+    ("backtrace", "trace", Console),
+
+
+    ("thing", "Object", Globals),
+    ("list", "Array", Globals),
+    ("text", "String", Globals),
+    ("num", "Number", Globals),
+    ("bool", "Boolean", Globals),
+    ("clock", "Date", Globals),
+    ("maths", "Math", Globals),
+    ("json", "JSON", Globals),
+    ("pattern", "RegExp", Globals),
+    ("fail", "Error", Globals),
+    ("promise", "Promise", Globals),
+    ("dict", "Map", Globals),
+    ("unique", "Set", Globals),
+    ("weakdict", "WeakMap", Globals),
+    ("weakunique", "WeakSet", Globals),
+    ("symbol", "Symbol", Globals),
+    ("proxy", "Proxy", Globals),
+    ("reflect", "Reflect", Globals),
+    ("intl", "Intl", Globals),
+    ("wasm", "WebAssembly", Globals),
+
+
+    ("toint", "parseInt", Functions),
+    ("tofloat", "parseFloat", Functions),
+    ("isnan", "isNaN", Functions),
+    ("isfinite", "isFinite", Functions),
+    ("encodeurl", "encodeURI", Functions),
+    ("encodeurlpart", "encodeURIComponent", Functions),
+    ("decodeurl", "decodeURI", Functions),
+    ("decodeurlpart", "decodeURIComponent", Functions),
+    ("esc", "escape", Functions),
+    ("unesc", "unescape", Functions),
+    ("runcode", "eval", Functions),
+    ("delay", "setTimeout", Functions),
+    ("repeat", "setInterval", Functions),
+    ("stopdelay", "clearTimeout", Functions),
+    ("stoprepeat", "clearInterval", Functions),
+    ("pull", "fetch", Functions),
+    ("need", "require", Functions),
 ];
 
+pub static KEYWORDS: LazyLock<Vec<(&str, &str)>> =
+    LazyLock::new(|| CATEGORIZED_KEYWORDS.iter().map(|(keyword, js_keyword, _)| (*keyword, *js_keyword)).collect());
+
 
 
 
@@ -147,24 +185,213 @@ pub static FORBIDDEN_KEYWORDS: &[&str] = &[
 ];
 
 
-pub static INVALID_SYNTAX: &[&str] = &[
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SyntaxContext {
+    Statement,
+    ClassBody,
+    Parameters,
+    ReturnType,
+    Expression,
+}
+
+#[derive(Debug)]
+pub struct SyntaxRule {
+    pub name: &'static str,
+    pub pattern: &'static str,
+    pub context: SyntaxContext,
+    pub message: &'static str,
+    pub level: DiagnosticLevel,
+}
+
+
+pub static INVALID_SYNTAX: &[SyntaxRule] = &[
+    SyntaxRule {
+        name: "declaration_type",
+        pattern: r"^(?:let|fixed|var)\s+[A-Za-z_$][\w$]*\s*!?\s*(:\s*(?:[A-Za-z_$][\w$.]*(?:<[^<>]*>)?(?:\[\])*|[{\[(]))",
+        context: SyntaxContext::Statement,
+        message: "Type annotations are TypeScript; drop the ': type' after the name.",
+        level: DiagnosticLevel::Error,
+    },
+    SyntaxRule {
+        name: "field_type",
+        pattern: r"^(?:(?:forever|static)\s+)?#?[A-Za-z_$][\w$]*\s*[?!]?\s*(:\s*(?:[A-Za-z_$][\w$.]*(?:<[^<>]*>)?(?:\[\])*|[{\[(]))",
+        context: SyntaxContext::ClassBody,
+        message: "Field types are TypeScript; drop the ': type' after the field name.",
+        level: DiagnosticLevel::Error,
+    },
+    SyntaxRule {
+        name: "parameter_type",
+        pattern: r"^[A-Za-z_$][\w$]*\s*\??\s*(:\s*(?:[A-Za-z_$][\w$.]*(?:<[^<>]*>)?(?:\[\])*|[{\[(]))",
+        context: SyntaxContext::Parameters,
+        message: "Parameter types are TypeScript; drop the ': type' after the parameter.",
+        level: DiagnosticLevel::Error,
+    },
+    SyntaxRule {
+        name: "return_type",
+        pattern: r"^(:\s*(?:[A-Za-z_$][\w$.]*(?:<[^<>{}]*>)?(?:\[\])*)(?:\s*\|\s*[A-Za-z_$][\w$.]*(?:<[^<>{}]*>)?(?:\[\])*)*)\s*(?:\{|=>)",
+        context: SyntaxContext::ReturnType,
+        message: "Return types are TypeScript; drop the ': type' after the parameter list.",
+        level: DiagnosticLevel::Error,
+    },
+    SyntaxRule {
+        name: "type_assertion",
+        pattern: r"^as\s+(?:string|number|boolean|any|unknown|never|object|const)\b",
+        context: SyntaxContext::Expression,
+        message: "'as' type assertions are TypeScript; remove the assertion.",
+        level: DiagnosticLevel::Error,
+    },
+    SyntaxRule {
+        name: "angle_assertion",
+        pattern: r"^<\s*(?:string|number|boolean|any|unknown|never|object)\s*(?:\[\s*\])?\s*>",
+        context: SyntaxContext::Expression,
+        message: "Type arguments and '<type>' assertions are TypeScript; remove them.",
+        level: DiagnosticLevel::Error,
+    },
+    SyntaxRule {
+        name: "type_parameters",
+        pattern: r"^<\s*(?:const\s+)?[A-Z][\w$]*\s*(?:extends\b[^<>;]*|(?:,\s*[A-Z][\w$]*\s*)*)>\s*\(",
+        context: SyntaxContext::Expression,
+        message: "Generic type parameters are TypeScript; remove the '<T>' part.",
+        level: DiagnosticLevel::Warn,
+    },
+    SyntaxRule {
+        name: "decorator",
+        pattern: r"^@[A-Za-z_$][\w$]*",
+        context: SyntaxContext::Statement,
+        message: "Decorators are not supported in NullScript; call the function directly instead.",
+        level: DiagnosticLevel::Error,
+    },
+];
+
+
+pub static JS_RESERVED_WORDS: &[&str] = &[
+    "break", "case", "catch", "class", "const", "continue", "debugger", "default",
+    "delete", "do", "else", "export", "extends", "false", "finally", "for",
+    "function", "if", "import", "in", "instanceof", "new", "null", "return",
+    "super", "switch", "this", "throw", "true", "try", "typeof", "var",
+    "void", "while", "with", "yield", "let", "static", "await", "enum",
+    "implements", "interface", "package", "private", "protected", "public",
+    "arguments", "eval",
+];
+
+
+pub static JS_GLOBALS: &[&str] = &[
+    "globalThis", "window", "document", "console", "process", "require", "module", "exports",
+    "Object", "Array", "String", "Number", "Boolean", "Symbol", "BigInt", "Function",
+    "Math", "JSON", "Date", "RegExp", "Error", "TypeError", "RangeError", "Promise",
+    "Map", "Set", "WeakMap", "WeakSet", "Proxy", "Reflect", "Intl", "Buffer", "URL",
+    "setTimeout", "setInterval", "clearTimeout", "clearInterval", "queueMicrotask",
+    "structuredClone", "fetch", "parseInt", "parseFloat", "isNaN", "isFinite",
+    "undefined", "NaN", "Infinity",
+];
+
+
+pub static GLOBAL_ALIASES: &[&str] = &[
+    "speak", "parent",
+    "thing", "list", "text", "num", "bool", "clock", "maths", "json", "pattern", "fail",
+    "promise", "dict", "unique", "weakdict", "weakunique", "symbol", "proxy", "reflect",
+    "intl", "wasm",
+];
 
-    ": string", ": number", ": boolean", ": any", ": void", ": never",
-    ": unknown", ": object", ": array", ": tuple", ": union",
-    ": String", ": Number", ": Boolean", ": Any", ": Void", ": Never",
-    ": Unknown", ": Object", ": Array", ": Tuple", ": Union",
 
+pub static ARITHMETIC_ALIASES: &[&str] = &["power", "leftover"];
 
-    "<T>", "<string>", "<number>", "<boolean>", "<any>",
-    "extends T", "implements I", "infer U",
 
+pub fn is_operator_alias(nullscript_keyword: &str) -> bool {
+    KEYWORDS.iter().any(|(keyword, js_keyword)| {
+        *keyword == nullscript_keyword && js_keyword.chars().all(|c| "=!<>&|*%".contains(c))
+    })
+}
 
-    "as string", "as number", "as boolean", "as any",
-    "satisfies T", "asserts T", "const T",
 
+pub fn keyword_categories() -> Vec<(KeywordCategory, Vec<(&'static str, &'static str)>)> {
+    KeywordCategory::ALL
+        .into_iter()
+        .map(|category| {
+            let keywords = CATEGORIZED_KEYWORDS
+                .iter()
+                .filter(|(_, _, of)| *of == category)
+                .map(|(keyword, js_keyword, _)| (*keyword, *js_keyword))
+                .collect();
+            (category, keywords)
+        })
+        .collect()
+}
 
-    "interface", "enum", "namespace",
 
+pub static RUNTIME_SHIM_ALIASES: &[&str] = &["speak", "maths"];
 
-    "@decorator", "@Component", "@Injectable", "@Input", "@Output",
+
+pub static CONSOLE_METHODS: &[&str] = &[
+    "say", "yell", "scream", "whisper", "peek", "check", "wipe", "tally", "resetcount", "dir",
+    "deepdir", "group", "fold", "ungroup", "show", "time", "stoptimer", "logtimer", "backtrace",
 ];
+
+
+pub fn console_methods() -> Vec<(&'static str, &'static str)> {
+    KEYWORDS.iter().filter(|(keyword, _)| CONSOLE_METHODS.contains(keyword)).copied().collect()
+}
+
+
+pub fn is_global_alias(name: &str) -> bool {
+    KEYWORDS
+        .iter()
+        .filter(|(nullscript_keyword, _)| GLOBAL_ALIASES.contains(nullscript_keyword))
+        .any(|(nullscript_keyword, js_keyword)| *nullscript_keyword == name || *js_keyword == name)
+}
+
+
+pub const KEYWORD_TABLE_VERSION: u32 = 2;
+
+pub fn keyword_table_hash() -> String {
+    use sha2::{Digest, Sha256};
+
+    let mut hasher = Sha256::new();
+
+    for (nullscript_keyword, js_keyword) in KEYWORDS.iter() {
+        hasher.update(nullscript_keyword.as_bytes());
+        hasher.update(b"=");
+        hasher.update(js_keyword.as_bytes());
+        hasher.update(b"\n");
+    }
+
+    for keyword in FORBIDDEN_KEYWORDS.iter() {
+        hasher.update(b"!");
+        hasher.update(keyword.as_bytes());
+        hasher.update(b"\n");
+    }
+
+    for rule in INVALID_SYNTAX.iter() {
+        hasher.update(format!("!{}={}:{:?}:{:?}\n", rule.name, rule.pattern, rule.context, rule.level).as_bytes());
+    }
+
+    for alias in GLOBAL_ALIASES.iter() {
+        hasher.update(b".");
+        hasher.update(alias.as_bytes());
+        hasher.update(b"\n");
+    }
+
+    hasher
+        .finalize()
+        .iter()
+        .take(8)
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_keyword_categories_partition_the_table() {
+        let categories = keyword_categories();
+        let covered: Vec<_> = categories.iter().flat_map(|(_, keywords)| keywords.iter().copied()).collect();
+
+        assert_eq!(covered, KEYWORDS.to_vec());
+        assert_eq!(categories[1].1.first(), Some(&("is", "===")));
+        assert_eq!(categories[3].1.last(), Some(&("backtrace", "trace")));
+        assert_eq!(KeywordCategory::parse("Console"), Some(Console));
+        assert_eq!(KeywordCategory::parse("loops"), None);
+    }
+}
@@ -1,5 +1,8 @@
+pub mod config;
 pub mod errors;
+pub mod i18n;
 pub mod keywords;
 pub mod types;
+pub mod version;
 
 pub use errors::*;
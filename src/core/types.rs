@@ -1,3 +1,4 @@
+use crate::core::i18n::tr;
 use std::path::PathBuf;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -21,7 +22,7 @@ impl Location {
 
         if let Some(file_path) = &self.file_path {
             if let Some(file_name) = file_path.file_name() {
-                output.push_str(&format!(" in {}", file_name.to_string_lossy()));
+                output.push_str(&format!(" {} {}", tr("error.location_in"), file_name.to_string_lossy()));
             }
         }
 
@@ -0,0 +1,182 @@
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Version {
+    pub major: u64,
+    pub minor: u64,
+    pub patch: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Op {
+    Gt,
+    Ge,
+    Lt,
+    Le,
+    Eq,
+}
+
+#[derive(Debug, Clone)]
+pub struct VersionReq {
+    alternatives: Vec<Vec<(Op, Version)>>,
+}
+
+impl Version {
+    pub const fn new(major: u64, minor: u64, patch: u64) -> Self {
+        Self { major, minor, patch }
+    }
+
+    pub fn current() -> Self {
+        Self::parse(env!("CARGO_PKG_VERSION")).unwrap_or(Self::new(0, 0, 0))
+    }
+
+    pub fn parse(version: &str) -> Option<Self> {
+        match parse_partial(version)? {
+            (_, 0) => None,
+            (version, _) => Some(version),
+        }
+    }
+}
+
+impl fmt::Display for Version {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+    }
+}
+
+impl VersionReq {
+    pub fn parse(range: &str) -> Result<Self, String> {
+        let alternatives = range
+            .split("||")
+            .map(|set| {
+                let mut comparators = Vec::new();
+                let mut operator = String::new();
+                for token in set.split_whitespace() {
+                    if token.chars().all(|c| "<>=^~".contains(c)) {
+                        operator.push_str(token);
+                        continue;
+                    }
+                    let token = std::mem::take(&mut operator) + token;
+                    comparators.extend(parse_comparator(&token).ok_or_else(|| {
+                        format!("invalid version range '{}': cannot read '{}'", range.trim(), token)
+                    })?);
+                }
+                if !operator.is_empty() {
+                    return Err(format!("invalid version range '{}': '{}' has no version after it", range.trim(), operator));
+                }
+                Ok(comparators)
+            })
+            .collect::<Result<_, String>>()?;
+
+        Ok(Self { alternatives })
+    }
+
+    pub fn matches(&self, version: &Version) -> bool {
+        self.alternatives.iter().any(|comparators| {
+            comparators.iter().all(|(op, bound)| match op {
+                Op::Gt => version > bound,
+                Op::Ge => version >= bound,
+                Op::Lt => version < bound,
+                Op::Le => version <= bound,
+                Op::Eq => version == bound,
+            })
+        })
+    }
+}
+
+fn parse_partial(version: &str) -> Option<(Version, usize)> {
+    let core = version.trim().trim_start_matches(['v', '=']).split(['-', '+']).next()?;
+    let mut parts = [0u64; 3];
+    let mut given = 0;
+
+    for (index, part) in core.split('.').enumerate() {
+        if index >= 3 {
+            return None;
+        }
+        if matches!(part, "x" | "X" | "*") {
+            break;
+        }
+        parts[index] = part.parse().ok()?;
+        given = index + 1;
+    }
+
+    Some((Version::new(parts[0], parts[1], parts[2]), given))
+}
+
+fn bump(version: Version, given: usize) -> Version {
+    match given {
+        0 => Version::new(u64::MAX, 0, 0),
+        1 => Version::new(version.major + 1, 0, 0),
+        2 => Version::new(version.major, version.minor + 1, 0),
+        _ => Version::new(version.major, version.minor, version.patch + 1),
+    }
+}
+
+fn parse_comparator(token: &str) -> Option<Vec<(Op, Version)>> {
+    if let Some(rest) = token.strip_prefix('^') {
+        let (version, given) = parse_partial(rest)?;
+        let upper = match (version.major, version.minor, given) {
+            (0, 0, 3) => bump(version, 3),
+            (0, _, given) if given >= 2 => bump(version, 2),
+            _ => bump(version, given.min(1)),
+        };
+        return Some(vec![(Op::Ge, version), (Op::Lt, upper)]);
+    }
+
+    if let Some(rest) = token.strip_prefix('~') {
+        let (version, given) = parse_partial(rest)?;
+        return Some(vec![(Op::Ge, version), (Op::Lt, bump(version, given.clamp(1, 2)))]);
+    }
+
+    let (op, rest) = [(">=", Op::Ge), ("<=", Op::Le), (">", Op::Gt), ("<", Op::Lt), ("=", Op::Eq)]
+        .into_iter()
+        .find_map(|(prefix, op)| token.strip_prefix(prefix).map(|rest| (op, rest)))
+        .unwrap_or((Op::Eq, token));
+
+    let (version, given) = parse_partial(rest)?;
+    if given == 3 {
+        return Some(vec![(op, version)]);
+    }
+
+    Some(match op {
+        Op::Eq if given == 0 => Vec::new(),
+        Op::Eq => vec![(Op::Ge, version), (Op::Lt, bump(version, given))],
+        Op::Gt => vec![(Op::Ge, bump(version, given))],
+        Op::Le => vec![(Op::Lt, bump(version, given))],
+        op => vec![(op, version)],
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn satisfies(range: &str, version: &str) -> bool {
+        VersionReq::parse(range).unwrap().matches(&Version::parse(version).unwrap())
+    }
+
+    #[test]
+    fn test_version_ranges() {
+        assert!(satisfies("^2.0.0", "2.7.1"));
+        assert!(!satisfies("^2.0.0", "3.0.0"));
+        assert!(satisfies("^0.2.1", "0.2.9"));
+        assert!(!satisfies("^0.2.1", "0.3.0"));
+        assert!(satisfies("~2.0", "2.0.3"));
+        assert!(!satisfies("~2.0", "2.1.0"));
+        assert!(satisfies(">=2.0.0 <2.1", "2.0.3"));
+        assert!(satisfies(">= 2.0 < 3", "2.4.0"));
+        assert!(!satisfies(">= 2.0 < 3", "3.0.0"));
+        assert!(satisfies("^ 1.2", "1.9.0"));
+        assert!(VersionReq::parse(">= 2.0 <").is_err());
+        assert!(!satisfies(">2.0", "2.0.9"));
+        assert!(satisfies("<=2.0", "2.0.9"));
+        assert!(satisfies("1.x || 2.0.x", "2.0.3"));
+        assert!(satisfies("*", "9.9.9"));
+        assert!(satisfies("=2.0.3", "2.0.3"));
+        assert!(!satisfies("2.0.2", "2.0.3"));
+
+        assert!(VersionReq::parse(">=two").is_err());
+        assert_eq!(Version::parse("v2.1"), Some(Version::new(2, 1, 0)));
+        assert!(Version::parse("latest").is_none());
+    }
+}
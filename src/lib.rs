@@ -0,0 +1,5 @@
+#[cfg(feature = "cli")]
+pub mod cli;
+pub mod compiler;
+pub mod core;
+pub mod utils;
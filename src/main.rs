@@ -1,12 +1,8 @@
-mod cli;
-mod core;
-mod compiler;
-mod utils;
-
-use cli::run;
+use nullscript::cli::{output, run};
+use nullscript::core;
 use std::env;
 
-#[tokio::main]
+#[tokio::main(flavor = "current_thread")]
 async fn main() {
     let args: Vec<String> = env::args().collect();
     let program_path = args.first().map(|s| s.as_str()).unwrap_or("");
@@ -23,22 +19,24 @@ async fn main() {
             std::process::exit(0);
         }
 
-        println!("🎭 NullScript Transpiler v{}", env!("CARGO_PKG_VERSION"));
-        println!("==================================================");
-        println!("🚀 To transpile NullScript code, use the 'nsc' command.");
-        println!();
-        println!("📝 Examples:");
-        println!("   nsc --help                   # Show help");
-        println!("   nsc keywords                 # Show all keywords");
-        println!("   nsc build src/               # Transpile files");
-        println!("   nsc run hello.ns             # Run a file");
-        println!();
-        println!("==================================================");
+        output::set_theme(output::theme_requested(None, args.iter().any(|a| a == "--plain")));
+
+        output::line(format!("🎭 NullScript Transpiler v{}", env!("CARGO_PKG_VERSION")));
+        output::rule_with("=", 50);
+        output::line("🚀 To transpile NullScript code, use the 'nsc' command.");
+        output::blank();
+        output::line("📝 Examples:");
+        output::line("   nsc --help                   # Show help");
+        output::line("   nsc keywords                 # Show all keywords");
+        output::line("   nsc build src/               # Transpile files");
+        output::line("   nsc run hello.ns             # Run a file");
+        output::blank();
+        output::rule_with("=", 50);
         std::process::exit(0);
     }
 
     if let Err(e) = run().await {
-        eprintln!("Error: {}", e);
+        output::eline(core::i18n::trf("error.generic", &[("error", &e)]));
         std::process::exit(1);
     }
 }
@@ -1,16 +1,226 @@
+use crate::core::version::Version;
+use std::io::Read;
 use std::path::Path;
-use std::process::Command;
+use std::process::{Command, Output, Stdio};
+use std::sync::RwLock;
+use std::thread;
+use std::time::{Duration, Instant};
 
 pub struct CommandUtils;
 
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ToolPolicy {
+    pub timeout: Option<Duration>,
+    pub retries: u32,
+    pub hermetic: bool,
+}
+
+impl ToolPolicy {
+    pub fn from_env() -> Self {
+        let mut policy = Self::default();
+
+        if let Some(secs) = std::env::var("NSC_TOOL_TIMEOUT").ok().and_then(|v| v.trim().parse::<u64>().ok()) {
+            policy.timeout = (secs > 0).then(|| Duration::from_secs(secs));
+        }
+
+        if let Some(retries) = std::env::var("NSC_TOOL_RETRIES").ok().and_then(|v| v.trim().parse::<u32>().ok()) {
+            policy.retries = retries;
+        }
+
+        policy.hermetic = std::env::var("NSC_HERMETIC").is_ok_and(|v| matches!(v.trim(), "1" | "true"));
+
+        policy
+    }
+}
+
+static POLICY: RwLock<ToolPolicy> = RwLock::new(ToolPolicy {
+    timeout: None,
+    retries: 0,
+    hermetic: false,
+});
+
+const PROBE_TIMEOUT: Duration = Duration::from_secs(10);
+
 impl CommandUtils {
-    pub fn execute_command(command: &str, args: &[&str]) -> Result<std::process::Output, std::io::Error> {
-        Command::new(command).args(args).output()
+    pub fn set_policy(policy: ToolPolicy) {
+        if let Ok(mut current) = POLICY.write() {
+            *current = policy;
+        }
     }
 
-    pub fn execute_node(script_path: &Path) -> Result<std::process::Output, std::io::Error> {
-        Self::execute_command("node", &[&script_path.to_string_lossy()])
+    pub fn policy() -> ToolPolicy {
+        POLICY.read().map(|p| *p).unwrap_or_default()
     }
 
+    pub fn execute_command(command: &str, args: &[&str]) -> Result<Output, std::io::Error> {
+        Self::execute_with_policy(command, args, Self::policy())
+    }
 
+    pub fn probe_command(command: &str, args: &[&str]) -> Result<Output, std::io::Error> {
+        let mut policy = Self::policy();
+        policy.timeout = Some(policy.timeout.map_or(PROBE_TIMEOUT, |t| t.min(PROBE_TIMEOUT)));
+        Self::execute_with_policy(command, args, policy)
+    }
+
+    pub fn node_version() -> Option<Version> {
+        let output = Self::probe_command("node", &["--version"]).ok().filter(|output| output.status.success())?;
+        Version::parse(String::from_utf8_lossy(&output.stdout).trim().trim_start_matches('v'))
+    }
+
+    pub fn execute_with_policy(command: &str, args: &[&str], policy: ToolPolicy) -> Result<Output, std::io::Error> {
+        if policy.hermetic {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::PermissionDenied,
+                format!(
+                    "External tool '{}' is not allowed in hermetic mode.\n   Command: {}\n💡 Drop --hermetic (or unset NSC_HERMETIC) to let nsc run external tools.",
+                    command,
+                    Self::command_line(command, args)
+                ),
+            ));
+        }
+
+        let mut attempt = 0;
+
+        loop {
+            match Self::execute_with_timeout(command, args, policy.timeout) {
+                Err(e) if e.kind() == std::io::ErrorKind::TimedOut && attempt < policy.retries => {
+                    attempt += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+
+    pub fn execute_node(script_path: &Path) -> Result<Output, std::io::Error> {
+        Self::execute_node_with_args(script_path, &[])
+    }
+
+    pub fn execute_node_with_args(script_path: &Path, node_args: &[String]) -> Result<Output, std::io::Error> {
+        let script = script_path.to_string_lossy();
+        let mut args: Vec<&str> = node_args.iter().map(|a| a.as_str()).collect();
+        args.push(&script);
+        Self::execute_command("node", &args)
+    }
+
+    pub fn command_line(command: &str, args: &[&str]) -> String {
+        std::iter::once(command)
+            .chain(args.iter().copied())
+            .map(|part| {
+                if part.is_empty() || part.contains(char::is_whitespace) {
+                    format!("\"{}\"", part)
+                } else {
+                    part.to_string()
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    fn execute_with_timeout(command: &str, args: &[&str], timeout: Option<Duration>) -> Result<Output, std::io::Error> {
+        let mut child = Command::new(command)
+            .args(args)
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()?;
+
+        let stdout = child.stdout.take();
+        let stderr = child.stderr.take();
+        let stdout_reader = thread::spawn(move || read_pipe(stdout));
+        let stderr_reader = thread::spawn(move || read_pipe(stderr));
+
+        let status = match timeout {
+            None => child.wait()?,
+            Some(limit) => {
+                let started = Instant::now();
+                loop {
+                    if let Some(status) = child.try_wait()? {
+                        break status;
+                    }
+
+                    if started.elapsed() >= limit {
+                        let _ = child.kill();
+                        let _ = child.wait();
+                        return Err(std::io::Error::new(
+                            std::io::ErrorKind::TimedOut,
+                            format!(
+                                "External tool '{}' did not finish within {}s and was killed.\n   Command: {}\n💡 Raise the limit with --tool-timeout <seconds> or NSC_TOOL_TIMEOUT.",
+                                command,
+                                limit.as_secs_f64(),
+                                Self::command_line(command, args)
+                            ),
+                        ));
+                    }
+
+                    thread::sleep(Duration::from_millis(10));
+                }
+            }
+        };
+
+        Ok(Output {
+            status,
+            stdout: stdout_reader.join().unwrap_or_default(),
+            stderr: stderr_reader.join().unwrap_or_default(),
+        })
+    }
+}
+
+fn read_pipe<R: Read>(pipe: Option<R>) -> Vec<u8> {
+    let mut buffer = Vec::new();
+    if let Some(mut pipe) = pipe {
+        let _ = pipe.read_to_end(&mut buffer);
+    }
+    buffer
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    fn policy(timeout_ms: u64, retries: u32) -> ToolPolicy {
+        ToolPolicy { timeout: Some(Duration::from_millis(timeout_ms)), retries, hermetic: false }
+    }
+
+    #[test]
+    fn test_timeout_kills_a_hanging_tool() {
+        let started = Instant::now();
+        let error = CommandUtils::execute_with_policy("sleep", &["5"], policy(200, 0)).unwrap_err();
+        assert!(started.elapsed() < Duration::from_secs(4));
+        assert_eq!(error.kind(), std::io::ErrorKind::TimedOut);
+        assert!(error.to_string().starts_with("External tool 'sleep' did not finish within 0.2s and was killed."));
+        assert!(error.to_string().contains("Command: sleep 5"));
+
+        let output = CommandUtils::execute_with_policy("sh", &["-c", "echo done"], policy(5000, 0)).unwrap();
+        assert_eq!(String::from_utf8_lossy(&output.stdout), "done\n");
+    }
+
+    #[test]
+    fn test_timed_out_tools_are_retried() {
+        let dir = tempfile::tempdir().unwrap();
+        let attempts = dir.path().join("attempts");
+        let script = format!("echo try >> '{}'; sleep 5", attempts.display());
+
+        let error = CommandUtils::execute_with_policy("sh", &["-c", &script], policy(200, 2)).unwrap_err();
+        assert_eq!(error.kind(), std::io::ErrorKind::TimedOut);
+        assert_eq!(std::fs::read_to_string(&attempts).unwrap().lines().count(), 3);
+
+        let failing = dir.path().join("failing");
+        let script = format!("echo try >> '{}'; exit 3", failing.display());
+        let output = CommandUtils::execute_with_policy("sh", &["-c", &script], policy(5000, 2)).unwrap();
+        assert_eq!(output.status.code(), Some(3));
+        assert_eq!(std::fs::read_to_string(&failing).unwrap().lines().count(), 1);
+    }
+
+    #[test]
+    fn test_hermetic_mode_never_starts_the_tool() {
+        let dir = tempfile::tempdir().unwrap();
+        let marker = dir.path().join("ran");
+        let script = format!("touch '{}'", marker.display());
+
+        let hermetic = ToolPolicy { hermetic: true, ..ToolPolicy::default() };
+        let error = CommandUtils::execute_with_policy("sh", &["-c", &script], hermetic).unwrap_err();
+        assert_eq!(error.kind(), std::io::ErrorKind::PermissionDenied);
+        assert!(error.to_string().starts_with("External tool 'sh' is not allowed in hermetic mode."));
+        assert!(!marker.exists());
+    }
 }
@@ -0,0 +1,282 @@
+use crate::core::NullScriptError;
+use rquickjs::context::EvalOptions;
+use rquickjs::{CatchResultExt, CaughtError, Context, Ctx, Function, Runtime, Value};
+use std::cell::RefCell;
+use std::io::Write;
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+
+const PRELUDE: &str = r#"
+(function () {
+  function format(value) {
+    if (typeof value === "string") return value;
+    if (value instanceof Error) return value.stack ? value.name + ": " + value.message + "\n" + value.stack : String(value);
+    if (value !== null && typeof value === "object") {
+      try { return JSON.stringify(value); } catch (error) { return String(value); }
+    }
+    return String(value);
+  }
+  function writer(stream) {
+    return function () { __nsc_write(stream, Array.prototype.map.call(arguments, format).join(" ")); };
+  }
+  var out = writer(1), err = writer(2);
+  globalThis.console = { log: out, info: out, debug: out, table: out, dir: out, warn: err, error: err, trace: err };
+
+  var timers = new Map();
+  function schedule(repeat) {
+    return function (callback, delay) {
+      var args = Array.prototype.slice.call(arguments, 2);
+      delay = Number(delay);
+      if (!(delay >= 1 && delay <= 2147483647)) delay = 1;
+      var id = __nsc_timer(delay, repeat);
+      timers.set(id, { callback: callback, args: args, repeat: repeat });
+      return id;
+    };
+  }
+  function clear(id) {
+    if (timers.delete(id)) __nsc_clear_timer(id);
+  }
+  globalThis.setTimeout = schedule(false);
+  globalThis.setInterval = schedule(true);
+  globalThis.clearTimeout = globalThis.clearInterval = clear;
+  globalThis.__nsc_fire = function (id) {
+    var timer = timers.get(id);
+    if (!timer) return;
+    if (!timer.repeat) timers.delete(id);
+    if (typeof timer.callback === "function") timer.callback.apply(undefined, timer.args);
+  };
+
+  var rejections = new Map();
+  globalThis.__nsc_reject = function (promise, reason, handled) {
+    if (handled) rejections.delete(promise);
+    else rejections.set(promise, reason);
+  };
+  globalThis.__nsc_unhandled = function () {
+    var reasons = Array.from(rejections.values());
+    rejections.clear();
+    if (!reasons.length) return undefined;
+    try { return String(reasons[0]); } catch (error) { return format(reasons[0]); }
+  };
+
+  var settled;
+  globalThis.__nsc_settle = function (value) {
+    settled = { value: value };
+    if (value && typeof value.then === "function") {
+      settled = undefined;
+      value.then(function (result) { settled = { value: result }; }, function (error) { settled = { error: error }; });
+    }
+  };
+  globalThis.__nsc_settled = function () {
+    var result = settled;
+    settled = undefined;
+    if (!result) return "Promise { <pending> }";
+    if ("error" in result) throw result.error;
+    if (result.value === undefined) return undefined;
+    return typeof result.value === "string" ? JSON.stringify(result.value) : format(result.value);
+  };
+})();
+"#;
+
+struct Timer {
+    id: u32,
+    due: Instant,
+    every: Option<Duration>,
+}
+
+#[derive(Default)]
+struct Timers {
+    next_id: u32,
+    pending: Vec<Timer>,
+}
+
+pub struct EngineSession {
+    runtime: Runtime,
+    context: Context,
+    timers: Rc<RefCell<Timers>>,
+}
+
+impl EngineSession {
+    pub fn new() -> Result<Self, NullScriptError> {
+        let runtime = Runtime::new().map_err(engine_error)?;
+        let context = Context::full(&runtime).map_err(engine_error)?;
+
+        runtime.set_host_promise_rejection_tracker(Some(Box::new(|ctx: Ctx, promise: Value, reason: Value, handled: bool| {
+            if let Ok(track) = ctx.globals().get::<_, Function>("__nsc_reject") {
+                let _ = track.call::<_, ()>((promise, reason, handled));
+            }
+        })));
+
+        let timers: Rc<RefCell<Timers>> = Rc::default();
+        context.with(|ctx| -> Result<(), NullScriptError> {
+            let write = Function::new(ctx.clone(), |stream: i32, text: String| match stream {
+                2 => eprintln!("{}", text),
+                _ => {
+                    println!("{}", text);
+                    let _ = std::io::stdout().flush();
+                }
+            })
+            .map_err(engine_error)?;
+            ctx.globals().set("__nsc_write", write).map_err(engine_error)?;
+
+            let queue = timers.clone();
+            let schedule = Function::new(ctx.clone(), move |delay: f64, repeat: bool| {
+                let mut timers = queue.borrow_mut();
+                timers.next_id += 1;
+                let delay = Duration::from_millis(delay as u64);
+                let id = timers.next_id;
+                timers.pending.push(Timer { id, due: Instant::now() + delay, every: repeat.then_some(delay) });
+                id
+            })
+            .map_err(engine_error)?;
+            ctx.globals().set("__nsc_timer", schedule).map_err(engine_error)?;
+
+            let queue = timers.clone();
+            let clear = Function::new(ctx.clone(), move |id: u32| queue.borrow_mut().pending.retain(|timer| timer.id != id))
+                .map_err(engine_error)?;
+            ctx.globals().set("__nsc_clear_timer", clear).map_err(engine_error)?;
+
+            ctx.eval::<(), _>(PRELUDE).map_err(engine_error)
+        })?;
+
+        Ok(Self { runtime, context, timers })
+    }
+
+    pub fn eval(&self, script: &str, name: &str) -> Result<Option<String>, NullScriptError> {
+        self.context.with(|ctx| -> Result<(), NullScriptError> {
+            let mut options = EvalOptions::default();
+            options.strict = false;
+            let value = ctx
+                .eval_with_options::<Value, _>(script, options)
+                .catch(&ctx)
+                .map_err(|error| uncaught(&error, name))?;
+            let settle: Function = ctx.globals().get("__nsc_settle").map_err(engine_error)?;
+            settle.call::<_, ()>((value,)).map_err(engine_error)
+        })?;
+
+        self.drain(name, false)?;
+
+        self.context.with(|ctx| {
+            ctx.eval::<Option<String>, _>("__nsc_settled()")
+                .catch(&ctx)
+                .map_err(|error| uncaught(&error, name))
+        })
+    }
+
+    /// Runs timers until none are left, sleeping until each one is due.
+    pub fn finish(&self, name: &str) -> Result<(), NullScriptError> {
+        self.drain(name, true)
+    }
+
+    fn drain(&self, name: &str, wait: bool) -> Result<(), NullScriptError> {
+        loop {
+            loop {
+                match self.runtime.execute_pending_job() {
+                    Ok(true) => continue,
+                    Ok(false) => break,
+                    Err(job) => {
+                        return Err(job.0.with(|ctx| uncaught(&CaughtError::from_error(&ctx, rquickjs::Error::Exception), name)));
+                    }
+                }
+            }
+
+            let unhandled = self.context.with(|ctx| ctx.eval::<Option<String>, _>("__nsc_unhandled()").map_err(engine_error))?;
+            if let Some(reason) = unhandled {
+                return Err(NullScriptError::Io(std::io::Error::other(format!("{}: Uncaught (in promise) {}", name, reason))));
+            }
+
+            let Some(id) = self.next_timer(wait) else {
+                return Ok(());
+            };
+            self.context.with(|ctx| -> Result<(), NullScriptError> {
+                let fire: Function = ctx.globals().get("__nsc_fire").map_err(engine_error)?;
+                fire.call::<_, ()>((id,)).catch(&ctx).map_err(|error| uncaught(&error, name))
+            })?;
+        }
+    }
+
+    fn next_timer(&self, wait: bool) -> Option<u32> {
+        let due = self.timers.borrow().pending.iter().map(|timer| (timer.due, timer.id)).min()?;
+        let now = Instant::now();
+        if due.0 > now {
+            if !wait {
+                return None;
+            }
+            std::thread::sleep(due.0 - now);
+        }
+
+        let mut timers = self.timers.borrow_mut();
+        let position = timers.pending.iter().position(|timer| timer.id == due.1)?;
+        match timers.pending[position].every {
+            Some(every) => timers.pending[position].due = Instant::now() + every,
+            None => {
+                timers.pending.remove(position);
+            }
+        }
+        Some(due.1)
+    }
+}
+
+pub struct EngineUtils;
+
+impl EngineUtils {
+    pub fn run(script: &str, name: &str) -> Result<(), NullScriptError> {
+        let session = EngineSession::new()?;
+        session.eval(script, name)?;
+        session.finish(name)
+    }
+}
+
+fn uncaught(error: &CaughtError, name: &str) -> NullScriptError {
+    NullScriptError::Io(std::io::Error::other(format!("{}: Uncaught {}", name, error.to_string().trim_end())))
+}
+
+fn engine_error(error: rquickjs::Error) -> NullScriptError {
+    NullScriptError::Io(std::io::Error::other(format!("builtin engine: {}", error)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builtin_engine_reports_uncaught_errors() {
+        assert!(EngineUtils::run("console.log('hi', { a: [1] }); Promise.resolve(1).then(function () {});", "ok.ns").is_ok());
+
+        let thrown = EngineUtils::run("throw new Error('bad')", "throw.ns").unwrap_err().to_string();
+        assert!(thrown.contains("throw.ns: Uncaught Error: bad"));
+
+        let rejected = EngineUtils::run("(async function () { throw new Error('later'); })(); void 0;", "async.ns").unwrap_err().to_string();
+        assert!(rejected.contains("async.ns: Uncaught (in promise) Error: later"));
+
+        let session = EngineSession::new().unwrap();
+        assert_eq!(session.eval("const answer = 41;", "repl").unwrap(), None);
+        assert_eq!(session.eval("Promise.resolve(answer + 1)", "repl").unwrap().as_deref(), Some("42"));
+        assert_eq!(session.eval("'hi'", "repl").unwrap().as_deref(), Some("\"hi\""));
+    }
+
+    #[test]
+    fn test_rejections_are_tracked_per_promise() {
+        let source = "var first = Promise.reject(new Error('same'));\nPromise.reject(new Error('same'));\nfirst.catch(function () {});\n";
+        let rejected = EngineUtils::run(source, "twice.ns").unwrap_err().to_string();
+        assert!(rejected.contains("twice.ns: Uncaught (in promise) Error: same"));
+
+        assert!(EngineUtils::run("var late = Promise.reject(1);\nsetTimeout(function () {}, 1);\nlate.catch(function () {});\n", "late.ns").is_ok());
+    }
+
+    #[test]
+    fn test_timers_run_after_pending_jobs() {
+        let session = EngineSession::new().unwrap();
+        let source = "var seen = [];\n\
+            setTimeout(function () { seen.push('late'); }, 200);\n\
+            setTimeout(function (tag) { seen.push(tag); }, 0, 'soon');\n\
+            Promise.resolve().then(function () { seen.push('job'); });\n\
+            var ticks = 0, interval = setInterval(function () { seen.push('tick'); if (++ticks === 3) clearInterval(interval); }, 1);\n\
+            clearTimeout(setTimeout(function () { seen.push('cleared'); }, 1));\n";
+        session.eval(source, "timers").unwrap();
+        session.finish("timers").unwrap();
+        assert_eq!(session.eval("seen.join()", "repl").unwrap().as_deref(), Some("\"job,soon,tick,tick,tick,late\""));
+
+        let thrown = EngineUtils::run("setTimeout(function () { throw new Error('tick'); }, 1);", "timer.ns").unwrap_err().to_string();
+        assert!(thrown.contains("timer.ns: Uncaught Error: tick"));
+    }
+}
@@ -43,6 +43,42 @@ impl FileUtils {
         }
     }
 
+    pub fn binary_reason(bytes: &[u8]) -> Option<&'static str> {
+        let sample = &bytes[..bytes.len().min(8000)];
+
+        if sample.contains(&0) {
+            return Some("contains NUL bytes (binary content)");
+        }
+
+        if std::str::from_utf8(bytes).is_err() {
+            return Some("is not valid UTF-8 text");
+        }
+
+        None
+    }
+
+    pub fn parse_size(value: &str) -> Result<u64, String> {
+        let value = value.trim();
+        let split = value
+            .find(|c: char| !c.is_ascii_digit() && c != '.')
+            .unwrap_or(value.len());
+        let (number, unit) = value.split_at(split);
+
+        let number: f64 = number
+            .parse()
+            .map_err(|_| format!("invalid size '{}'", value))?;
+
+        let multiplier = match unit.trim().to_ascii_uppercase().as_str() {
+            "" | "B" => 1u64,
+            "K" | "KB" => 1024,
+            "M" | "MB" => 1024 * 1024,
+            "G" | "GB" => 1024 * 1024 * 1024,
+            other => return Err(format!("unknown size unit '{}' (use B, KB, MB or GB)", other)),
+        };
+
+        Ok((number * multiplier as f64) as u64)
+    }
+
     pub fn is_nullscript_file(file_path: &Path) -> bool {
         Self::has_extension(file_path, "ns")
     }
@@ -0,0 +1,165 @@
+use crate::core::NullScriptError;
+use crate::utils::commands::CommandUtils;
+use std::collections::BTreeMap;
+use std::ops::RangeInclusive;
+use std::path::{Path, PathBuf};
+
+pub struct GitUtils;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Change {
+    Added,
+    Lines(Vec<RangeInclusive<u32>>),
+}
+
+impl Change {
+    pub fn contains(&self, line: u32) -> bool {
+        match self {
+            Change::Added => true,
+            Change::Lines(ranges) => ranges.iter().any(|range| range.contains(&line)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Revision {
+    pub commit: String,
+    pub branch: Option<String>,
+}
+
+impl GitUtils {
+    pub fn toplevel(path: &Path) -> Result<PathBuf, NullScriptError> {
+        let directory = if path.is_dir() { path } else { path.parent().unwrap_or(Path::new(".")) };
+        let directory = if directory.as_os_str().is_empty() { Path::new(".") } else { directory };
+        Ok(PathBuf::from(git(&["-C", &directory.to_string_lossy(), "rev-parse", "--show-toplevel"])?.trim()))
+    }
+
+    pub fn revision(path: &Path) -> Option<Revision> {
+        let root = Self::toplevel(path).ok()?;
+        let root = root.to_string_lossy();
+        let commit = git(&["-C", &root, "rev-parse", "--short", "HEAD"]).ok()?.trim().to_string();
+        let branch = git(&["-C", &root, "symbolic-ref", "--quiet", "--short", "HEAD"])
+            .ok()
+            .map(|branch| branch.trim().to_string())
+            .filter(|branch| !branch.is_empty());
+
+        Some(Revision { commit, branch })
+    }
+
+    pub fn first_parent_commits(root: &Path, from: &str, to: &str) -> Result<Vec<(String, String)>, NullScriptError> {
+        let range = format!("{}..{}", from, to);
+        let log = git(&["-C", &root.to_string_lossy(), "log", "--first-parent", "--reverse", "--format=%h%x00%s", &range, "--"])?;
+
+        Ok(log
+            .lines()
+            .filter_map(|line| line.split_once('\0'))
+            .map(|(commit, subject)| (commit.to_string(), subject.to_string()))
+            .collect())
+    }
+
+    pub fn tree_files(root: &Path, commit: &str, pathspec: &str) -> Result<Vec<String>, NullScriptError> {
+        let files = git(&["-C", &root.to_string_lossy(), "ls-tree", "-r", "-z", "--name-only", commit, "--", pathspec])?;
+        Ok(files.split('\0').filter(|file| !file.is_empty()).map(str::to_string).collect())
+    }
+
+    pub fn changed_files(root: &Path, from: &str, to: &str, pathspec: &str) -> Result<Vec<String>, NullScriptError> {
+        let files = git(&["-C", &root.to_string_lossy(), "diff", "-z", "--name-only", "--no-renames", from, to, "--", pathspec])?;
+        Ok(files.split('\0').filter(|file| !file.is_empty()).map(str::to_string).collect())
+    }
+
+    pub fn show(root: &Path, commit: &str, file: &str) -> Result<Option<String>, NullScriptError> {
+        let spec = format!("{}:{}", commit, file);
+        let exists = CommandUtils::execute_command("git", &["-C", &root.to_string_lossy(), "cat-file", "-e", &spec])?;
+        if !exists.status.success() {
+            return Ok(None);
+        }
+        git(&["-C", &root.to_string_lossy(), "show", &spec]).map(Some)
+    }
+
+    pub fn changed_lines(reference: &str, path: &Path) -> Result<BTreeMap<PathBuf, Change>, NullScriptError> {
+        let pathspec = dunce::canonicalize(path)?.to_string_lossy().to_string();
+
+        let root = Self::toplevel(path)?;
+        let root_arg = root.to_string_lossy();
+
+        let diff = git(&[
+            "-C", &root_arg, "diff", "--unified=0", "--no-color", "--no-ext-diff", "--no-renames", reference, "--", &pathspec,
+        ])?;
+        let mut changed = Self::parse_diff(&diff, &root);
+
+        let untracked = git(&["-C", &root_arg, "ls-files", "--others", "--exclude-standard", "--", &pathspec])?;
+        for file in untracked.lines().filter(|line| !line.is_empty()) {
+            changed.insert(root.join(file), Change::Added);
+        }
+
+        Ok(changed)
+    }
+
+    pub fn parse_diff(diff: &str, root: &Path) -> BTreeMap<PathBuf, Change> {
+        let mut changed = BTreeMap::new();
+        let mut new_file = false;
+        let mut current: Option<PathBuf> = None;
+
+        for line in diff.lines() {
+            if let Some(old) = line.strip_prefix("--- ") {
+                new_file = old == "/dev/null";
+            } else if let Some(new) = line.strip_prefix("+++ ") {
+                let new = new.trim_matches('"');
+                current = new.strip_prefix("b/").map(|file| root.join(file));
+                if let (Some(file), true) = (&current, new_file) {
+                    changed.insert(file.clone(), Change::Added);
+                }
+            } else if let (Some(hunk), Some(file)) = (line.strip_prefix("@@ "), &current) {
+                let Some(range) = added_range(hunk) else {
+                    continue;
+                };
+                if let Change::Lines(ranges) = changed.entry(file.clone()).or_insert_with(|| Change::Lines(Vec::new())) {
+                    ranges.push(range);
+                }
+            }
+        }
+
+        changed
+    }
+}
+
+fn added_range(hunk: &str) -> Option<RangeInclusive<u32>> {
+    let added = hunk.split_whitespace().find_map(|part| part.strip_prefix('+'))?;
+    let (start, count) = match added.split_once(',') {
+        Some((start, count)) => (start.parse::<u32>().ok()?, count.parse::<u32>().ok()?),
+        None => (added.parse::<u32>().ok()?, 1),
+    };
+
+    (count > 0).then(|| start..=start + count - 1)
+}
+
+fn git(args: &[&str]) -> Result<String, NullScriptError> {
+    let output = CommandUtils::execute_command("git", args)?;
+    if !output.status.success() {
+        return Err(NullScriptError::Io(std::io::Error::other(format!(
+            "{} failed: {}",
+            CommandUtils::command_line("git", args),
+            String::from_utf8_lossy(&output.stderr).trim()
+        ))));
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_diff_collects_added_lines() {
+        let diff = "diff --git a/src/app.ns b/src/app.ns\n--- a/src/app.ns\n+++ b/src/app.ns\n@@ -3 +3,2 @@ run main() {\n-old\n+new\n+more\n@@ -10,2 +11,0 @@\n-gone\n-gone\n@@ -20 +19 @@\n-a\n+b\n\
+diff --git a/src/new.ns b/src/new.ns\nnew file mode 100644\n--- /dev/null\n+++ b/src/new.ns\n@@ -0,0 +1,3 @@\n+a\n+b\n+c\n\
+diff --git a/src/old.ns b/src/old.ns\ndeleted file mode 100644\n--- a/src/old.ns\n+++ /dev/null\n@@ -1 +0,0 @@\n-x\n";
+
+        let changed = GitUtils::parse_diff(diff, Path::new("/repo"));
+        assert_eq!(changed.len(), 2);
+        assert_eq!(changed[Path::new("/repo/src/app.ns")], Change::Lines(vec![3..=4, 19..=19]));
+        assert_eq!(changed[Path::new("/repo/src/new.ns")], Change::Added);
+        assert!(changed[Path::new("/repo/src/app.ns")].contains(4) && !changed[Path::new("/repo/src/app.ns")].contains(11));
+    }
+}
@@ -0,0 +1,379 @@
+use crate::core::NullScriptError;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::path::Path;
+
+pub struct HeapEntry {
+    pub name: String,
+    pub count: usize,
+    pub self_size: u64,
+}
+
+pub struct HeapRetainer {
+    pub name: String,
+    pub self_size: u64,
+    pub retained_size: u64,
+}
+
+pub struct HeapSummary {
+    pub node_count: usize,
+    pub total_size: u64,
+    pub entries: Vec<HeapEntry>,
+    pub retainers: Vec<HeapRetainer>,
+}
+
+impl HeapSummary {
+    pub fn from_snapshot(snapshot_path: &Path) -> Result<Self, NullScriptError> {
+        let content = std::fs::read_to_string(snapshot_path)?;
+        let snapshot: Value = serde_json::from_str(&content)?;
+        Self::from_value(&snapshot)
+    }
+
+    pub fn from_value(snapshot: &Value) -> Result<Self, NullScriptError> {
+        let meta = &snapshot["snapshot"]["meta"];
+
+        let node_fields: Vec<&str> = meta["node_fields"]
+            .as_array()
+            .map(|fields| fields.iter().filter_map(|f| f.as_str()).collect())
+            .unwrap_or_default();
+
+        let node_types: Vec<&str> = meta["node_types"][0]
+            .as_array()
+            .map(|types| types.iter().filter_map(|t| t.as_str()).collect())
+            .unwrap_or_default();
+
+        let field_index = |name: &str| node_fields.iter().position(|f| *f == name);
+
+        let (Some(type_index), Some(name_index), Some(size_index), Some(edge_count_index)) =
+            (field_index("type"), field_index("name"), field_index("self_size"), field_index("edge_count"))
+        else {
+            return Err(invalid_snapshot("missing node field metadata"));
+        };
+
+        let edge_fields: Vec<&str> = meta["edge_fields"]
+            .as_array()
+            .map(|fields| fields.iter().filter_map(|f| f.as_str()).collect())
+            .unwrap_or_default();
+        let edge_types: Vec<&str> = meta["edge_types"][0]
+            .as_array()
+            .map(|types| types.iter().filter_map(|t| t.as_str()).collect())
+            .unwrap_or_default();
+        let edge_field = |name: &str| edge_fields.iter().position(|f| *f == name);
+        let (Some(edge_type_index), Some(to_node_index)) = (edge_field("type"), edge_field("to_node")) else {
+            return Err(invalid_snapshot("missing edge field metadata"));
+        };
+
+        let nodes = snapshot["nodes"]
+            .as_array()
+            .ok_or_else(|| invalid_snapshot("missing nodes array"))?;
+        let strings = snapshot["strings"]
+            .as_array()
+            .ok_or_else(|| invalid_snapshot("missing strings table"))?;
+        let edges = snapshot["edges"]
+            .as_array()
+            .ok_or_else(|| invalid_snapshot("missing edges array"))?;
+
+        let stride = node_fields.len();
+        let edge_stride = edge_fields.len();
+        let mut groups: HashMap<String, (usize, u64)> = HashMap::new();
+        let mut node_count = 0;
+        let mut total_size = 0u64;
+        let mut names: Vec<String> = Vec::new();
+        let mut sizes: Vec<u64> = Vec::new();
+        let mut synthetic: Vec<bool> = Vec::new();
+        let mut children: Vec<Vec<usize>> = Vec::new();
+        let mut edge_offset = 0;
+
+        for node in nodes.chunks(stride) {
+            if node.len() < stride {
+                break;
+            }
+
+            let edge_count = node[edge_count_index].as_u64().unwrap_or(0) as usize;
+            let mut targets = Vec::with_capacity(edge_count);
+            for edge in edges.get(edge_offset..).unwrap_or_default().chunks(edge_stride).take(edge_count) {
+                let edge_type = edge
+                    .get(edge_type_index)
+                    .and_then(|t| t.as_u64())
+                    .and_then(|t| edge_types.get(t as usize))
+                    .copied()
+                    .unwrap_or("");
+                if matches!(edge_type, "weak" | "shortcut") {
+                    continue;
+                }
+                if let Some(to_node) = edge.get(to_node_index).and_then(|t| t.as_u64()) {
+                    targets.push(to_node as usize / stride);
+                }
+            }
+            edge_offset += edge_count * edge_stride;
+            children.push(targets);
+
+            let node_type = node[type_index]
+                .as_u64()
+                .and_then(|t| node_types.get(t as usize))
+                .copied()
+                .unwrap_or("unknown");
+            let name = node[name_index]
+                .as_u64()
+                .and_then(|n| strings.get(n as usize))
+                .and_then(|s| s.as_str())
+                .unwrap_or("");
+            let self_size = node[size_index].as_u64().unwrap_or(0);
+
+            let group = match node_type {
+                "object" | "native" if !name.is_empty() => name.to_string(),
+                other => format!("({})", other),
+            };
+
+            names.push(group.clone());
+            sizes.push(self_size);
+            synthetic.push(matches!(node_type, "synthetic" | "hidden"));
+
+            let entry = groups.entry(group).or_insert((0, 0));
+            entry.0 += 1;
+            entry.1 += self_size;
+
+            node_count += 1;
+            total_size += self_size;
+        }
+
+        let mut entries: Vec<HeapEntry> = groups
+            .into_iter()
+            .map(|(name, (count, self_size))| HeapEntry { name, count, self_size })
+            .collect();
+        entries.sort_by(|a, b| b.self_size.cmp(&a.self_size).then_with(|| a.name.cmp(&b.name)));
+
+        let retained = retained_sizes(&children, &sizes);
+        let mut retainers: Vec<HeapRetainer> = retained
+            .iter()
+            .enumerate()
+            .skip(1)
+            .filter(|&(index, &size)| !synthetic[index] && size > 0)
+            .map(|(index, &retained_size)| HeapRetainer {
+                name: names[index].clone(),
+                self_size: sizes[index],
+                retained_size,
+            })
+            .collect();
+        retainers.sort_by(|a, b| b.retained_size.cmp(&a.retained_size).then_with(|| a.name.cmp(&b.name)));
+
+        Ok(Self {
+            node_count,
+            total_size,
+            entries,
+            retainers,
+        })
+    }
+
+    pub fn render(&self, source: &Path, limit: usize) -> String {
+        use crate::utils::files::FileUtils;
+
+        let mut output = String::new();
+        output.push_str("NullScript Memory Report\n");
+        output.push_str(&"=".repeat(60));
+        output.push('\n');
+        output.push_str(&format!("Script: {}\n", source.display()));
+        output.push_str(&format!("Heap objects: {}\n", self.node_count));
+        output.push_str(&format!("Total self size: {}\n\n", FileUtils::format_file_size(self.total_size)));
+        output.push_str(&format!("Top {} constructors by self size:\n", limit.min(self.entries.len())));
+        output.push_str(&format!("{:<36} {:>10} {:>12} {:>7}\n", "Constructor", "Count", "Self size", "Share"));
+        output.push_str(&"-".repeat(60));
+        output.push('\n');
+
+        for entry in self.entries.iter().take(limit) {
+            let share = if self.total_size > 0 {
+                entry.self_size as f64 * 100.0 / self.total_size as f64
+            } else {
+                0.0
+            };
+            output.push_str(&format!(
+                "{:<36} {:>10} {:>12} {:>6.1}%\n",
+                truncate_name(&entry.name),
+                entry.count,
+                FileUtils::format_file_size(entry.self_size),
+                share
+            ));
+        }
+
+        output.push_str(&format!("\nTop {} retainers by retained size:\n", limit.min(self.retainers.len())));
+        output.push_str(&format!("{:<36} {:>10} {:>12}\n", "Object", "Self size", "Retained"));
+        output.push_str(&"-".repeat(60));
+        output.push('\n');
+
+        for retainer in self.retainers.iter().take(limit) {
+            output.push_str(&format!(
+                "{:<36} {:>10} {:>12}\n",
+                truncate_name(&retainer.name),
+                FileUtils::format_file_size(retainer.self_size),
+                FileUtils::format_file_size(retainer.retained_size)
+            ));
+        }
+
+        output
+    }
+}
+
+fn truncate_name(name: &str) -> String {
+    if name.chars().count() > 34 {
+        format!("{}...", name.chars().take(31).collect::<String>())
+    } else {
+        name.to_string()
+    }
+}
+
+fn retained_sizes(children: &[Vec<usize>], sizes: &[u64]) -> Vec<u64> {
+    let count = children.len();
+    let mut retained = vec![0u64; count];
+    if count == 0 {
+        return retained;
+    }
+
+    let mut order: Vec<usize> = Vec::with_capacity(count);
+    let mut visited = vec![false; count];
+    let mut stack = vec![(0usize, 0usize)];
+    visited[0] = true;
+    while let Some((node, next)) = stack.last_mut() {
+        match children[*node].get(*next) {
+            Some(&child) => {
+                *next += 1;
+                if child < count && !visited[child] {
+                    visited[child] = true;
+                    stack.push((child, 0));
+                }
+            }
+            None => {
+                order.push(*node);
+                stack.pop();
+            }
+        }
+    }
+    order.reverse();
+
+    let mut rank = vec![usize::MAX; count];
+    for (position, &node) in order.iter().enumerate() {
+        rank[node] = position;
+    }
+    let mut predecessors: Vec<Vec<usize>> = vec![Vec::new(); count];
+    for &node in &order {
+        for &child in &children[node] {
+            if child < count && visited[child] {
+                predecessors[child].push(node);
+            }
+        }
+    }
+
+    let mut dominator = vec![usize::MAX; count];
+    dominator[0] = 0;
+    let mut changed = true;
+    while changed {
+        changed = false;
+        for &node in order.iter().skip(1) {
+            let mut candidate = usize::MAX;
+            for &predecessor in &predecessors[node] {
+                if dominator[predecessor] == usize::MAX {
+                    continue;
+                }
+                candidate = if candidate == usize::MAX {
+                    predecessor
+                } else {
+                    let (mut a, mut b) = (candidate, predecessor);
+                    while a != b {
+                        while rank[a] > rank[b] {
+                            a = dominator[a];
+                        }
+                        while rank[b] > rank[a] {
+                            b = dominator[b];
+                        }
+                    }
+                    a
+                };
+            }
+            if candidate != usize::MAX && dominator[node] != candidate {
+                dominator[node] = candidate;
+                changed = true;
+            }
+        }
+    }
+
+    for &node in order.iter().rev() {
+        retained[node] += sizes[node];
+        if node != 0 {
+            retained[dominator[node]] += retained[node];
+        }
+    }
+    retained
+}
+
+pub fn heap_hook_script(snapshot_path: &Path) -> Result<String, NullScriptError> {
+    let target = serde_json::to_string(&snapshot_path.to_string_lossy())?;
+    Ok(format!(
+        "const v8 = require('v8');\nprocess.on('exit', () => {{\n    v8.writeHeapSnapshot({});\n}});\n",
+        target
+    ))
+}
+
+fn invalid_snapshot(reason: &str) -> NullScriptError {
+    NullScriptError::Io(std::io::Error::new(
+        std::io::ErrorKind::InvalidData,
+        format!("Invalid heap snapshot: {}", reason),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn snapshot() -> Value {
+        json!({
+            "snapshot": {
+                "meta": {
+                    "node_fields": ["type", "name", "id", "self_size", "edge_count"],
+                    "node_types": [["hidden", "object", "synthetic"]],
+                    "edge_fields": ["type", "name_or_index", "to_node"],
+                    "edge_types": [["element", "property", "weak"]]
+                }
+            },
+            "nodes": [
+                2, 0, 1, 0, 2,
+                1, 1, 2, 10, 2,
+                1, 2, 3, 100, 0,
+                1, 3, 4, 5, 2,
+                1, 4, 5, 7, 0
+            ],
+            "edges": [
+                1, 0, 5,
+                1, 0, 15,
+                1, 0, 10,
+                1, 0, 20,
+                1, 0, 20,
+                2, 0, 10
+            ],
+            "strings": ["(GC roots)", "Cache", "Entry", "Other", "Shared"]
+        })
+    }
+
+    #[test]
+    fn test_groups_by_constructor() {
+        let summary = HeapSummary::from_value(&snapshot()).unwrap();
+        assert_eq!((summary.node_count, summary.total_size), (5, 122));
+        let names: Vec<(&str, u64)> = summary.entries.iter().map(|e| (e.name.as_str(), e.self_size)).collect();
+        assert_eq!(names, vec![("Entry", 100), ("Cache", 10), ("Shared", 7), ("Other", 5), ("(synthetic)", 0)]);
+    }
+
+    #[test]
+    fn test_top_retainers_follow_dominators() {
+        let summary = HeapSummary::from_value(&snapshot()).unwrap();
+        let retainers: Vec<(&str, u64, u64)> = summary
+            .retainers
+            .iter()
+            .map(|r| (r.name.as_str(), r.self_size, r.retained_size))
+            .collect();
+        assert_eq!(retainers, vec![("Cache", 10, 110), ("Entry", 100, 100), ("Shared", 7, 7), ("Other", 5, 5)]);
+
+        let report = summary.render(Path::new("app.ns"), 1);
+        assert!(report.contains("Top 1 retainers by retained size:"));
+        assert!(report.lines().any(|line| line.starts_with("Cache") && line.ends_with("110 B")));
+        assert!(!report.lines().any(|line| line.starts_with("Other")));
+    }
+}
@@ -1,3 +1,9 @@
 pub mod commands;
-pub mod strings;
+#[cfg(feature = "engine")]
+pub mod engine;
 pub mod files;
+pub mod git;
+pub mod heap;
+pub mod paths;
+pub mod strings;
+pub mod update;
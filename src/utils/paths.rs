@@ -0,0 +1,176 @@
+use std::path::{Component, Path, PathBuf};
+
+pub struct PathUtils;
+
+impl PathUtils {
+    pub fn normalize(path: &Path) -> PathBuf {
+        let mut normalized = PathBuf::new();
+
+        for component in dunce::simplified(path).components() {
+            match component {
+                Component::CurDir => {}
+                Component::ParentDir => match normalized.components().next_back() {
+                    Some(Component::Normal(_)) => {
+                        normalized.pop();
+                    }
+                    Some(Component::RootDir) | Some(Component::Prefix(_)) => {}
+                    _ => normalized.push(".."),
+                },
+                other => normalized.push(other.as_os_str()),
+            }
+        }
+
+        if normalized.as_os_str().is_empty() {
+            normalized.push(".");
+        }
+
+        normalized
+    }
+
+    pub fn absolute(path: &Path) -> PathBuf {
+        Self::normalize(&std::path::absolute(path).unwrap_or_else(|_| path.to_path_buf()))
+    }
+
+    pub fn relative_to(path: &Path, base: &Path) -> Option<PathBuf> {
+        let path = Self::normalize(path);
+        let base = Self::normalize(base);
+
+        if base == Path::new(".") && path.is_relative() {
+            return Some(path);
+        }
+
+        if let Ok(relative) = path.strip_prefix(&base) {
+            return Some(relative.to_path_buf());
+        }
+
+        Self::strip_prefix_ignore_case(&path, &base)
+    }
+
+    pub fn relative_path(path: &Path, from_dir: &Path) -> PathBuf {
+        let path = Self::normalize(path);
+        let from_dir = Self::normalize(from_dir);
+        let shared = path
+            .components()
+            .zip(from_dir.components())
+            .take_while(|(a, b)| a == b)
+            .count();
+
+        let mut relative = PathBuf::new();
+        for _ in from_dir.components().skip(shared) {
+            relative.push("..");
+        }
+        for component in path.components().skip(shared) {
+            relative.push(component.as_os_str());
+        }
+
+        if relative.as_os_str().is_empty() {
+            relative.push(".");
+        }
+
+        relative
+    }
+
+    pub fn output_path(input_root: &Path, source: &Path, out_dir: &Path, extension: &str) -> Option<PathBuf> {
+        let relative = Self::relative_to(source, input_root)?;
+        Some(out_dir.join(relative.with_extension(extension)))
+    }
+
+    pub fn single_output_path(source: &Path, out_dir: &Path, extension: &str) -> PathBuf {
+        let file_name = source.file_name().map(Path::new).unwrap_or(Path::new("output"));
+        out_dir.join(file_name.with_extension(extension))
+    }
+
+    pub fn to_slash(path: &Path) -> String {
+        let mut parts: Vec<String> = Vec::new();
+        let mut rooted = false;
+
+        for component in path.components() {
+            match component {
+                Component::Prefix(prefix) => parts.push(prefix.as_os_str().to_string_lossy().replace('\\', "/")),
+                Component::RootDir => rooted = true,
+                other => parts.push(other.as_os_str().to_string_lossy().to_string()),
+            }
+        }
+
+        let joined = if rooted && parts.first().is_some_and(|p| p.ends_with(':') || p.starts_with("//")) {
+            format!("{}/{}", parts[0], parts[1..].join("/"))
+        } else if rooted {
+            format!("/{}", parts.join("/"))
+        } else {
+            parts.join("/")
+        };
+
+        if joined.is_empty() { ".".to_string() } else { joined }
+    }
+
+    #[cfg(windows)]
+    fn strip_prefix_ignore_case(path: &Path, base: &Path) -> Option<PathBuf> {
+        let mut path_components = path.components();
+
+        for base_component in base.components() {
+            let path_component = path_components.next()?;
+            let left = path_component.as_os_str().to_string_lossy().to_lowercase();
+            let right = base_component.as_os_str().to_string_lossy().to_lowercase();
+            if left != right {
+                return None;
+            }
+        }
+
+        Some(path_components.as_path().to_path_buf())
+    }
+
+    #[cfg(not(windows))]
+    fn strip_prefix_ignore_case(_path: &Path, _base: &Path) -> Option<PathBuf> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_removes_dot_segments() {
+        assert_eq!(PathUtils::normalize(Path::new("./src/./lib/../main.ns")), PathBuf::from("src/main.ns"));
+        assert_eq!(PathUtils::normalize(Path::new("../shared/x.ns")), PathBuf::from("../shared/x.ns"));
+        assert_eq!(PathUtils::normalize(Path::new("./")), PathBuf::from("."));
+    }
+
+    #[test]
+    fn test_output_path_for_build() {
+        let output = PathUtils::output_path(Path::new("./src"), Path::new("src/app/main.ns"), Path::new("dist"), "js");
+        assert_eq!(output, Some(PathBuf::from("dist/app/main.js")));
+
+        let single = PathUtils::single_output_path(Path::new("src/main.test.ns"), Path::new("dist"), "js");
+        assert_eq!(single, PathBuf::from("dist/main.test.js"));
+    }
+
+    #[test]
+    fn test_relative_path_walks_up() {
+        assert_eq!(PathUtils::relative_path(Path::new("/p/src/app/util.ns"), Path::new("/p/src/pages")), PathBuf::from("../app/util.ns"));
+        assert_eq!(PathUtils::relative_path(Path::new("/p/src/util.ns"), Path::new("/p/src")), PathBuf::from("util.ns"));
+    }
+
+    #[test]
+    fn test_to_slash() {
+        assert_eq!(PathUtils::to_slash(Path::new("src/app/main.ns")), "src/app/main.ns");
+    }
+
+    #[cfg(windows)]
+    #[test]
+    fn test_windows_unc_and_drive_paths() {
+        let output = PathUtils::output_path(
+            Path::new(r"C:\project\src"),
+            Path::new(r"\\?\C:\project\src\app\main.ns"),
+            Path::new(r"C:\project\dist"),
+            "js",
+        );
+        assert_eq!(output, Some(PathBuf::from(r"C:\project\dist\app\main.js")));
+
+        let mixed_case = PathUtils::relative_to(Path::new(r"c:\Project\SRC\main.ns"), Path::new(r"C:\project\src"));
+        assert_eq!(mixed_case, Some(PathBuf::from("main.ns")));
+
+        assert_eq!(PathUtils::to_slash(Path::new(r"C:\project\src\main.ns")), "C:/project/src/main.ns");
+        assert_eq!(PathUtils::normalize(Path::new(r"C:src\..\lib\a.ns")), PathBuf::from(r"C:lib\a.ns"));
+    }
+}
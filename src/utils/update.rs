@@ -0,0 +1,170 @@
+use crate::core::NullScriptError;
+use crate::core::version::Version;
+use crate::utils::commands::CommandUtils;
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+use std::ffi::OsString;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+pub const LATEST_RELEASE_URL: &str = "https://api.github.com/repos/nullscript-lang/nullscript/releases/latest";
+pub const CHECKSUMS_ASSET: &str = "SHA256SUMS";
+
+#[derive(Debug, Deserialize)]
+pub struct Release {
+    pub tag_name: String,
+    pub assets: Vec<ReleaseAsset>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ReleaseAsset {
+    pub name: String,
+    pub browser_download_url: String,
+}
+
+impl Release {
+    pub fn fetch_latest() -> Result<Self, NullScriptError> {
+        let body = download(LATEST_RELEASE_URL)?;
+        Ok(serde_json::from_slice(&body)?)
+    }
+
+    pub fn version(&self) -> &str {
+        self.tag_name.trim_start_matches('v')
+    }
+
+    pub fn asset(&self, name: &str) -> Result<&ReleaseAsset, NullScriptError> {
+        self.assets
+            .iter()
+            .find(|asset| asset.name == name)
+            .ok_or_else(|| update_error(format!("Release {} has no {} asset", self.tag_name, name)))
+    }
+}
+
+pub fn is_newer(latest: &str, current: &str) -> bool {
+    match (Version::parse(latest), Version::parse(current)) {
+        (Some(latest), Some(current)) => latest > current,
+        _ => false,
+    }
+}
+
+pub fn platform_asset_name() -> String {
+    let extension = if cfg!(windows) { ".exe" } else { "" };
+    format!("nsc-{}-{}{}", std::env::consts::ARCH, std::env::consts::OS, extension)
+}
+
+pub fn download(url: &str) -> Result<Vec<u8>, NullScriptError> {
+    let output = CommandUtils::execute_command(
+        "curl",
+        &["-fsSL", "-H", "Accept: application/vnd.github+json", "-H", "User-Agent: nsc", url],
+    )?;
+
+    if !output.status.success() {
+        return Err(update_error(format!(
+            "Download of {} failed: {}",
+            url,
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+
+    Ok(output.stdout)
+}
+
+pub fn expected_checksum(checksums: &str, asset: &str) -> Option<String> {
+    checksums.lines().find_map(|line| {
+        let mut fields = line.split_whitespace();
+        let hash = fields.next()?;
+        let name = fields.next()?.trim_start_matches('*');
+        (name == asset).then(|| hash.to_ascii_lowercase())
+    })
+}
+
+pub fn verify_checksum(bytes: &[u8], expected: &str) -> Result<(), NullScriptError> {
+    let actual: String = Sha256::digest(bytes).iter().map(|b| format!("{:02x}", b)).collect();
+
+    if actual != expected {
+        return Err(update_error(format!(
+            "Checksum mismatch: expected {}, downloaded file has {}",
+            expected, actual
+        )));
+    }
+
+    Ok(())
+}
+
+pub fn replace_executable(
+    exe: &Path,
+    bytes: &[u8],
+    verify: impl Fn(&Path) -> bool,
+) -> Result<(), NullScriptError> {
+    let staged = sibling(exe, ".new");
+    let backup = sibling(exe, ".old");
+
+    fs::write(&staged, bytes)?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(&staged, fs::Permissions::from_mode(0o755))?;
+    }
+
+    if !verify(&staged) {
+        let _ = fs::remove_file(&staged);
+        return Err(update_error("The downloaded binary did not start; the current version was kept".to_string()));
+    }
+
+    fs::rename(exe, &backup)?;
+    if let Err(e) = fs::rename(&staged, exe) {
+        fs::rename(&backup, exe)?;
+        let _ = fs::remove_file(&staged);
+        return Err(e.into());
+    }
+
+    let _ = fs::remove_file(&backup);
+    Ok(())
+}
+
+fn sibling(path: &Path, suffix: &str) -> PathBuf {
+    let mut name = OsString::from(path.as_os_str());
+    name.push(suffix);
+    PathBuf::from(name)
+}
+
+fn update_error(message: String) -> NullScriptError {
+    NullScriptError::Io(std::io::Error::other(message))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_versions_and_checksums() {
+        assert!(is_newer("v2.1.0", "2.0.3"));
+        assert!(is_newer("3", "2.9.9"));
+        assert!(!is_newer("2.0.3", "2.0.3"));
+        assert!(!is_newer("2.0.3-beta.1", "2.0.3"));
+        assert!(!is_newer("latest", "2.0.3"));
+
+        let checksums = "0a1b  nsc-x86_64-windows.exe\nABCDEF *nsc-x86_64-linux\n";
+        assert_eq!(expected_checksum(checksums, "nsc-x86_64-linux").as_deref(), Some("abcdef"));
+        assert_eq!(expected_checksum(checksums, "nsc-aarch64-macos"), None);
+
+        let hello = "2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824";
+        assert!(verify_checksum(b"hello", hello).is_ok());
+        assert!(verify_checksum(b"hello!", hello).is_err());
+    }
+
+    #[test]
+    fn test_replace_executable_keeps_original_on_failed_verify() {
+        let dir = tempfile::tempdir().unwrap();
+        let exe = dir.path().join("nsc");
+        fs::write(&exe, "old").unwrap();
+
+        assert!(replace_executable(&exe, b"broken", |_| false).is_err());
+        assert_eq!(fs::read_to_string(&exe).unwrap(), "old");
+        assert!(!sibling(&exe, ".new").exists());
+
+        replace_executable(&exe, b"new", |_| true).unwrap();
+        assert_eq!(fs::read_to_string(&exe).unwrap(), "new");
+        assert!(!sibling(&exe, ".old").exists());
+    }
+}
@@ -0,0 +1,23 @@
+class Shape {
+
+
+  constructor(sides) {
+	this.sides = sides;
+  }
+
+	describe() {
+	  const labels = {
+		name() { return "shape"; }
+	  };
+	  return labels.name() + " with " + this.sides + " sides";
+	}
+}
+
+function area(width, height) {
+	function half(value) {
+		return value / 2;
+	}
+    return half(width * height) * 2;
+}
+
+console.log(new Shape(4).describe(), area(2, 3));
@@ -0,0 +1,7 @@
+class Greeter {
+    greet(name) {
+        return `hello ${name}`;
+    }
+}
+
+console.log(new Greeter().greet("world"))
\ No newline at end of file
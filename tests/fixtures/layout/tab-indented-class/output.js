@@ -0,0 +1,19 @@
+class Counter {
+
+
+	constructor(start) {
+		this.count = start;
+	}
+
+	increment() {
+		this.count += 1;
+		return this.count;
+	}
+
+	async reset(value) {
+		this.count = value;
+	}
+}
+
+const counter = new Counter(0);
+console.log(counter.increment());
@@ -0,0 +1,40 @@
+// Simple inventory tracker
+import { readFile } from "fs/promises";
+
+const DEFAULT_STOCK = 10;
+
+class Inventory {
+    constructor(items) {
+        this.items = new Map();
+        for (const item of items) {
+            this.items.set(item.name, item.count ?? DEFAULT_STOCK);
+        }
+    }
+
+    static async fromFile(path) {
+        const data = JSON.parse(await readFile(path, "utf8"));
+        return new Inventory(data);
+    }
+
+    take(name, amount) {
+        const current = this.items.get(name);
+        if (typeof current === "undefined") {
+            throw new Error(`Unknown item ${name}`);
+        } else if (current < amount) {
+            return false;
+        }
+        this.items.set(name, current - amount);
+        return true;
+    }
+}
+
+export function summary(inventory) {
+    let total = 0;
+    for (const [name, count] of inventory.items) {
+        console.log(`${name}: ${count}`);
+        total += count;
+    }
+    return total;
+}
+
+export default Inventory;
@@ -0,0 +1,19 @@
+import fs, { readFileSync as read } from 'fs';
+import * as path from 'path';
+import './polyfills.js';
+export { parse, format as print } from './format.js';
+export * from './helpers.js';
+export * as strings from './strings.js';
+export { default as Box } from './box.js';
+
+const limit = 10;
+export function load(file) {
+    switch (path.extname(file)) {
+        case '.json':
+            return JSON.parse(read(file, 'utf8'));
+        default:
+            return fs.readFileSync(file);
+    }
+}
+export { limit as maxFiles, limit };
+export default { load, limit };
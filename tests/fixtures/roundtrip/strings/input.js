@@ -0,0 +1,17 @@
+// function names in comments stay as written: const, let, class, return
+/* block comment: if (true) { return null; }   with   extra   spaces */
+const greeting = "function   returns   a   class";
+const single = 'console.log("new this") && while   true';
+const path = "C:\\new\\function\\return.js";
+const escaped = "say \"return\" then \"throw\"";
+const report = `class ${typeof greeting} says:   ${greeting.length > 0 ? "yes, return" : 'no'}   done`;
+const nested = `outer ${`inner ${"for  const"} let`} await`;
+const matcher = /function|return\s+this/g;
+function describe(value) {
+    // return early when value is null
+    if (value === null) {
+        return "null   or   undefined";
+    }
+    return `${value} is   ${typeof value}`;
+}
+console.log(greeting, single, path, escaped, report, nested, matcher.exec("return this"), describe(1));